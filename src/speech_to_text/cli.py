@@ -107,6 +107,20 @@ class ProgressDisplay:
     default=None,
     help='Include metadata in output files'
 )
+@click.option(
+    '--temperature',
+    type=click.FloatRange(0.0, 1.0),
+    help='Decoding temperature (0 decodes greedily)'
+)
+@click.option(
+    '--beam-size',
+    type=click.IntRange(1, 10),
+    help='Number of beams used in beam search'
+)
+@click.option(
+    '--initial-prompt',
+    help='Text that primes the transcription, e.g. names or domain vocabulary'
+)
 @click.option(
     '--quiet', '-q',
     is_flag=True,
@@ -136,6 +150,9 @@ def transcribe(
     batch: Optional[bool],
     recursive: Optional[bool],
     include_metadata: Optional[bool],
+    temperature: Optional[float],
+    beam_size: Optional[int],
+    initial_prompt: Optional[str],
     quiet: bool,
     verbose: bool,
     config: Optional[str],
@@ -236,6 +253,12 @@ def transcribe(
         # Initialize transcriber
         progress.start_operation(f"Loading Whisper model ({app_config.model_size})...")
         transcriber = SpeechTranscriber(model_size=app_config.model_size)
+        if temperature is not None:
+            transcriber.decode_options['temperature'] = temperature
+        if beam_size is not None:
+            transcriber.decode_options['beam_size'] = beam_size
+        if initial_prompt:
+            transcriber.decode_options['initial_prompt'] = initial_prompt
         
         # Process files
         if len(input_files) == 1:
@@ -58,6 +58,10 @@ class ModelCache:
             }
 
 
+# Language value that lets Whisper detect the spoken language itself
+AUTO_LANGUAGE = "auto"
+
+
 class SpeechTranscriber:
     """
     Speech-to-text transcriber using OpenAI Whisper models.
@@ -91,6 +95,8 @@ class SpeechTranscriber:
         
         self.model_size = model_size
         self.use_cache = use_cache
+        # Extra Whisper decoding options (temperature, beam_size, ...) applied to every file
+        self.decode_options: Dict[str, Any] = {}
         self._model = None
         self._model_cache = ModelCache() if use_cache else None
         self._load_model()
@@ -121,7 +127,8 @@ class SpeechTranscriber:
         
         Args:
             audio_path: Path to the audio file to transcribe
-            language: Language code for transcription (default: "ko" for Korean)
+            language: Language code for transcription (default: "ko" for Korean),
+                      or "auto" to detect it from the audio
             optimize_memory: Whether to optimize memory usage during transcription
             
         Returns:
@@ -139,10 +146,13 @@ class SpeechTranscriber:
         
         start_time = time.time()
         
+        # Whisper rejects "auto"; without a language it detects one from the audio
+        auto_detect = language is None or language.strip().lower() == AUTO_LANGUAGE
+        
         try:
             # Optimize transcription parameters for memory usage
             transcribe_options = {
-                "language": language,
+                "language": None if auto_detect else language,
                 "verbose": True  # Enable verbose output for progress tracking
             }
             
@@ -157,6 +167,8 @@ class SpeechTranscriber:
                         "no_speech_threshold": 0.6           # Skip silence
                     })
             
+            transcribe_options.update(self.decode_options)
+            
             # Perform transcription
             result = self._model.transcribe(audio_path, **transcribe_options)
             
@@ -168,6 +180,10 @@ class SpeechTranscriber:
             # Calculate confidence score using segment information if available
             confidence_score = self._calculate_confidence_score(result)
             
+            # Report the language Whisper detected in place of "auto"
+            if auto_detect:
+                language = result.get("language") or AUTO_LANGUAGE
+            
             # Force garbage collection for memory optimization
             if optimize_memory:
                 del result
@@ -1,9 +1,11 @@
 use crate::error::{AppError, AppResult};
-use crate::models::{AppSettings, ProcessingProgress, ProcessingStage, TranscriptionResult, TranscriptionMetadata, AudioInfo};
+use crate::models::{requires_ffmpeg, AppSettings, CLI_INPUT_FORMATS, CliLogLine, CliLogStream, ModelSize, OutputDirectoryMode, OutputFormat, PartialTranscription, ProcessingProgress, ProcessingStage, Segment, TranscriptionResult, TranscriptionMetadata, AudioFile, AudioInfo};
+use crate::transcription_cache::TranscriptionCache;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::process::Stdio;
 use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::time::{timeout, Duration};
 use tokio_util::sync::CancellationToken;
 use regex::Regex;
@@ -20,10 +22,388 @@ pub struct CliResult {
 /// Progress callback type
 pub type ProgressCallback = Arc<dyn Fn(ProcessingProgress) + Send + Sync>;
 
+/// Callback receiving segment text as the CLI transcribes it
+pub type PartialTextCallback = Arc<dyn Fn(PartialTranscription) + Send + Sync>;
+
+/// Callback receiving every line the CLI writes to stdout or stderr
+pub type LogLineCallback = Arc<dyn Fn(CliLogLine) + Send + Sync>;
+
+/// Callback told when a transcription process starts (`Some(file_path)`) and exits (`None`)
+pub type ProcessCallback = Arc<dyn Fn(u32, Option<&str>) + Send + Sync>;
+
+/// Callback told the output path reserved for a file before its CLI process starts
+pub type OutputPathCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Callback receiving each output line of a raw CLI command as it is written
+pub type OutputLineCallback = Arc<dyn Fn(CliLogStream, &str) + Send + Sync>;
+
+/// Reports a tracked process as exited when dropped, however processing ends
+struct ProcessGuard {
+    callback: ProcessCallback,
+    pid: u32,
+}
+
+impl Drop for ProcessGuard {
+    fn drop(&mut self) {
+        (self.callback)(self.pid, None);
+    }
+}
+
+/// Language value that asks Whisper to detect the spoken language
+pub const AUTO_LANGUAGE: &str = "auto";
+
+/// Option asking the CLI to label segments by speaker; the bundled Whisper CLI doesn't have it
+const DIARIZE_OPTION: &str = "--diarize";
+
+/// `--help` output of each CLI command probed so far, keyed by the resolved command
+static CLI_HELP: once_cell::sync::Lazy<std::sync::Mutex<std::collections::HashMap<String, String>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+
+/// Delay before the first retry of a failed CLI run; doubled for each further retry
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Run `operation` up to `retry_count + 1` times with exponential backoff between attempts.
+/// Cancellation, missing files, timed-out runs and failures that retrying can't fix fail immediately. `on_retry` receives the upcoming
+/// attempt number and the total number of attempts.
+pub async fn retry_with_backoff<T, F, Fut>(
+    retry_count: u32,
+    base_delay: Duration,
+    cancellation_token: Option<&CancellationToken>,
+    mut on_retry: impl FnMut(u32, u32),
+    mut operation: F,
+) -> AppResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = AppResult<T>>,
+{
+    let total_attempts = retry_count.saturating_add(1);
+    let mut attempt = 1;
+
+    loop {
+        let error = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        let cancelled = cancellation_token.is_some_and(|token| token.is_cancelled());
+        let permanent = matches!(
+            error,
+            AppError::FileNotFound(_)
+                | AppError::ModelNotFound(_)
+                | AppError::FfmpegMissing(_)
+                | AppError::AudioDecodeError(_)
+                | AppError::Timeout(_)
+        );
+        if attempt >= total_attempts || cancelled || permanent {
+            return Err(error);
+        }
+
+        let delay = base_delay.saturating_mul(2u32.saturating_pow(attempt - 1));
+        println!("🔥 Attempt {}/{} failed: {}. Retrying in {:?}", attempt, total_attempts, error, delay);
+
+        match cancellation_token {
+            Some(token) => tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = token.cancelled() => {
+                    return Err(AppError::ProcessingError("Processing was cancelled".to_string()));
+                }
+            },
+            None => tokio::time::sleep(delay).await,
+        }
+
+        attempt += 1;
+        on_retry(attempt, total_attempts);
+    }
+}
+
+/// Number of trailing stderr lines kept to explain a failed CLI run
+const STDERR_TAIL_LINES: usize = 50;
+
+/// How long to wait for the stderr reader to drain after the CLI exits
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Slack when comparing output modification times against the run start, for file
+/// systems that store coarse timestamps
+const OUTPUT_MTIME_SLACK: Duration = Duration::from_secs(2);
+
+/// Codec named by an FFmpeg "no decoder" error, e.g. "Decoder (codec amr_wb) not found for input stream #0:0"
+pub fn parse_missing_decoder(stderr: &str) -> Option<String> {
+    let decoder_regex = Regex::new(
+        r"(?i)decoder \(codec (\w+)\) not found|no decoder found for:?\s*(\w+)|unsupported codec with id \d+ for input stream|failed to find decoder for stream"
+    ).unwrap();
+
+    stderr.lines().rev().find_map(|line| {
+        let caps = decoder_regex.captures(line)?;
+        let codec = caps.get(1).or_else(|| caps.get(2)).map_or("unknown", |codec| codec.as_str());
+        Some(codec.to_string())
+    })
+}
+
+/// Map the stderr of a failed CLI run to the most specific error it matches.
+/// Unrecognized output falls back to `CliError` with the exit code.
+pub fn classify_cli_failure(stderr: &str, exit_code: Option<i32>) -> AppError {
+    // FFmpeg is installed but can't decode this codec
+    if let Some(codec) = parse_missing_decoder(stderr) {
+        return AppError::AudioDecodeError(format!(
+            "no decoder for codec '{}'. Convert the file to WAV or MP3 and try again",
+            codec
+        ));
+    }
+
+    type ErrorVariant = fn(String) -> AppError;
+    let patterns: [(&str, ErrorVariant); 3] = [
+        (
+            r"(?i)no such file or directory:? '?ffmpeg|ffmpeg(?:\.exe)?'? (?:is )?not (?:found|installed)|ffmpeg: (?:command )?not found|'ffmpeg' is not recognized",
+            AppError::FfmpegMissing,
+        ),
+        (
+            r"(?i)model \S+ not found|available models|checksum does not (?:not )?match|no such file or directory:? \S*\.pt\b",
+            AppError::ModelNotFound,
+        ),
+        (
+            r"(?i)failed to load audio|invalid data found when processing input|moov atom not found|could not find codec parameters|error while decoding",
+            AppError::AudioDecodeError,
+        ),
+    ];
+
+    for (pattern, variant) in patterns {
+        let regex = Regex::new(pattern).unwrap();
+        if let Some(line) = stderr.lines().find(|line| regex.is_match(line)) {
+            return variant(line.trim().to_string());
+        }
+    }
+
+    let last_line = stderr.lines().rev().map(str::trim).find(|line| !line.is_empty());
+    match last_line {
+        Some(line) => AppError::CliError(format!("CLI execution failed with exit code: {:?}: {}", exit_code, line)),
+        None => AppError::CliError(format!("CLI execution failed with exit code: {:?}", exit_code)),
+    }
+}
+
+/// Await the stderr reader and join the lines it kept
+async fn collect_stderr_tail(reader: tokio::task::JoinHandle<std::collections::VecDeque<String>>) -> String {
+    match timeout(STDERR_DRAIN_TIMEOUT, reader).await {
+        Ok(Ok(lines)) => lines.into_iter().collect::<Vec<_>>().join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Parts of a file's progress bar given to each phase, as (start, end) percentages. A model
+/// download gets only a sliver, since it happens once rather than for every file.
+pub const DOWNLOADING_PROGRESS_BAND: (f64, f64) = (0.0, 5.0);
+pub const PREPROCESSING_PROGRESS_BAND: (f64, f64) = (5.0, 15.0);
+pub const TRANSCRIBING_PROGRESS_BAND: (f64, f64) = (15.0, 95.0);
+pub const SAVING_PROGRESS_BAND: (f64, f64) = (95.0, 100.0);
+
+/// Audio length assumed when estimating progress from timestamps, since the total is unknown
+const ASSUMED_AUDIO_SECONDS: f64 = 300.0;
+
+/// Length of the clip `run_self_test` transcribes
+const SELF_TEST_SAMPLE_SECONDS: u32 = 2;
+
+/// Map a phase's own 0-100% onto its band of the overall progress bar
+pub fn progress_in_band(percent: f64, band: (f64, f64)) -> f64 {
+    band.0 + (band.1 - band.0) * percent.clamp(0.0, 100.0) / 100.0
+}
+
+/// Minimum time between progress events parsed from CLI output
+pub const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Rate-limits progress events. Updates arriving within the interval are coalesced into the
+/// latest one; stage changes and 100% progress are always emitted immediately.
+pub struct ProgressThrottle {
+    callback: ProgressCallback,
+    interval: Duration,
+    state: std::sync::Mutex<ThrottleState>,
+}
+
+#[derive(Default)]
+struct ThrottleState {
+    last_emit: Option<std::time::Instant>,
+    last_stage: Option<ProcessingStage>,
+    pending: Option<ProcessingProgress>,
+}
+
+impl ProgressThrottle {
+    pub fn new(callback: ProgressCallback, interval: Duration) -> Self {
+        Self {
+            callback,
+            interval,
+            state: std::sync::Mutex::new(ThrottleState::default()),
+        }
+    }
+
+    /// Emit `progress` now if allowed, otherwise keep it as the pending update
+    pub fn emit(&self, progress: ProcessingProgress) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        let now = std::time::Instant::now();
+        let due = state.last_emit.is_none_or(|last| now.duration_since(last) >= self.interval);
+        let stage_changed = state.last_stage.as_ref() != Some(&progress.stage);
+        if !(due || stage_changed || progress.progress >= 100.0) {
+            state.pending = Some(progress);
+            return;
+        }
+
+        state.last_emit = Some(now);
+        state.last_stage = Some(progress.stage.clone());
+        state.pending = None;
+        drop(state);
+        (self.callback)(progress);
+    }
+
+    /// Emit the pending update, if any
+    pub fn flush(&self) {
+        let pending = self.state.lock().ok().and_then(|mut state| {
+            state.last_emit = Some(std::time::Instant::now());
+            state.pending.take()
+        });
+        if let Some(progress) = pending {
+            (self.callback)(progress);
+        }
+    }
+
+    /// Callback that routes progress through `throttle`
+    pub fn callback(throttle: &Arc<Self>) -> ProgressCallback {
+        let throttle = throttle.clone();
+        Arc::new(move |progress| throttle.emit(progress))
+    }
+}
+
+/// Longest a post-processing command may run before it is killed
+const POST_PROCESS_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Environment variable holding the transcription path for post-processing commands
+pub const POST_PROCESS_PATH_ENV: &str = "TRANSCRIPTION_PATH";
+
+/// Run a user post-processing command with the transcription path appended as its last
+/// argument and exported as `TRANSCRIPTION_PATH`. Returns the command's stdout.
+pub async fn run_post_process_command(command: &str, output_path: &str, time_limit: Duration) -> AppResult<String> {
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(command).arg(output_path);
+        cmd
+    };
+
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+        // "$@" forwards the output path as a single argument, whatever it contains
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(format!("{} \"$@\"", command)).arg("post-process").arg(output_path);
+        cmd
+    };
+
+    cmd.env(POST_PROCESS_PATH_ENV, output_path)
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+
+    let output = timeout(time_limit, cmd.output())
+        .await
+        .map_err(|_| AppError::ProcessingError(format!("Post-processing command timed out after {:?}", time_limit)))?
+        .map_err(|e| AppError::ProcessingError(format!("Failed to run post-processing command: {}", e)))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(AppError::ProcessingError(format!(
+            "Post-processing command failed with exit code {:?}: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Caps the number of CLI child processes running at the same time
+pub struct ConcurrencyLimiter {
+    semaphore: Arc<Semaphore>,
+    state: std::sync::Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    limit: usize,
+    // Permits still to be retired once running jobs release them after the limit shrank
+    pending_reduction: usize,
+}
+
+/// Permit held while a CLI child process is running
+pub struct ConcurrencyPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    limiter: Arc<ConcurrencyLimiter>,
+}
+
+impl ConcurrencyLimiter {
+    /// Create a limiter allowing `limit` concurrent processes (at least one)
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            state: std::sync::Mutex::new(LimiterState { limit, pending_reduction: 0 }),
+        }
+    }
+
+    /// Current concurrency limit
+    pub fn limit(&self) -> usize {
+        self.state.lock().map(|state| state.limit).unwrap_or(1)
+    }
+
+    /// Change the concurrency limit; running processes are never interrupted
+    pub fn set_limit(&self, limit: usize) {
+        let limit = limit.max(1);
+        let Ok(mut state) = self.state.lock() else { return };
+
+        if limit > state.limit {
+            let increase = limit - state.limit;
+            let cancelled = increase.min(state.pending_reduction);
+            state.pending_reduction -= cancelled;
+            self.semaphore.add_permits(increase - cancelled);
+        } else if limit < state.limit {
+            let reduction = state.limit - limit;
+            let forgotten = self.semaphore.forget_permits(reduction);
+            state.pending_reduction += reduction - forgotten;
+        }
+
+        state.limit = limit;
+    }
+
+    /// Wait for a free slot
+    pub async fn acquire(self: &Arc<Self>) -> AppResult<ConcurrencyPermit> {
+        let permit = self.semaphore.clone().acquire_owned().await
+            .map_err(|e| AppError::ProcessingError(format!("Concurrency limiter closed: {}", e)))?;
+
+        Ok(ConcurrencyPermit {
+            permit: Some(permit),
+            limiter: self.clone(),
+        })
+    }
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            if let Ok(mut state) = self.limiter.state.lock() {
+                if state.pending_reduction > 0 {
+                    state.pending_reduction -= 1;
+                    permit.forget();
+                }
+            }
+        }
+    }
+}
+
 /// CLI integration manager
 pub struct CliManager {
     use_sidecar: bool,
     timeout_duration: Duration,
+    concurrency_limiter: Option<Arc<ConcurrencyLimiter>>,
+    partial_text_callback: Option<PartialTextCallback>,
+    log_line_callback: Option<LogLineCallback>,
+    process_callback: Option<ProcessCallback>,
+    output_path_callback: Option<OutputPathCallback>,
+    cli_binary_path: Option<String>,
+    transcription_cache: Option<TranscriptionCache>,
 }
 
 impl Default for CliManager {
@@ -31,6 +411,13 @@ impl Default for CliManager {
         Self {
             use_sidecar: true, // Always try sidecar first in production
             timeout_duration: Duration::from_secs(3600), // 1 hour timeout
+            concurrency_limiter: None,
+            partial_text_callback: None,
+            log_line_callback: None,
+            process_callback: None,
+            output_path_callback: None,
+            cli_binary_path: None,
+            transcription_cache: None,
         }
     }
 }
@@ -46,18 +433,164 @@ impl CliManager {
         Self {
             use_sidecar: false,
             timeout_duration: Duration::from_secs(3600),
+            concurrency_limiter: None,
+            partial_text_callback: None,
+            log_line_callback: None,
+            process_callback: None,
+            output_path_callback: None,
+            cli_binary_path: None,
+            transcription_cache: None,
         }
     }
 
-    /// Set timeout duration for CLI operations
+    /// Set timeout duration for CLI operations; `Duration::ZERO` disables the timeout
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout_duration = timeout;
         self
     }
 
+    /// Timeout applied to CLI processes, or `None` when unbounded
+    pub fn timeout(&self) -> Option<Duration> {
+        Some(self.timeout_duration).filter(|duration| !duration.is_zero())
+    }
+
+    /// Await `future` within the processing timeout, if one is set
+    async fn with_process_timeout<F: std::future::Future>(&self, future: F) -> Result<F::Output, tokio::time::error::Elapsed> {
+        match self.timeout() {
+            Some(duration) => timeout(duration, future).await,
+            None => Ok(future.await),
+        }
+    }
+
+    /// Share a concurrency limiter that caps simultaneous CLI processes
+    pub fn with_concurrency_limiter(mut self, limiter: Arc<ConcurrencyLimiter>) -> Self {
+        self.concurrency_limiter = Some(limiter);
+        self
+    }
+
+    /// Stream transcribed segment text to `callback` while files are processed
+    pub fn with_partial_text_callback(mut self, callback: PartialTextCallback) -> Self {
+        self.partial_text_callback = Some(callback);
+        self
+    }
+
+    /// Forward raw CLI output lines to `callback` while files are processed
+    pub fn with_log_line_callback(mut self, callback: LogLineCallback) -> Self {
+        self.log_line_callback = Some(callback);
+        self
+    }
+
+    /// Report the PID of each transcription process to `callback` when it starts and exits
+    pub fn with_process_callback(mut self, callback: ProcessCallback) -> Self {
+        self.process_callback = Some(callback);
+        self
+    }
+
+    /// Report each file's output path to `callback` once it is reserved, before the CLI runs
+    pub fn with_output_path_callback(mut self, callback: OutputPathCallback) -> Self {
+        self.output_path_callback = Some(callback);
+        self
+    }
+
+    /// Report `child` as running until the returned guard is dropped
+    fn track_process(&self, child: &tokio::process::Child, file_path: &str) -> Option<ProcessGuard> {
+        let callback = self.process_callback.clone()?;
+        let pid = child.id()?;
+        callback(pid, Some(file_path));
+        Some(ProcessGuard { callback, pid })
+    }
+
+    /// Run a specific CLI binary instead of the sidecar or development lookup
+    pub fn with_cli_binary_path(mut self, path: impl Into<String>) -> Self {
+        self.cli_binary_path = Some(path.into());
+        self
+    }
+
+    /// Reuse results for files already transcribed with the same settings
+    pub fn with_transcription_cache(mut self, cache: TranscriptionCache) -> Self {
+        self.transcription_cache = Some(cache);
+        self
+    }
+
+    /// Whether to run the bundled sidecar; a custom binary, once validated, takes precedence
+    fn should_use_sidecar(&self) -> AppResult<bool> {
+        match self.cli_binary_path {
+            Some(ref path) => {
+                crate::settings::SettingsValidator::validate_cli_binary_path(path)?;
+                Ok(false)
+            }
+            None => Ok(self.use_sidecar),
+        }
+    }
+
+    /// CLI command for non-sidecar execution
+    fn dev_cli_command(&self) -> String {
+        self.cli_binary_path
+            .as_ref()
+            .map(|path| path.trim().to_string())
+            .unwrap_or_else(|| self.find_dev_cli_command())
+    }
+
+    /// Path or command that will be run for transcription
+    pub fn resolved_cli_path(&self) -> AppResult<String> {
+        if self.should_use_sidecar()? {
+            self.find_sidecar_path()
+        } else {
+            Ok(self.dev_cli_command())
+        }
+    }
+
+    /// Whether the resolved CLI lists `option` in its `--help` output. Probed once per CLI;
+    /// a CLI that can't be run supports nothing.
+    pub async fn cli_supports_option(&self, option: &str) -> bool {
+        let Ok(cli_path) = self.resolved_cli_path() else {
+            return false;
+        };
+        let cached = CLI_HELP.lock().ok().and_then(|help| help.get(&cli_path).cloned());
+        let help = match cached {
+            Some(help) => help,
+            None => {
+                let output = timeout(
+                    Duration::from_secs(10),
+                    tokio::process::Command::new(&cli_path).arg("--help").output(),
+                ).await;
+                let Ok(Ok(output)) = output else {
+                    return false;
+                };
+                if !output.status.success() {
+                    return false;
+                }
+                let help = String::from_utf8_lossy(&output.stdout).to_string();
+                if let Ok(mut cache) = CLI_HELP.lock() {
+                    cache.insert(cli_path, help.clone());
+                }
+                help
+            }
+        };
+
+        help.split(|c: char| c.is_whitespace() || c == ',' || c == '=' || c == '/')
+            .any(|word| word == option)
+    }
+
+    /// The CLI that would run, prefixed with how it was chosen, for diagnostics.
+    /// Lookup failures are described rather than returned.
+    pub fn describe_resolved_cli(&self) -> String {
+        match self.cli_binary_path {
+            Some(ref path) => match crate::settings::SettingsValidator::validate_cli_binary_path(path) {
+                Ok(()) => format!("custom binary: {}", path.trim()),
+                Err(e) => format!("custom binary (unusable): {}", e),
+            },
+            None if self.use_sidecar => match self.find_sidecar_path() {
+                Ok(path) => format!("sidecar: {}", path),
+                Err(e) => format!("sidecar (not found): {}", e),
+            },
+            None => format!("development: {}", self.find_dev_cli_command()),
+        }
+    }
+
     /// Check if the CLI is available and working
     pub async fn check_cli_availability(&self) -> AppResult<bool> {
-        if self.use_sidecar {
+        if self.should_use_sidecar()? {
             // Use bundled sidecar - find the correct path
             let cli_path = self.find_sidecar_path()?;
             let output = tokio::process::Command::new(&cli_path)
@@ -151,7 +684,7 @@ impl CliManager {
     async fn check_cli_availability_dev(&self) -> AppResult<bool> {
         use tokio::process::Command as AsyncCommand;
         
-        let cli_command = self.find_dev_cli_command();
+        let cli_command = self.dev_cli_command();
         let output = timeout(
             Duration::from_secs(10),
             AsyncCommand::new(&cli_command)
@@ -192,7 +725,7 @@ impl CliManager {
 
     /// Get CLI version information
     pub async fn get_cli_version(&self) -> AppResult<String> {
-        if self.use_sidecar {
+        if self.should_use_sidecar()? {
             let cli_path = self.find_sidecar_path()?;
             let output = tokio::process::Command::new(&cli_path)
                 .arg("--version")
@@ -216,7 +749,7 @@ impl CliManager {
     async fn get_cli_version_dev(&self) -> AppResult<String> {
         use tokio::process::Command as AsyncCommand;
         
-        let cli_command = self.find_dev_cli_command();
+        let cli_command = self.dev_cli_command();
         let output = timeout(
             Duration::from_secs(10),
             AsyncCommand::new(&cli_command)
@@ -245,7 +778,8 @@ impl CliManager {
         self.process_file_with_cancellation(file_path, settings, progress_callback, None).await
     }
 
-    /// Process a single audio file with cancellation support
+    /// Process a single audio file with cancellation support. Each CLI run is limited to
+    /// `per_file_timeout_secs`, which doesn't count time spent waiting for a free slot or converting.
     pub async fn process_file_with_cancellation(
         &self,
         file_path: &str,
@@ -254,29 +788,50 @@ impl CliManager {
         cancellation_token: Option<CancellationToken>,
     ) -> AppResult<TranscriptionResult> {
         println!("🔥 process_file_with_cancellation started with: {}", file_path);
+        let resolved_path = crate::utils::resolve_file_path(file_path);
+        let file_path = resolved_path.as_str();
+        let settings = &settings.resolved_for_file(file_path);
 
         // Validate file exists
         if !std::path::Path::new(file_path).exists() {
             println!("🔥 File not found: {}", file_path);
             return Err(AppError::FileNotFound(file_path.to_string()));
         }
+        crate::utils::validate_file_size(file_path, settings.max_file_size_mb)?;
         println!("🔥 File exists, building CLI command");
 
-        // Build CLI command arguments - back to original approach
-        let mut args = vec![
-            file_path.to_string(),
-            "--language".to_string(), 
-            settings.language.clone(),
-            "--model-size".to_string(), 
-            settings.model_size.to_string(),
-        ];
-
-        println!("🔥 CLI command args: {:?}", args);
-
-        if settings.include_metadata {
-            args.push("--include-metadata".to_string());
+        // Identical content with the same settings has already been transcribed
+        let cache_entry = match self.transcription_cache {
+            Some(ref cache) => Self::hashed_audio_file(file_path).await.map(|audio_file| (cache, audio_file)),
+            None => None,
+        };
+        // An entry whose transcript was since moved or deleted is a miss, since there'd be nothing to open
+        let cached = cache_entry
+            .as_ref()
+            .and_then(|(cache, audio_file)| cache.get(audio_file, settings))
+            .filter(|cached| std::path::Path::new(&cached.output_path).is_file());
+        if let Some(mut cached) = cached {
+            println!("🔥 Loaded from cache: {}", file_path);
+            if let Some(ref callback) = progress_callback {
+                callback(ProcessingProgress {
+                    stage: ProcessingStage::Saving,
+                    progress: 100.0,
+                    current_file: Some(file_path.to_string()),
+                    timestamp: Utc::now(),
+                    message: Some("Loaded from cache".to_string()),
+                    job_id: None,
+                    file_index: None,
+                    total_files: None,
+                    can_cancel: false,
+                    estimated_completion: None,
+                });
+            }
+            self.finish_result(&mut cached, file_path, settings).await;
+            return Ok(cached);
         }
 
+        let use_sidecar = self.should_use_sidecar()?;
+
         // Check for cancellation before starting
         if let Some(ref token) = cancellation_token {
             if token.is_cancelled() {
@@ -296,33 +851,374 @@ impl CliManager {
                 file_index: None,
                 total_files: None,
                 can_cancel: cancellation_token.is_some(),
+                estimated_completion: None,
             });
         }
 
-        // Execute command with timeout
+        // Wait for a free slot so the total number of CLI processes stays under the cap
+        let _permit = match self.concurrency_limiter {
+            Some(ref limiter) => {
+                limiter.set_limit(settings.resolved_concurrent_jobs());
+                match cancellation_token {
+                    Some(ref token) => tokio::select! {
+                        permit = limiter.acquire() => Some(permit?),
+                        _ = token.cancelled() => {
+                            return Err(AppError::ProcessingError("Processing was cancelled".to_string()));
+                        }
+                    },
+                    None => Some(limiter.acquire().await?),
+                }
+            }
+            None => None,
+        };
+
+        // Otherwise the output search could find what an earlier run of this file left behind
+        if settings.clean_workdir_before_run {
+            let base_name = std::path::Path::new(file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("output");
+            match crate::utils::clear_stale_outputs(&crate::utils::work_directory(), base_name) {
+                Ok(report) if report.files_removed > 0 => {
+                    println!("🧹 Removed {} stale outputs for {}", report.files_removed, base_name);
+                }
+                Ok(_) => {}
+                Err(e) => println!("⚠️ Failed to clean working directory: {}", e),
+            }
+        }
+
+        // The CLI goes by the suffix, so a file recognized from its contents runs under the detected extension
+        let detected_input = crate::utils::DetectedFormatInput::create(file_path, &crate::utils::work_directory().join("inputs"))?;
+        let input_path = detected_input.as_ref().map_or(file_path, |input| input.path());
+
+        let transcoded_path = if Self::needs_wav_conversion(input_path, settings) {
+            if let Some(ref callback) = progress_callback {
+                callback(ProcessingProgress {
+                    stage: ProcessingStage::Preprocessing,
+                    progress: 0.0,
+                    current_file: Some(file_path.to_string()),
+                    timestamp: Utc::now(),
+                    message: Some("Converting to WAV...".to_string()),
+                    job_id: None,
+                    file_index: None,
+                    total_files: None,
+                    can_cancel: cancellation_token.is_some(),
+                    estimated_completion: None,
+                });
+            }
+            let transcode_dir = dirs::cache_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join("SpeechToText")
+                .join("transcoded");
+            Some(crate::utils::transcode_to_wav(file_path, &transcode_dir.to_string_lossy()).await?)
+        } else {
+            None
+        };
+        let source_path = transcoded_path.as_deref().unwrap_or(input_path);
+
+        // The final name is reserved before the CLI runs, and the CLI writes into a folder of its
+        // own, so concurrent runs for same-named files can't overwrite each other's output
+        let output_reservation = match settings.output_directory.trim() {
+            "" => None,
+            output_directory => Some(crate::utils::reserve_output_filename(file_path, output_directory, settings)?),
+        };
+        if let (Some(reservation), Some(callback)) = (&output_reservation, &self.output_path_callback) {
+            callback(&reservation.path().to_string_lossy());
+        }
+        let run_output_dir = match output_reservation {
+            Some(_) => Some(crate::utils::ScratchDirectory::create(&crate::utils::work_directory().join("runs"))?),
+            None => None,
+        };
+        let run_settings = match run_output_dir {
+            Some(ref directory) => AppSettings {
+                output_directory: directory.path().to_string_lossy().to_string(),
+                ..settings.clone()
+            },
+            None => settings.clone(),
+        };
+        let run_settings = &run_settings;
+
+        let mut args = Self::build_cli_args(source_path, run_settings);
+        // Passing an option the CLI doesn't know would fail every run
+        let diarization_unsupported = settings.enable_diarization && !self.cli_supports_option(DIARIZE_OPTION).await;
+        if diarization_unsupported {
+            args.retain(|arg| arg != DIARIZE_OPTION);
+        }
+        println!("🔥 CLI command args: {:?}", args);
+
+        // Execute command with timeout, retrying transient failures
         let start_time = std::time::Instant::now();
         println!("🔥 About to spawn CLI process");
 
-        if self.use_sidecar {
-            // Use Tauri sidecar
-            self.process_with_sidecar(args, file_path, settings, progress_callback, cancellation_token, start_time).await
-        } else {
-            // Use development CLI
-            self.process_with_dev_cli(args, file_path, settings, progress_callback, cancellation_token, start_time).await
+        let on_retry = |attempt: u32, total_attempts: u32| {
+            if let Some(ref callback) = progress_callback {
+                callback(ProcessingProgress {
+                    stage: ProcessingStage::Initializing,
+                    progress: 0.0,
+                    current_file: Some(file_path.to_string()),
+                    timestamp: Utc::now(),
+                    message: Some(format!("Retrying ({}/{})...", attempt, total_attempts)),
+                    job_id: None,
+                    file_index: None,
+                    total_files: None,
+                    can_cancel: cancellation_token.is_some(),
+                    estimated_completion: None,
+                });
+            }
+        };
+
+        let outcome = retry_with_backoff(settings.retry_count, RETRY_BASE_DELAY, cancellation_token.as_ref(), on_retry, || {
+            let args = args.clone();
+            let progress_callback = progress_callback.clone();
+            let cancellation_token = cancellation_token.clone();
+            async move {
+                let run = async {
+                    if use_sidecar {
+                        // Use Tauri sidecar
+                        self.process_with_sidecar(args, source_path, run_settings, progress_callback, cancellation_token, start_time).await
+                    } else {
+                        // Use development CLI
+                        self.process_with_dev_cli(args, source_path, run_settings, progress_callback, cancellation_token, start_time).await
+                    }
+                };
+                Self::with_per_file_timeout(file_path, settings, run).await
+            }
+        }).await;
+
+        // The converted WAV is only an intermediate; report the file that was asked for
+        let mut result = match transcoded_path {
+            Some(ref transcoded_path) => {
+                let _ = std::fs::remove_file(transcoded_path);
+                let mut result = outcome?;
+                result.original_file = crate::utils::create_audio_file(file_path)?;
+                result.warnings.push("Converted to WAV with FFmpeg before transcribing".to_string());
+                result
+            }
+            None => outcome?,
+        };
+        if detected_input.is_some() && transcoded_path.is_none() {
+            result.original_file = crate::utils::create_audio_file(file_path)?;
+        }
+        if let Some(ref reservation) = output_reservation {
+            result.output_path = Self::move_to_reserved_path(&result.output_path, reservation.path())?;
+        }
+        if diarization_unsupported {
+            result.warnings.push("Speaker diarization isn't supported by this CLI; transcribed without speaker labels".to_string());
+        }
+
+        if let Some((cache, audio_file)) = cache_entry.as_ref() {
+            result.original_file.hash = audio_file.hash.clone();
+            if let Err(e) = cache.put(&result, settings) {
+                println!("⚠️ Failed to cache transcription: {}", e);
+            }
         }
+
+        self.finish_result(&mut result, file_path, settings).await;
+        Ok(result)
     }
 
-    /// Process file using Tauri sidecar
-    async fn process_with_sidecar(
+    /// `file_path` with its content hash, for looking it up in the transcription cache
+    async fn hashed_audio_file(file_path: &str) -> Option<AudioFile> {
+        let mut audio_file = crate::utils::create_audio_file(file_path).ok()?;
+        audio_file.hash = Some(crate::utils::hash_file_blocking(&audio_file.path).await.ok()?);
+        Some(audio_file)
+    }
+
+    /// Whether `file_path` has to be converted to WAV before the CLI runs: always for formats the
+    /// CLI can't read, and for the other formats only FFmpeg can decode when `auto_transcode` is on
+    pub fn needs_wav_conversion(file_path: &str, settings: &AppSettings) -> bool {
+        crate::utils::get_file_extension(file_path).is_some_and(|ext| {
+            !CLI_INPUT_FORMATS.contains(&ext.as_str()) || (settings.auto_transcode && requires_ffmpeg(&ext))
+        })
+    }
+
+    /// Flag low confidence, write the metadata sidecar and run the post-processing command,
+    /// collecting anything that goes wrong in the result's warnings
+    async fn finish_result(&self, result: &mut TranscriptionResult, file_path: &str, settings: &AppSettings) {
+        if result.flag_low_confidence(settings.low_confidence_threshold) {
+            println!("⚠️ Low confidence for {}: {:?}", file_path, result.confidence);
+        }
+
+        if settings.include_metadata {
+            if let Err(e) = crate::export::write_metadata_sidecar(result, settings) {
+                println!("⚠️ {}", e);
+                result.warnings.push(e.to_string());
+            }
+        }
+
+        // Post-processing failures don't fail the transcription
+        if let Some(command) = settings.post_process_command.as_deref().map(str::trim).filter(|command| !command.is_empty()) {
+            println!("🔥 Running post-processing command: {}", command);
+            if let Err(e) = run_post_process_command(command, &result.output_path, POST_PROCESS_TIMEOUT).await {
+                println!("⚠️ {}", e);
+                result.warnings.push(e.to_string());
+            }
+        }
+    }
+
+    /// Transcribe a generated sample clip with the tiny model to check the CLI, model and
+    /// output handling end to end
+    pub async fn run_self_test(&self, settings: &AppSettings) -> AppResult<TranscriptionResult> {
+        if !self.check_cli_availability().await.unwrap_or(false) {
+            return Err(AppError::CliError(format!(
+                "The speech-to-text CLI could not be started ({})",
+                self.describe_resolved_cli()
+            )));
+        }
+
+        let self_test_dir = crate::utils::work_directory().join("self-test");
+        std::fs::create_dir_all(&self_test_dir)?;
+        let sample_path = self_test_dir.join("self-test.wav");
+        crate::utils::write_sample_wav(&sample_path, SELF_TEST_SAMPLE_SECONDS)?;
+
+        let settings = AppSettings {
+            model_size: ModelSize::Tiny,
+            output_directory: self_test_dir.to_string_lossy().to_string(),
+            output_directory_mode: OutputDirectoryMode::Absolute,
+            output_format: OutputFormat::Txt,
+            skip_existing_output: false,
+            auto_transcode: false,
+            retry_count: 0,
+            post_process_command: None,
+            ..settings.clone()
+        };
+
+        // The sample never changes, so a cached result would prove nothing
+        let manager = Self {
+            use_sidecar: self.use_sidecar,
+            timeout_duration: self.timeout_duration,
+            concurrency_limiter: self.concurrency_limiter.clone(),
+            partial_text_callback: None,
+            log_line_callback: None,
+            process_callback: None,
+            output_path_callback: None,
+            cli_binary_path: self.cli_binary_path.clone(),
+            transcription_cache: None,
+        };
+        let result = manager.process_file(&sample_path.to_string_lossy(), &settings, None).await;
+
+        let _ = std::fs::remove_dir_all(&self_test_dir);
+        result
+    }
+
+    /// Re-transcribe the source file of a previous result, replacing any cached copy
+    pub async fn reprocess_result(
         &self,
-        args: Vec<String>,
-        file_path: &str,
+        result: &TranscriptionResult,
         settings: &AppSettings,
         progress_callback: Option<ProgressCallback>,
-        cancellation_token: Option<CancellationToken>,
-        start_time: std::time::Instant,
     ) -> AppResult<TranscriptionResult> {
-        use tokio::io::{AsyncBufReadExt, BufReader};
+        let source = result.original_file.path.as_str();
+        if !std::path::Path::new(source).is_file() {
+            return Err(AppError::FileNotFound(source.to_string()));
+        }
+
+        if let Some(ref cache) = self.transcription_cache {
+            if let Some(audio_file) = Self::hashed_audio_file(source).await {
+                cache.remove(&audio_file, settings)?;
+            }
+        }
+
+        println!("🔁 Reprocessing {}", source);
+        self.process_file(source, settings, progress_callback).await
+    }
+
+    /// Settings that reproduce `result`: the language and model from its metadata on top of `base`
+    pub fn settings_for_result(result: &TranscriptionResult, base: &AppSettings) -> AppSettings {
+        let mut settings = base.clone();
+        if !result.metadata.language.trim().is_empty() {
+            settings.language = result.metadata.language.clone();
+        }
+        if let Ok(model_size) = result.metadata.model_size.parse() {
+            settings.model_size = model_size;
+        }
+        settings
+    }
+
+    /// Build CLI command arguments for a single file
+    pub fn build_cli_args(file_path: &str, settings: &AppSettings) -> Vec<String> {
+        let mut args = vec![file_path.to_string()];
+
+        // "auto" is passed through explicitly; omitting --language would make the
+        // CLI fall back to the language in its own config file instead of detecting
+        let language = if settings.language.trim().eq_ignore_ascii_case(AUTO_LANGUAGE) {
+            AUTO_LANGUAGE.to_string()
+        } else {
+            settings.language.clone()
+        };
+        args.push("--language".to_string());
+        args.push(language);
+
+        args.push("--model-size".to_string());
+        args.push(settings.model_size.to_string());
+
+        // Only passed when changed, so the defaults keep Whisper's own decoding behaviour
+        if settings.temperature != 0.0 {
+            args.push("--temperature".to_string());
+            args.push(settings.temperature.to_string());
+        }
+        if settings.beam_size != crate::models::DEFAULT_BEAM_SIZE {
+            args.push("--beam-size".to_string());
+            args.push(settings.beam_size.to_string());
+        }
+
+        // An explicit output directory lets parse_cli_completion read the output directly
+        let output_directory = settings.output_directory.trim();
+        if !output_directory.is_empty() {
+            args.push("--output-dir".to_string());
+            args.push(output_directory.to_string());
+        }
+
+        if settings.include_metadata {
+            args.push("--include-metadata".to_string());
+        }
+
+        if settings.enable_diarization {
+            args.push(DIARIZE_OPTION.to_string());
+        }
+
+        // Attached with `=` so a prompt starting with "-" isn't read as another option
+        if let Some(prompt) = Self::initial_prompt(settings) {
+            args.push(format!("--initial-prompt={}", prompt));
+        }
+
+        args
+    }
+
+    /// The initial prompt followed by the custom vocabulary, on one line. The arguments never
+    /// go through a shell, so only line breaks and control characters need replacing.
+    pub fn initial_prompt(settings: &AppSettings) -> Option<String> {
+        let single_line = |text: &str| -> String {
+            text.split(|c: char| c.is_whitespace() || c.is_control())
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let vocabulary: Vec<String> = settings.custom_vocabulary
+            .iter()
+            .map(|term| single_line(term))
+            .filter(|term| !term.is_empty())
+            .collect();
+        let parts: Vec<String> = [
+            settings.initial_prompt.as_deref().map(single_line).unwrap_or_default(),
+            vocabulary.join(", "),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect();
+
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+
+    /// Process file using Tauri sidecar
+    async fn process_with_sidecar(
+        &self,
+        args: Vec<String>,
+        file_path: &str,
+        settings: &AppSettings,
+        progress_callback: Option<ProgressCallback>,
+        cancellation_token: Option<CancellationToken>,
+        start_time: std::time::Instant,
+    ) -> AppResult<TranscriptionResult> {
+        use tokio::io::{AsyncBufReadExt, BufReader};
         
         let cli_path = self.find_sidecar_path()?;
         
@@ -370,7 +1266,8 @@ impl CliManager {
            .current_dir(&work_dir) // Set working directory outside app bundle
            .env("TMPDIR", work_dir.to_string_lossy().to_string()) // Set temp directory
            .env("HOME", dirs::home_dir().unwrap_or_else(|| std::env::temp_dir()).to_string_lossy().to_string()) // Ensure HOME is set
-           .env("PATH", enhanced_path); // Enhanced PATH with ffmpeg locations
+           .env("PATH", enhanced_path) // Enhanced PATH with ffmpeg locations
+           .kill_on_drop(true);
 
         println!("🔥 About to spawn sidecar with command: {} {:?}", cli_path, args);
         println!("🔥 Working directory: {:?}", work_dir);
@@ -433,34 +1330,60 @@ impl CliManager {
             })?;
 
         println!("🔥 Sidecar process spawned successfully");
+        let _process_guard = self.track_process(&child, file_path);
+
+        // Language reported by the CLI when running in auto-detect mode
+        let detected_language: Arc<std::sync::Mutex<Option<String>>> = Arc::new(std::sync::Mutex::new(None));
 
         // Monitor stdout and stderr for progress
-        if let Some(callback) = progress_callback {
-            let stdout = child.stdout.take().ok_or_else(|| AppError::CliError("Failed to capture stdout".to_string()))?;
-            let stderr = child.stderr.take().ok_or_else(|| AppError::CliError("Failed to capture stderr".to_string()))?;
-            
+        let output_streams = progress_callback.as_ref().and_then(|_| Self::take_output_streams(&mut child));
+        if let (Some(callback), Some((stdout, stderr))) = (progress_callback.clone(), output_streams) {
             let file_path_clone = file_path.to_string();
-            let callback_clone = callback.clone();
+            // tqdm redraws many times per second, so parsed progress goes through a shared throttle
+            let progress_throttle = Arc::new(ProgressThrottle::new(callback.clone(), PROGRESS_EMIT_INTERVAL));
+            let callback_clone = ProgressThrottle::callback(&progress_throttle);
+            let detected_language_clone = detected_language.clone();
+            let partial_text_callback = self.partial_text_callback.clone();
+            let log_line_callback = self.log_line_callback.clone();
             tokio::spawn(async move {
                 let mut lines = BufReader::new(stdout).lines();
                 while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(ref log_callback) = log_line_callback {
+                        Self::emit_log_line(&line, CliLogStream::Stdout, log_callback, &file_path_clone);
+                    }
+                    Self::record_detected_language(&line, &detected_language_clone);
+                    if let Some(ref partial_callback) = partial_text_callback {
+                        Self::emit_partial_text(&line, partial_callback, &file_path_clone);
+                    }
                     Self::parse_and_emit_progress(&line, &callback_clone, &file_path_clone);
                 }
             });
 
             let file_path_clone_2 = file_path.to_string();
-            let callback_clone_2 = callback.clone();
-            tokio::spawn(async move {
+            let callback_clone_2 = ProgressThrottle::callback(&progress_throttle);
+            let detected_language_clone_2 = detected_language.clone();
+            let log_line_callback = self.log_line_callback.clone();
+            let stderr_reader = tokio::spawn(async move {
                 let mut lines = BufReader::new(stderr).lines();
+                let mut tail = std::collections::VecDeque::with_capacity(STDERR_TAIL_LINES);
                 while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(ref log_callback) = log_line_callback {
+                        Self::emit_log_line(&line, CliLogStream::Stderr, log_callback, &file_path_clone_2);
+                    }
+                    Self::record_detected_language(&line, &detected_language_clone_2);
                     Self::parse_and_emit_progress(&line, &callback_clone_2, &file_path_clone_2);
+                    if tail.len() == STDERR_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
                 }
+                tail
             });
 
             // Send completion status
             let status = if let Some(ref token) = cancellation_token {
                 tokio::select! {
-                    result = timeout(self.timeout_duration, async {
+                    result = self.with_process_timeout(async {
                         // Check for cancellation before waiting
                         if token.is_cancelled() {
                             let _ = child.kill().await;
@@ -487,7 +1410,7 @@ impl CliManager {
                 }
             } else {
                 println!("🔥 Calling timeout with child.wait()");
-                timeout(self.timeout_duration, child.wait()).await
+                self.with_process_timeout(child.wait()).await
                     .map_err(|e| {
                         println!("🔥 Sidecar process timed out: {}", e);
                         AppError::CliError("Sidecar process timed out".to_string())
@@ -499,24 +1422,14 @@ impl CliManager {
             };
 
             println!("🔥 Sidecar process output received");
+            progress_throttle.flush();
 
             let processing_time = start_time.elapsed().as_secs_f64();
 
             if status.success() {
-                callback(ProcessingProgress {
-                    stage: ProcessingStage::Saving,
-                    progress: 100.0,
-                    current_file: Some(file_path.to_string()),
-                    timestamp: Utc::now(),
-                    message: Some("Transcription completed!".to_string()),
-                    job_id: None,
-                    file_index: None,
-                    total_files: None,
-                    can_cancel: false,
-                });
-
                 // Read output files since CLI completed successfully
-                self.parse_cli_completion(file_path, processing_time, settings).await
+                let detected_language = detected_language.lock().ok().and_then(|language| language.clone());
+                self.finish_with_progress(&callback, file_path, processing_time, settings, detected_language).await
             } else {
                 let mut error_log = String::new();
                 
@@ -540,44 +1453,97 @@ impl CliManager {
                 println!("{}", msg);
                 error_log.push_str(&msg);
 
-                // Try to capture stderr for more details
-                let stderr_output = tokio::process::Command::new(&cli_path)
-                    .args(&args)
-                    .output()
-                    .await;
-                    
-                match stderr_output {
-                    Ok(output) => {
-                        let msg = format!("🔥 CLI stderr: {}\n", String::from_utf8_lossy(&output.stderr));
-                        println!("{}", msg);
-                        error_log.push_str(&msg);
-                        
-                        let msg = format!("🔥 CLI stdout: {}\n", String::from_utf8_lossy(&output.stdout));
-                        println!("{}", msg);
-                        error_log.push_str(&msg);
-                    }
-                    Err(e) => {
-                        let msg = format!("🔥 Failed to capture CLI output: {}\n", e);
-                        println!("{}", msg);
-                        error_log.push_str(&msg);
-                    }
-                }
+                // The stderr monitor keeps the tail of the output for diagnosis
+                let stderr_tail = collect_stderr_tail(stderr_reader).await;
+                let msg = format!("🔥 CLI stderr: {}\n", stderr_tail);
+                println!("{}", msg);
+                error_log.push_str(&msg);
 
                 // Write error log to file on Desktop for easy access
                 let desktop_path = dirs::desktop_dir().unwrap_or_else(|| std::env::temp_dir());
                 let error_log_path = desktop_path.join("speechtotext_error.log");
                 let _ = std::fs::write(&error_log_path, &error_log);
 
-                Err(AppError::CliError(format!("CLI execution failed with exit code: {:?}", status.code())))
+                Err(classify_cli_failure(&stderr_tail, status.code()))
             }
         } else {
-            // No progress callback provided, just wait for the process to finish
-            let status = child.wait().await.map_err(|e| AppError::CliError(format!("Sidecar process failed: {}", e)))?;
+            // No progress callback, or the output couldn't be captured: just wait for the process to finish
+            let output = self.wait_for_captured_output(child, cancellation_token.as_ref(), "Sidecar").await?;
+            self.emit_captured_output(&output, file_path);
             let processing_time = start_time.elapsed().as_secs_f64();
-            if status.success() {
-                self.parse_cli_completion(file_path, processing_time, settings).await
+            if output.status.success() {
+                // Nothing was streamed, so the language report is still in the captured output
+                let detected_language = Self::captured_detected_language(&output);
+                match progress_callback {
+                    Some(ref callback) => self.finish_with_progress(callback, file_path, processing_time, settings, detected_language).await,
+                    None => self.parse_cli_completion(file_path, processing_time, settings, detected_language).await,
+                }
             } else {
-                Err(AppError::CliError(format!("CLI execution failed with exit code: {:?}", status.code())))
+                Err(classify_cli_failure(&String::from_utf8_lossy(&output.stderr), output.status.code()))
+            }
+        }
+    }
+
+    /// Wait for a child whose output is captured rather than streamed, under the same timeout and
+    /// cancellation as a monitored run. The child is spawned with `kill_on_drop`, so abandoning the
+    /// wait kills it.
+    async fn wait_for_captured_output(
+        &self,
+        child: tokio::process::Child,
+        cancellation_token: Option<&CancellationToken>,
+        label: &str,
+    ) -> AppResult<std::process::Output> {
+        let wait = self.with_process_timeout(child.wait_with_output());
+        let result = match cancellation_token {
+            Some(token) => tokio::select! {
+                result = wait => result,
+                _ = token.cancelled() => {
+                    println!("🔥 Processing was cancelled");
+                    return Err(AppError::ProcessingError("Processing was cancelled".to_string()));
+                }
+            },
+            None => wait.await,
+        };
+        result
+            .map_err(|e| {
+                println!("🔥 {} process timed out: {}", label, e);
+                AppError::CliError(format!("{} process timed out", label))
+            })?
+            .map_err(|e| AppError::CliError(format!("{} process failed: {}", label, e)))
+    }
+
+    /// Limit one CLI run to `per_file_timeout_secs`. Dropping the run on timeout kills its CLI process.
+    async fn with_per_file_timeout(
+        file_path: &str,
+        settings: &AppSettings,
+        run: impl std::future::Future<Output = AppResult<TranscriptionResult>>,
+    ) -> AppResult<TranscriptionResult> {
+        if settings.per_file_timeout_secs == 0 {
+            return run.await;
+        }
+
+        let time_limit = Duration::from_secs(settings.per_file_timeout_secs as u64);
+        timeout(time_limit, run).await.unwrap_or_else(|_| {
+            println!("⏱️ {} exceeded the per-file timeout of {}s", file_path, settings.per_file_timeout_secs);
+            Err(AppError::Timeout(format!(
+                "{} took longer than {} seconds",
+                file_path, settings.per_file_timeout_secs
+            )))
+        })
+    }
+
+    /// Take the child's stdout and stderr for monitoring. If either can't be captured, both are
+    /// left on the child so the caller can wait for it without progress instead of failing.
+    fn take_output_streams(
+        child: &mut tokio::process::Child,
+    ) -> Option<(tokio::process::ChildStdout, tokio::process::ChildStderr)> {
+        match (child.stdout.take(), child.stderr.take()) {
+            (Some(stdout), Some(stderr)) => Some((stdout, stderr)),
+            (stdout, stderr) => {
+                println!("⚠️ Failed to capture CLI output; waiting without progress updates");
+                child.stdout = stdout;
+                child.stderr = stderr;
+                None
             }
         }
     }
@@ -595,7 +1561,7 @@ impl CliManager {
         use tokio::process::Command as AsyncCommand;
         use tokio::io::{AsyncBufReadExt, BufReader};
 
-        let cli_command = self.find_dev_cli_command();
+        let cli_command = self.dev_cli_command();
         
         // Create a safe working directory for CLI execution
         let work_dir = dirs::cache_dir()
@@ -611,7 +1577,8 @@ impl CliManager {
            .stderr(Stdio::piped())
            .current_dir(&work_dir) // Set working directory outside app bundle
            .env("TMPDIR", work_dir.to_string_lossy().to_string()) // Set temp directory
-           .env("HOME", dirs::home_dir().unwrap_or_else(|| std::env::temp_dir()).to_string_lossy().to_string()); // Ensure HOME is set
+           .env("HOME", dirs::home_dir().unwrap_or_else(|| std::env::temp_dir()).to_string_lossy().to_string()) // Ensure HOME is set
+           .kill_on_drop(true);
 
         let mut child = cmd.spawn()
             .map_err(|e| {
@@ -620,34 +1587,60 @@ impl CliManager {
             })?;
 
         println!("🔥 CLI process spawned successfully");
+        let _process_guard = self.track_process(&child, file_path);
+
+        // Language reported by the CLI when running in auto-detect mode
+        let detected_language: Arc<std::sync::Mutex<Option<String>>> = Arc::new(std::sync::Mutex::new(None));
 
         // Monitor stdout and stderr for progress
-        if let Some(callback) = progress_callback {
-            let stdout = child.stdout.take().ok_or_else(|| AppError::CliError("Failed to capture stdout".to_string()))?;
-            let stderr = child.stderr.take().ok_or_else(|| AppError::CliError("Failed to capture stderr".to_string()))?;
-            
+        let output_streams = progress_callback.as_ref().and_then(|_| Self::take_output_streams(&mut child));
+        if let (Some(callback), Some((stdout, stderr))) = (progress_callback.clone(), output_streams) {
             let file_path_clone = file_path.to_string();
-            let callback_clone = callback.clone();
+            // tqdm redraws many times per second, so parsed progress goes through a shared throttle
+            let progress_throttle = Arc::new(ProgressThrottle::new(callback.clone(), PROGRESS_EMIT_INTERVAL));
+            let callback_clone = ProgressThrottle::callback(&progress_throttle);
+            let detected_language_clone = detected_language.clone();
+            let partial_text_callback = self.partial_text_callback.clone();
+            let log_line_callback = self.log_line_callback.clone();
             tokio::spawn(async move {
                 let mut lines = BufReader::new(stdout).lines();
                 while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(ref log_callback) = log_line_callback {
+                        Self::emit_log_line(&line, CliLogStream::Stdout, log_callback, &file_path_clone);
+                    }
+                    Self::record_detected_language(&line, &detected_language_clone);
+                    if let Some(ref partial_callback) = partial_text_callback {
+                        Self::emit_partial_text(&line, partial_callback, &file_path_clone);
+                    }
                     Self::parse_and_emit_progress(&line, &callback_clone, &file_path_clone);
                 }
             });
 
             let file_path_clone_2 = file_path.to_string();
-            let callback_clone_2 = callback.clone();
-            tokio::spawn(async move {
+            let callback_clone_2 = ProgressThrottle::callback(&progress_throttle);
+            let detected_language_clone_2 = detected_language.clone();
+            let log_line_callback = self.log_line_callback.clone();
+            let stderr_reader = tokio::spawn(async move {
                 let mut lines = BufReader::new(stderr).lines();
+                let mut tail = std::collections::VecDeque::with_capacity(STDERR_TAIL_LINES);
                 while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(ref log_callback) = log_line_callback {
+                        Self::emit_log_line(&line, CliLogStream::Stderr, log_callback, &file_path_clone_2);
+                    }
+                    Self::record_detected_language(&line, &detected_language_clone_2);
                     Self::parse_and_emit_progress(&line, &callback_clone_2, &file_path_clone_2);
+                    if tail.len() == STDERR_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
                 }
+                tail
             });
 
             // Send completion status
             let status = if let Some(ref token) = cancellation_token {
                 tokio::select! {
-                    result = timeout(self.timeout_duration, async {
+                    result = self.with_process_timeout(async {
                         // Check for cancellation before waiting
                         if token.is_cancelled() {
                             let _ = child.kill().await;
@@ -674,7 +1667,7 @@ impl CliManager {
                 }
             } else {
                 println!("🔥 Calling timeout with child.wait()");
-                timeout(self.timeout_duration, child.wait()).await
+                self.with_process_timeout(child.wait()).await
                     .map_err(|e| {
                         println!("🔥 CLI process timed out: {}", e);
                         AppError::CliError("CLI process timed out".to_string())
@@ -686,24 +1679,14 @@ impl CliManager {
             };
 
             println!("🔥 CLI process output received");
+            progress_throttle.flush();
 
             let processing_time = start_time.elapsed().as_secs_f64();
 
             if status.success() {
-                callback(ProcessingProgress {
-                    stage: ProcessingStage::Saving,
-                    progress: 100.0,
-                    current_file: Some(file_path.to_string()),
-                    timestamp: Utc::now(),
-                    message: Some("Transcription completed!".to_string()),
-                    job_id: None,
-                    file_index: None,
-                    total_files: None,
-                    can_cancel: false,
-                });
-
                 // Read output files since CLI completed successfully
-                self.parse_cli_completion(file_path, processing_time, settings).await
+                let detected_language = detected_language.lock().ok().and_then(|language| language.clone());
+                self.finish_with_progress(&callback, file_path, processing_time, settings, detected_language).await
             } else {
                 println!("🔥 CLI execution failed!");
                 println!("🔥 Exit code: {:?}", status.code());
@@ -721,16 +1704,24 @@ impl CliManager {
                     println!("🔥 Failed to run CLI version test");
                 }
 
-                Err(AppError::CliError(format!("CLI execution failed with exit code: {:?}", status.code())))
+                let stderr_tail = collect_stderr_tail(stderr_reader).await;
+                println!("🔥 CLI stderr: {}", stderr_tail);
+                Err(classify_cli_failure(&stderr_tail, status.code()))
             }
         } else {
-            // No progress callback provided, just wait for the process to finish
-            let status = child.wait().await.map_err(|e| AppError::CliError(format!("CLI process failed: {}", e)))?;
+            // No progress callback, or the output couldn't be captured: just wait for the process to finish
+            let output = self.wait_for_captured_output(child, cancellation_token.as_ref(), "CLI").await?;
+            self.emit_captured_output(&output, file_path);
             let processing_time = start_time.elapsed().as_secs_f64();
-            if status.success() {
-                self.parse_cli_completion(file_path, processing_time, settings).await
+            if output.status.success() {
+                // Nothing was streamed, so the language report is still in the captured output
+                let detected_language = Self::captured_detected_language(&output);
+                match progress_callback {
+                    Some(ref callback) => self.finish_with_progress(callback, file_path, processing_time, settings, detected_language).await,
+                    None => self.parse_cli_completion(file_path, processing_time, settings, detected_language).await,
+                }
             } else {
-                Err(AppError::CliError(format!("CLI execution failed with exit code: {:?}", status.code())))
+                Err(classify_cli_failure(&String::from_utf8_lossy(&output.stderr), output.status.code()))
             }
         }
     }
@@ -742,10 +1733,50 @@ impl CliManager {
         settings: &AppSettings,
         progress_callback: Option<ProgressCallback>,
     ) -> AppResult<Vec<TranscriptionResult>> {
+        self.process_batch_with_cancellation(file_paths, settings, progress_callback, None).await
+    }
+
+    /// Process a batch that stops between files once `cancellation_token` is cancelled,
+    /// returning the results finished so far
+    pub async fn process_batch_with_cancellation(
+        &self,
+        file_paths: &[String],
+        settings: &AppSettings,
+        progress_callback: Option<ProgressCallback>,
+        cancellation_token: Option<CancellationToken>,
+    ) -> AppResult<Vec<TranscriptionResult>> {
+        self.run_batch(file_paths, settings, &[], progress_callback, cancellation_token).await
+    }
+
+    /// Process a batch where `file_settings[i]`, when present, replaces `settings` for file `i`
+    pub async fn process_batch_with_overrides(
+        &self,
+        file_paths: &[String],
+        settings: &AppSettings,
+        file_settings: &[Option<AppSettings>],
+        progress_callback: Option<ProgressCallback>,
+    ) -> AppResult<Vec<TranscriptionResult>> {
+        self.run_batch(file_paths, settings, file_settings, progress_callback, None).await
+    }
+
+    async fn run_batch(
+        &self,
+        file_paths: &[String],
+        settings: &AppSettings,
+        file_settings: &[Option<AppSettings>],
+        progress_callback: Option<ProgressCallback>,
+        cancellation_token: Option<CancellationToken>,
+    ) -> AppResult<Vec<TranscriptionResult>> {
+        Self::validate_file_settings(file_paths, file_settings)?;
         let mut results = Vec::new();
         let total_files = file_paths.len();
 
         for (index, file_path) in file_paths.iter().enumerate() {
+            if cancellation_token.as_ref().is_some_and(|token| token.is_cancelled()) {
+                println!("🛑 Batch cancelled after {} of {} files", index, total_files);
+                break;
+            }
+
             // Update batch progress
             if let Some(ref callback) = progress_callback {
                 let batch_progress = (index as f64 / total_files as f64) * 100.0;
@@ -758,12 +1789,18 @@ impl CliManager {
                     job_id: None,
                     file_index: Some(index),
                     total_files: Some(total_files),
-                    can_cancel: true,
+                    can_cancel: cancellation_token.is_some(),
+                    estimated_completion: None,
                 });
             }
 
             // Process individual file
-            match self.process_file(file_path, settings, progress_callback.clone()).await {
+            let file_settings = Self::settings_for_file(settings, file_settings, index);
+            if let Some(output_path) = Self::existing_output_for(file_path, file_settings) {
+                println!("⏭️ Skipping {}, already transcribed to {}", file_path, output_path);
+                continue;
+            }
+            match self.process_file_with_cancellation(file_path, file_settings, progress_callback.clone(), cancellation_token.clone()).await {
                 Ok(result) => results.push(result),
                 Err(e) => {
                     // Log error but continue with other files
@@ -776,6 +1813,31 @@ impl CliManager {
         Ok(results)
     }
 
+    /// Output of an earlier run that lets `file_path` be skipped, when `skip_existing_output` is on
+    pub fn existing_output_for(file_path: &str, settings: &AppSettings) -> Option<String> {
+        if !settings.skip_existing_output {
+            return None;
+        }
+        crate::utils::existing_output_path(file_path, &settings.output_directory_for(file_path), settings)
+    }
+
+    /// Per-file settings must be empty or line up one-to-one with the files
+    pub fn validate_file_settings(file_paths: &[String], file_settings: &[Option<AppSettings>]) -> AppResult<()> {
+        if !file_settings.is_empty() && file_settings.len() != file_paths.len() {
+            return Err(AppError::ConfigError(format!(
+                "Got settings for {} files but the batch has {} files",
+                file_settings.len(),
+                file_paths.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Settings for file `index`: its override if it has one, otherwise the batch settings
+    pub fn settings_for_file<'a>(settings: &'a AppSettings, file_settings: &'a [Option<AppSettings>], index: usize) -> &'a AppSettings {
+        file_settings.get(index).and_then(Option::as_ref).unwrap_or(settings)
+    }
+
     /// Monitor processing progress (simulated for now)
     pub async fn monitor_progress(&self, callback: ProgressCallback, file_path: &str) {
         self.monitor_progress_with_cancellation(callback, file_path.to_string(), None).await;
@@ -798,10 +1860,11 @@ impl CliManager {
         cancellation_token: Option<CancellationToken>
     ) {
         let stages = [
-            (ProcessingStage::LoadingModel, "Loading Whisper model...", 15.0),
-            (ProcessingStage::Preprocessing, "Preprocessing audio...", 25.0),
+            (ProcessingStage::LoadingModel, "Loading Whisper model...", 5.0),
+            (ProcessingStage::Preprocessing, "Preprocessing audio...", 15.0),
             (ProcessingStage::Transcribing, "Transcribing audio...", 80.0),
             (ProcessingStage::Postprocessing, "Processing results...", 95.0),
+            (ProcessingStage::Finalizing, "Finalizing transcription...", 97.0),
             (ProcessingStage::Saving, "Saving transcription...", 100.0),
         ];
 
@@ -823,6 +1886,7 @@ impl CliManager {
                 file_index: None,
                 total_files: None,
                 can_cancel: cancellation_token.is_some(),
+                estimated_completion: None,
             });
 
             // Simulate processing time with cancellation checks
@@ -851,17 +1915,25 @@ impl CliManager {
         // For now, we'll create a basic result structure
         // In a real implementation, this would parse the actual CLI output format
         let audio_file = crate::utils::create_audio_file(file_path)?;
-        let output_path = crate::utils::get_output_filename(file_path, &settings.output_directory)?;
+        let output_path = crate::utils::get_output_filename(file_path, &settings.output_directory, settings)?;
         
         // Try to read the transcribed text from the output file
         let transcribed_text = if std::path::Path::new(&output_path).exists() {
             tokio::fs::read_to_string(&output_path).await
+                .map(crate::utils::strip_bom)
                 .unwrap_or_else(|_| "Transcription completed but text could not be read".to_string())
         } else {
             // Fallback: extract text from CLI output if available
             output_str.trim().to_string()
         };
 
+        let audio_info = crate::utils::probe_audio_metadata(file_path).unwrap_or(AudioInfo {
+            duration: 0.0,
+            sample_rate: None,
+            channels: None,
+        });
+        let realtime_factor = crate::utils::realtime_factor(audio_info.duration, processing_time);
+
         Ok(TranscriptionResult {
             id: crate::utils::generate_id(),
             original_file: audio_file,
@@ -870,38 +1942,165 @@ impl CliManager {
                 language: settings.language.clone(),
                 model_size: settings.model_size.to_string(),
                 timestamp: Utc::now(),
-                audio_info: AudioInfo {
-                    duration: 0.0, // Would be extracted from CLI output
-                    sample_rate: None,
-                    channels: None,
-                },
+                audio_info,
             },
             output_path,
             processing_time,
             confidence: None, // Would be extracted from CLI output if available
+            segments: None,
+            warnings: Vec::new(),
+            no_speech_detected: false,
+            realtime_factor,
         })
     }
 
+    /// Report Finalizing while the output is read back, then Saving once the result is ready
+    async fn finish_with_progress(
+        &self,
+        callback: &ProgressCallback,
+        file_path: &str,
+        processing_time: f64,
+        settings: &AppSettings,
+        detected_language: Option<String>,
+    ) -> AppResult<TranscriptionResult> {
+        let stage_progress = |stage: ProcessingStage, progress: f64, message: &str| ProcessingProgress {
+            stage,
+            progress,
+            current_file: Some(file_path.to_string()),
+            timestamp: Utc::now(),
+            message: Some(message.to_string()),
+            job_id: None,
+            file_index: None,
+            total_files: None,
+            can_cancel: false,
+            estimated_completion: None,
+        };
+
+        callback(stage_progress(ProcessingStage::Finalizing, progress_in_band(40.0, SAVING_PROGRESS_BAND), "Finalizing transcription..."));
+        let result = self.parse_cli_completion(file_path, processing_time, settings, detected_language).await?;
+        callback(stage_progress(ProcessingStage::Saving, SAVING_PROGRESS_BAND.1, "Transcription completed!"));
+        Ok(result)
+    }
+
     /// Parse CLI completion by reading output files (instead of relying on stdout)
     async fn parse_cli_completion(
         &self,
         file_path: &str,
         processing_time: f64,
         settings: &AppSettings,
+        detected_language: Option<String>,
     ) -> AppResult<TranscriptionResult> {
         // Create a basic result structure by reading output files
         let audio_file = crate::utils::create_audio_file(file_path)?;
-        
-        // Since we're not specifying output-dir, files will be in the CLI working directory
-        let work_dir = dirs::cache_dir()
-            .unwrap_or_else(|| std::env::temp_dir())
-            .join("SpeechToText");
-        
+
         let base_name = std::path::Path::new(&audio_file.name)
             .file_stem()
             .and_then(|s| s.to_str())
-            .unwrap_or("output");
-        
+            .unwrap_or("output")
+            .to_string();
+
+        // Outputs older than this run belong to an earlier run
+        let run_started = std::time::SystemTime::now().checked_sub(Duration::from_secs_f64(processing_time.max(0.0)));
+
+        let (transcribed_text, actual_output_path) = if settings.output_directory.trim().is_empty() {
+            Self::search_output_file(&crate::utils::work_directory(), &base_name, run_started).await?
+        } else {
+            // The CLI was given --output-dir, so the output can only be in that directory
+            let output_dir = std::path::Path::new(settings.output_directory.trim());
+            let output_path = Self::find_output_in_directory(output_dir, &base_name, run_started).ok_or_else(|| {
+                AppError::CliError(format!("Transcription output file not found in {}", output_dir.display()))
+            })?;
+            println!("🔥 Reading output file: {}", output_path.display());
+            let text = tokio::fs::read_to_string(&output_path).await
+                .map(crate::utils::strip_bom)
+                .unwrap_or_else(|_| "Transcription completed but text could not be read".to_string());
+            (text, output_path.to_string_lossy().to_string())
+        };
+
+        // Problems past this point are reported on the result instead of failing it
+        let mut warnings = Vec::new();
+
+        // Load timestamped segments from the JSON output written next to the text output
+        let mut transcribed_text = transcribed_text;
+        let mut segments = None;
+        if matches!(settings.output_format, OutputFormat::Json) {
+            let json_output_path = std::path::Path::new(&actual_output_path).with_extension("json");
+            if json_output_path.exists() {
+                match tokio::fs::read_to_string(&json_output_path).await {
+                    Ok(content) => match Self::parse_segments_json(&content) {
+                        Ok(parsed) => {
+                            transcribed_text = Self::concatenate_segments(&parsed);
+                            segments = Some(parsed);
+                        }
+                        Err(e) => {
+                            println!("🔥 Failed to parse JSON segments: {}", e);
+                            warnings.push(format!("Timestamped segments could not be parsed: {}", e));
+                        }
+                    },
+                    Err(e) => {
+                        println!("🔥 Failed to read JSON output: {}", e);
+                        warnings.push(format!("Timestamped segments could not be read: {}", e));
+                    }
+                }
+            }
+        }
+
+        if settings.clean_output_text {
+            let cleaned = crate::utils::normalize_transcription(&transcribed_text);
+            if cleaned != transcribed_text {
+                if let Err(e) = std::fs::write(&actual_output_path, &cleaned) {
+                    println!("🔥 Failed to write cleaned text to {}: {}", actual_output_path, e);
+                    warnings.push(format!("Cleaned text could not be saved: {}", e));
+                }
+                transcribed_text = cleaned;
+            }
+        }
+        if let Err(e) = crate::utils::apply_output_encoding(&actual_output_path, &settings.output_encoding) {
+            println!("🔥 Failed to re-encode {}: {}", actual_output_path, e);
+            warnings.push(format!("Output could not be re-encoded: {}", e));
+        }
+
+        // Silent audio leaves an empty transcript, which is a result rather than a failure
+        let no_speech_detected = transcribed_text.trim().is_empty();
+        if no_speech_detected {
+            println!("🔇 No speech detected in {}", file_path);
+            warnings.push("No speech detected: the audio may be silent".to_string());
+        }
+
+        let audio_info = crate::utils::probe_audio_metadata(file_path).unwrap_or(AudioInfo {
+            duration: 0.0,
+            sample_rate: None,
+            channels: None,
+        });
+        let realtime_factor = crate::utils::realtime_factor(audio_info.duration, processing_time);
+
+        Ok(TranscriptionResult {
+            id: crate::utils::generate_id(),
+            original_file: audio_file,
+            transcribed_text,
+            metadata: TranscriptionMetadata {
+                language: detected_language.unwrap_or_else(|| settings.language.clone()),
+                model_size: settings.model_size.to_string(),
+                timestamp: Utc::now(),
+                audio_info,
+            },
+            output_path: actual_output_path,
+            processing_time,
+            confidence: None, // Would be extracted from CLI output if available
+            segments,
+            warnings,
+            no_speech_detected,
+            realtime_factor,
+        })
+    }
+
+    /// Search the CLI working directory and a few fallbacks for output written without --output-dir
+    async fn search_output_file(
+        work_dir: &std::path::Path,
+        base_name: &str,
+        run_started: Option<std::time::SystemTime>,
+    ) -> AppResult<(String, String)> {
+        // Without --output-dir, files will be in the CLI working directory
         let expected_output_path = work_dir.join(format!("{}_transcription.txt", base_name)).to_string_lossy().to_string();
 
         // Try to read the transcribed text from the output file
@@ -911,14 +2110,11 @@ impl CliManager {
         // Log to desktop for debugging
         let mut debug_log = format!("🔥 Looking for output file at: {}\n", expected_output_path);
         
-        // Also check the working directory where CLI was executed
-        let work_dir = dirs::cache_dir()
-            .unwrap_or_else(|| std::env::temp_dir())
-            .join("SpeechToText");
+        // Check the working directory where CLI was executed
         debug_log.push_str(&format!("🔥 CLI working directory: {:?}\n", work_dir));
         
         // List files in working directory
-        if let Ok(work_entries) = std::fs::read_dir(&work_dir) {
+        if let Ok(work_entries) = std::fs::read_dir(work_dir) {
             debug_log.push_str("🔥 Files in CLI working directory:\n");
             for entry in work_entries {
                 if let Ok(entry) = entry {
@@ -943,23 +2139,18 @@ impl CliManager {
             }
         }
         
-        let (transcribed_text, actual_output_path) = if std::path::Path::new(&expected_output_path).exists() {
+        let (transcribed_text, actual_output_path) = if Self::written_since(std::path::Path::new(&expected_output_path), run_started) {
             println!("🔥 Found exact output file");
             debug_log.push_str("🔥 Found exact output file\n");
             let text = tokio::fs::read_to_string(&expected_output_path).await
+                .map(crate::utils::strip_bom)
                 .unwrap_or_else(|_| "Transcription completed but text could not be read".to_string());
             (text, expected_output_path)
         } else {
             // If exact path doesn't exist, look for files with timestamp suffixes
-            let audio_file = crate::utils::create_audio_file(file_path)?;
-            let base_name = std::path::Path::new(&audio_file.name)
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("output");
-
             // Look in multiple directories for files matching the pattern
             let search_dirs = vec![
-                work_dir.clone(),                    // CLI working directory
+                work_dir.to_path_buf(),              // CLI working directory
                 work_dir.join("output"),             // CLI output subdirectory
                 dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("/tmp")), // User home directory
             ];
@@ -989,6 +2180,7 @@ impl CliManager {
                         }
                     }
                     
+                    let output_pattern = Self::output_name_pattern(base_name, true);
                     let mut matching_files: Vec<_> = std::fs::read_dir(&search_dir)
                         .unwrap()
                         .filter_map(|entry| entry.ok())
@@ -996,29 +2188,9 @@ impl CliManager {
                             if let Some(filename) = entry.file_name().to_str() {
                                 debug_log.push_str(&format!("  Checking file: {}\n", filename));
                                 
-                                // Look for files with multiple patterns:
-                                // 1. {base_name}_transcription_YYYYMMDDHHMM.txt (timestamp format from CLI)
-                                // 2. {base_name}_transcription.txt (exact format)
-                                // 3. {base_name}.txt (CLI default format)
-                                
-                                // Pattern 1: timestamp format (most common from CLI)
-                                let transcription_timestamp_pattern = format!("{}_transcription_", base_name);
-                                let timestamp_match = filename.starts_with(&transcription_timestamp_pattern) && filename.ends_with(".txt");
-                                
-                                // Pattern 2: exact format
-                                let exact_transcription_pattern = format!("{}_transcription.txt", base_name);
-                                let exact_match = filename == exact_transcription_pattern;
-                                
-                                // Pattern 3: simple format
-                                let simple_pattern = format!("{}.txt", base_name);
-                                let simple_match = filename == simple_pattern;
-                                
-                                // Pattern 4: any file containing the base name and "transcription"
-                                let contains_match = filename.contains(base_name) && 
-                                                   filename.contains("transcription") && 
-                                                   filename.ends_with(".txt");
-                                
-                                let matches = timestamp_match || exact_match || simple_match || contains_match;
+                                // Only the base name followed by the CLI's suffixes, so "meeting" never
+                                // matches "meeting_notes", and only files this run wrote
+                                let matches = output_pattern.is_match(filename) && Self::written_since(&entry.path(), run_started);
                                 
                                 if matches {
                                     debug_log.push_str(&format!("    ✅ MATCH: {}\n", filename));
@@ -1057,6 +2229,7 @@ impl CliManager {
                 println!("🔥 Found actual output file: {}", actual_path);
                 debug_log.push_str(&format!("🔥 Final result - Found actual output file: {}\n", actual_path));
                 let text = tokio::fs::read_to_string(&file_path_buf).await
+                    .map(crate::utils::strip_bom)
                     .unwrap_or_else(|_| "Transcription completed but text could not be read".to_string());
                 (text, actual_path)
             } else {
@@ -1076,29 +2249,88 @@ impl CliManager {
         let debug_log_path = desktop_path.join("speechtotext_file_search.log");
         let _ = std::fs::write(&debug_log_path, &debug_log);
 
-        Ok(TranscriptionResult {
-            id: crate::utils::generate_id(),
-            original_file: audio_file,
-            transcribed_text,
-            metadata: TranscriptionMetadata {
-                language: settings.language.clone(),
-                model_size: settings.model_size.to_string(),
-                timestamp: Utc::now(),
-                audio_info: AudioInfo {
-                    duration: 0.0, // Would be extracted from CLI output
-                    sample_rate: None,
-                    channels: None,
-                },
-            },
-            output_path: actual_output_path,
-            processing_time,
-            confidence: None, // Would be extracted from CLI output if available
-        })
+        Ok((transcribed_text, actual_output_path))
+    }
+
+    /// Find the CLI output for `base_name` in `output_dir`. The CLI appends a minute-resolution
+    /// timestamp (`{base}_transcription_YYYYMMDDHHMM.txt`), so the newest match written since
+    /// `run_started` is used.
+    pub fn find_output_in_directory(
+        output_dir: &std::path::Path,
+        base_name: &str,
+        run_started: Option<std::time::SystemTime>,
+    ) -> Option<std::path::PathBuf> {
+        let pattern = Self::output_name_pattern(base_name, false);
+
+        std::fs::read_dir(output_dir).ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_str().is_some_and(|name| pattern.is_match(name)))
+            .filter(|entry| Self::written_since(&entry.path(), run_started))
+            .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).unwrap_or(std::time::SystemTime::UNIX_EPOCH))
+            .map(|entry| entry.path())
+    }
+
+    /// Text output names the CLI writes for `base_name`, optionally including plain `{base}.txt`
+    fn output_name_pattern(base_name: &str, allow_plain: bool) -> Regex {
+        let suffix = if allow_plain { r"(?:_transcription(?:_\d{12})?)?" } else { r"_transcription(?:_\d{12})?" };
+        Regex::new(&format!(r"^{}{}\.txt$", regex::escape(base_name), suffix)).unwrap()
+    }
+
+    /// Whether `path` exists and was modified after `run_started` (allowing for file systems
+    /// with coarse timestamps). Without a start time any existing file counts.
+    fn written_since(path: &std::path::Path, run_started: Option<std::time::SystemTime>) -> bool {
+        let Ok(modified) = std::fs::metadata(path).and_then(|metadata| metadata.modified()) else {
+            return false;
+        };
+        run_started
+            .and_then(|started| started.checked_sub(OUTPUT_MTIME_SLACK))
+            .is_none_or(|cutoff| modified >= cutoff)
+    }
+
+    /// Move the output a run wrote into its own folder to the path reserved for it, along with
+    /// the JSON output next to it
+    fn move_to_reserved_path(run_output_path: &str, reserved_path: &std::path::Path) -> AppResult<String> {
+        if let Some(parent) = reserved_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        crate::utils::move_file(std::path::Path::new(run_output_path), reserved_path).map_err(|e| {
+            AppError::IoError(format!("Failed to save output as {}: {}", reserved_path.display(), e))
+        })?;
+
+        let run_json_path = std::path::Path::new(run_output_path).with_extension("json");
+        let reserved_json_path = reserved_path.with_extension("json");
+        if run_json_path.exists() && !reserved_json_path.exists() {
+            let _ = crate::utils::move_file(&run_json_path, &reserved_json_path);
+        }
+
+        println!("🔥 Output saved as: {}", reserved_path.display());
+        Ok(reserved_path.to_string_lossy().to_string())
+    }
+
+    /// Parse the CLI's JSON output into timestamped segments
+    pub fn parse_segments_json(content: &str) -> AppResult<Vec<Segment>> {
+        #[derive(Deserialize)]
+        struct JsonOutput {
+            segments: Vec<Segment>,
+        }
+
+        let output: JsonOutput = serde_json::from_str(content)?;
+        Ok(output.segments)
+    }
+
+    /// Join segment texts into a single transcription string
+    pub fn concatenate_segments(segments: &[Segment]) -> String {
+        segments
+            .iter()
+            .map(|segment| segment.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 
     /// Execute a raw CLI command for testing
     pub async fn execute_raw_command(&self, args: &[&str]) -> AppResult<CliResult> {
-        if self.use_sidecar {
+        if self.should_use_sidecar()? {
             let cli_path = self.find_sidecar_path()?;
             let output = timeout(
                 Duration::from_secs(30),
@@ -1120,7 +2352,7 @@ impl CliManager {
                 exit_code: output.status.code().unwrap_or(-1),
             })
         } else {
-            let cli_command = self.find_dev_cli_command();
+            let cli_command = self.dev_cli_command();
             let output = timeout(
                 Duration::from_secs(30),
                 tokio::process::Command::new(&cli_command)
@@ -1143,9 +2375,219 @@ impl CliManager {
         }
     }
 
-    /// Parses a line of CLI output and emits a progress event if progress information is found.
-    fn parse_and_emit_progress(line: &str, callback: &ProgressCallback, file_path: &str) {
-        println!("🔥 CLI Output: {}", line);
+    /// Like `execute_raw_command`, but hands each output line to `callback` as it arrives and
+    /// uses the manager's timeout, for long operations such as model downloads
+    pub async fn execute_raw_command_streaming(&self, args: &[&str], callback: OutputLineCallback) -> AppResult<CliResult> {
+        let program = if self.should_use_sidecar()? {
+            self.find_sidecar_path()?
+        } else {
+            self.dev_cli_command()
+        };
+
+        let mut child = tokio::process::Command::new(&program)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| AppError::CliError(format!("Failed to execute command: {}", e)))?;
+
+        let stdout = child.stdout.take().ok_or_else(|| AppError::CliError("Failed to capture stdout".to_string()))?;
+        let stderr = child.stderr.take().ok_or_else(|| AppError::CliError("Failed to capture stderr".to_string()))?;
+        let stdout_reader = tokio::spawn(Self::stream_output_lines(stdout, CliLogStream::Stdout, callback.clone()));
+        let stderr_reader = tokio::spawn(Self::stream_output_lines(stderr, CliLogStream::Stderr, callback));
+
+        // Dropping the child on timeout kills it
+        let status = self.with_process_timeout(child.wait()).await
+            .map_err(|_| AppError::CliError("Command timed out".to_string()))?
+            .map_err(|e| AppError::CliError(format!("Failed to execute command: {}", e)))?;
+
+        let output = stdout_reader.await.unwrap_or_default();
+        let error = stderr_reader.await.unwrap_or_default();
+        Ok(CliResult {
+            success: status.success(),
+            output,
+            error: if error.is_empty() { None } else { Some(error) },
+            exit_code: status.code().unwrap_or(-1),
+        })
+    }
+
+    /// Forward every line of `reader` to `callback`, returning everything that was read
+    async fn stream_output_lines<R>(reader: R, stream: CliLogStream, callback: OutputLineCallback) -> String
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let mut collected = String::new();
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            callback(stream, &line);
+            collected.push_str(&line);
+            collected.push('\n');
+        }
+        collected
+    }
+
+    /// Parse a language detection line (e.g., "Detected language: Korean (ko)") into a language code
+    pub fn parse_detected_language(line: &str) -> Option<String> {
+        let detected_regex = Regex::new(r"Detected language:\s*([^(]+?)\s*(?:\((\w+)\))?\s*$").unwrap();
+        let caps = detected_regex.captures(line.trim())?;
+
+        // Prefer the language code in parentheses; Whisper itself prints only the name
+        if let Some(code) = caps.get(2) {
+            return Some(code.as_str().to_lowercase());
+        }
+        let name = caps.get(1)?.as_str().trim();
+        match crate::models::language_code_for_name(name) {
+            Some(code) => Some(code.to_string()),
+            // Anything else is only usable if it is already a code
+            None => crate::models::is_supported_language(name).then(|| name.to_lowercase()),
+        }
+    }
+
+    /// Store the detected language if the line reports one
+    fn record_detected_language(line: &str, detected_language: &std::sync::Mutex<Option<String>>) {
+        if let Some(language) = Self::parse_detected_language(line) {
+            println!("🔥 Detected language: {}", language);
+            if let Ok(mut detected) = detected_language.lock() {
+                *detected = Some(language);
+            }
+        }
+    }
+
+    /// Find the last language detection line in output that was captured in one piece
+    fn captured_detected_language(output: &std::process::Output) -> Option<String> {
+        [&output.stdout, &output.stderr]
+            .into_iter()
+            .flat_map(|captured| {
+                String::from_utf8_lossy(captured)
+                    .lines()
+                    .filter_map(Self::parse_detected_language)
+                    .collect::<Vec<_>>()
+            })
+            .last()
+    }
+
+    /// Parse a segment line (e.g., "[00:00.000 --> 00:04.500] Hello") into its time range and text
+    pub fn parse_segment_line(line: &str) -> Option<Segment> {
+        let segment_regex = Regex::new(
+            r"^\s*\[((?:\d+:)?\d+:\d{2}\.\d{3})\s*-->\s*((?:\d+:)?\d+:\d{2}\.\d{3})\]\s*(.*)$"
+        ).unwrap();
+        let caps = segment_regex.captures(line)?;
+
+        let text = caps[3].trim();
+        if text.is_empty() {
+            return None;
+        }
+
+        Some(Segment {
+            start: Self::parse_segment_timestamp(&caps[1])?,
+            end: Self::parse_segment_timestamp(&caps[2])?,
+            text: text.to_string(),
+            speaker: None,
+        })
+    }
+
+    /// Convert a "[HH:]MM:SS.mmm" timestamp to seconds
+    fn parse_segment_timestamp(timestamp: &str) -> Option<f64> {
+        timestamp
+            .split(':')
+            .try_fold(0.0, |total, part| part.parse::<f64>().ok().map(|value| total * 60.0 + value))
+    }
+
+    /// Forward the text of a segment line to the partial text callback
+    fn emit_partial_text(line: &str, callback: &PartialTextCallback, file_path: &str) {
+        if let Some(segment) = Self::parse_segment_line(line) {
+            callback(PartialTranscription {
+                file_path: file_path.to_string(),
+                job_id: None,
+                start: segment.start,
+                end: segment.end,
+                text: segment.text,
+            });
+        }
+    }
+
+    /// Forward output that was captured in one piece instead of streamed to the log callback
+    fn emit_captured_output(&self, output: &std::process::Output, file_path: &str) {
+        let Some(ref callback) = self.log_line_callback else {
+            return;
+        };
+        for (captured, stream) in [(&output.stdout, CliLogStream::Stdout), (&output.stderr, CliLogStream::Stderr)] {
+            for line in String::from_utf8_lossy(captured).lines() {
+                Self::emit_log_line(line, stream, callback, file_path);
+            }
+        }
+    }
+
+    /// Forward one line of CLI output to the log callback
+    fn emit_log_line(line: &str, stream: CliLogStream, callback: &LogLineCallback, file_path: &str) {
+        callback(CliLogLine {
+            job_id: None,
+            file_path: file_path.to_string(),
+            stream,
+            line: line.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// Parse a model download progress bar into (percent, downloaded, total).
+    /// Download bars report byte sizes ("62.6M/139M"), unlike the frame counts of transcription bars.
+    pub fn parse_download_progress(line: &str) -> Option<(f64, String, String)> {
+        // tqdm redraws with carriage returns, so only the latest redraw matters
+        let line = line.rsplit('\r').find(|part| !part.trim().is_empty())?;
+
+        let download_regex = Regex::new(
+            r"(\d{1,3})%\|[^|]*\|\s*([\d.]+\s*[kMGT]i?B?)/([\d.]+\s*[kMGT]i?B?)\s*\["
+        ).unwrap();
+        let caps = download_regex.captures(line)?;
+        let percent = caps[1].parse::<f64>().ok()?;
+
+        Some((percent.min(100.0), caps[2].trim().to_string(), caps[3].trim().to_string()))
+    }
+
+    /// How far through preprocessing (0-100%) a line from that phase is. FFmpeg reports the
+    /// audio time converted so far ("size= 512kB time=00:01:02.50 bitrate=..."), without the total.
+    pub fn parse_preprocessing_progress(line: &str) -> Option<f64> {
+        if line.contains("Audio preprocessing completed") {
+            return Some(100.0);
+        }
+        if line.contains("Preprocessing audio file") {
+            return Some(0.0);
+        }
+
+        let ffmpeg_regex = Regex::new(r"size=.*time=(\d+):(\d{2}):(\d{2}(?:\.\d+)?)").unwrap();
+        let caps = ffmpeg_regex.captures(line)?;
+        let seconds = caps[1].parse::<f64>().ok()? * 3600.0 + caps[2].parse::<f64>().ok()? * 60.0 + caps[3].parse::<f64>().ok()?;
+        Some((seconds / ASSUMED_AUDIO_SECONDS * 100.0).min(90.0))
+    }
+
+    /// Stage and overall progress for the CLI's messages after transcription (postprocessing,
+    /// saving, done). They stay below the Finalizing update sent once the CLI exits.
+    pub fn parse_finalization_progress(line: &str) -> Option<(ProcessingStage, f64, &'static str)> {
+        let line = line.to_lowercase();
+        if line.contains("postprocessing") || line.contains("post-processing") {
+            return Some((ProcessingStage::Postprocessing, SAVING_PROGRESS_BAND.0, "Postprocessing transcription..."));
+        }
+
+        let saving_regex = Regex::new(r"\bsaving\b.*\b(?:to|transcription|output|results?)\b").unwrap();
+        if saving_regex.is_match(&line) {
+            return Some((ProcessingStage::Saving, progress_in_band(20.0, SAVING_PROGRESS_BAND), "Saving transcription..."));
+        }
+
+        // "Done" on its own, possibly after a log prefix, or the CLI's completion message
+        let done_regex = Regex::new(r"(?:^|\s-\s)\s*done\b|transcription complete").unwrap();
+        if done_regex.is_match(line.trim()) {
+            return Some((ProcessingStage::Saving, progress_in_band(30.0, SAVING_PROGRESS_BAND), "Finishing transcription..."));
+        }
+
+        None
+    }
+
+    /// Parses a line of CLI output and emits a progress event if progress information is found.
+    fn parse_and_emit_progress(line: &str, callback: &ProgressCallback, file_path: &str) {
+        println!("🔥 CLI Output: {}", line);
         
         // Log all CLI output to desktop for debugging
         let desktop_path = dirs::desktop_dir().unwrap_or_else(|| std::env::temp_dir());
@@ -1163,6 +2605,41 @@ impl CliManager {
         // Skip sending basic CLI output as progress to avoid overriding real progress
         // Only send important messages, not every line of output
 
+        // Pattern 0: model download bar (e.g., " 45%|████▌     | 62.6M/139M [00:03<00:04, 18.5MiB/s]")
+        if let Some((percent, downloaded, total)) = Self::parse_download_progress(line) {
+            callback(ProcessingProgress {
+                stage: ProcessingStage::Downloading,
+                progress: progress_in_band(percent, DOWNLOADING_PROGRESS_BAND),
+                current_file: Some(file_path.to_string()),
+                timestamp: Utc::now(),
+                message: Some(format!("Downloading model: {}% ({}/{})", percent as u8, downloaded, total)),
+                job_id: None,
+                file_index: None,
+                total_files: None,
+                can_cancel: true,
+                estimated_completion: None,
+            });
+            return;
+        }
+
+        // Preprocessing (decoding and resampling before Whisper runs) fills the first band
+        if let Some(percent) = Self::parse_preprocessing_progress(line) {
+            let progress = progress_in_band(percent, PREPROCESSING_PROGRESS_BAND);
+            callback(ProcessingProgress {
+                stage: ProcessingStage::Preprocessing,
+                progress,
+                current_file: Some(file_path.to_string()),
+                timestamp: Utc::now(),
+                message: Some(format!("Preprocessing audio: {:.0}%", percent)),
+                job_id: None,
+                file_index: None,
+                total_files: None,
+                can_cancel: true,
+                estimated_completion: None,
+            });
+            return;
+        }
+
         // Pattern 1: Whisper segment progress (e.g., "[00:00.000 --> 00:30.000]")
         let segment_regex = Regex::new(r"\[(\d{2}):(\d{2})\.(\d{3}) --> (\d{2}):(\d{2})\.(\d{3})\]").unwrap();
         if let Some(caps) = segment_regex.captures(line) {
@@ -1174,10 +2651,9 @@ impl CliManager {
                 caps[5].parse::<f64>(),
             ) {
                 let current_time = end_min * 60.0 + end_sec;
-                // Assume audio is roughly 5 minutes long for progress calculation
                 // This is a rough estimate since we don't know total duration
-                let estimated_progress = (current_time / 300.0) * 100.0;
-                let progress = if estimated_progress > 90.0 { 90.0 } else { estimated_progress };
+                let estimated_progress = (current_time / ASSUMED_AUDIO_SECONDS) * 100.0;
+                let progress = progress_in_band(estimated_progress.min(90.0), TRANSCRIBING_PROGRESS_BAND);
 
                 callback(ProcessingProgress {
                     stage: ProcessingStage::Transcribing,
@@ -1189,6 +2665,7 @@ impl CliManager {
                     file_index: None,
                     total_files: None,
                     can_cancel: true,
+                    estimated_completion: None,
                 });
                 return;
             }
@@ -1204,7 +2681,7 @@ impl CliManager {
             ) {
                 callback(ProcessingProgress {
                     stage: ProcessingStage::Transcribing,
-                    progress: percent,
+                    progress: progress_in_band(percent, TRANSCRIBING_PROGRESS_BAND),
                     current_file: Some(file_path.to_string()),
                     timestamp: Utc::now(),
                     message: Some(format!("Transcribing: {}% ({}/{})", percent as u8, current as u64, total as u64)),
@@ -1212,6 +2689,7 @@ impl CliManager {
                     file_index: None,
                     total_files: None,
                     can_cancel: true,
+                    estimated_completion: None,
                 });
                 return;
             }
@@ -1227,7 +2705,7 @@ impl CliManager {
             ) {
                 callback(ProcessingProgress {
                     stage: ProcessingStage::Transcribing,
-                    progress: percent,
+                    progress: progress_in_band(percent, TRANSCRIBING_PROGRESS_BAND),
                     current_file: Some(file_path.to_string()),
                     timestamp: Utc::now(),
                     message: Some(format!("Processing file: {}% ({}/{})", percent as u8, current as u64, total as u64)),
@@ -1235,18 +2713,38 @@ impl CliManager {
                     file_index: None,
                     total_files: None,
                     can_cancel: true,
+                    estimated_completion: None,
                 });
                 return;
             }
         }
 
+        // Postprocessing and saving after the last segment, so the bar doesn't sit at the end of
+        // the transcribing band until the CLI exits. Segment lines were matched above, so their
+        // text can't be mistaken for these messages.
+        if let Some((stage, progress, message)) = Self::parse_finalization_progress(line) {
+            callback(ProcessingProgress {
+                stage,
+                progress,
+                current_file: Some(file_path.to_string()),
+                timestamp: Utc::now(),
+                message: Some(message.to_string()),
+                job_id: None,
+                file_index: None,
+                total_files: None,
+                can_cancel: true,
+                estimated_completion: None,
+            });
+            return;
+        }
+
         // Pattern 4: Simple percentage (e.g., "50%" or "Processing: 50%")
         let percent_regex = Regex::new(r"(\d+)%").unwrap();
         if let Some(caps) = percent_regex.captures(line) {
             if let Ok(percent) = caps[1].parse::<f64>() {
                 callback(ProcessingProgress {
                     stage: ProcessingStage::Transcribing,
-                    progress: percent,
+                    progress: progress_in_band(percent, TRANSCRIBING_PROGRESS_BAND),
                     current_file: Some(file_path.to_string()),
                     timestamp: Utc::now(),
                     message: Some(format!("Transcribing: {}%", percent as u8)),
@@ -1254,6 +2752,7 @@ impl CliManager {
                     file_index: None,
                     total_files: None,
                     can_cancel: true,
+                    estimated_completion: None,
                 });
                 return;
             }
@@ -1271,6 +2770,7 @@ impl CliManager {
                 file_index: None,
                 total_files: None,
                 can_cancel: true,
+                estimated_completion: None,
             });
             return;
         }
@@ -1279,7 +2779,7 @@ impl CliManager {
         if line.contains("Transcribing") && !line.contains("Loading") {
             callback(ProcessingProgress {
                 stage: ProcessingStage::Transcribing,
-                progress: 25.0,
+                progress: TRANSCRIBING_PROGRESS_BAND.0,
                 current_file: Some(file_path.to_string()),
                 timestamp: Utc::now(),
                 message: Some("Transcribing audio...".to_string()),
@@ -1287,6 +2787,7 @@ impl CliManager {
                 file_index: None,
                 total_files: None,
                 can_cancel: true,
+                estimated_completion: None,
             });
             return;
         }
@@ -1318,6 +2819,21 @@ mod tests {
         assert_eq!(manager.timeout_duration, Duration::from_secs(30));
     }
 
+    #[tokio::test]
+    async fn test_zero_timeout_is_unbounded() {
+        let manager = CliManager::default().with_timeout(Duration::from_secs(30));
+        assert_eq!(manager.timeout(), Some(Duration::from_secs(30)));
+
+        let manager = CliManager::default().with_timeout(Duration::ZERO);
+        assert_eq!(manager.timeout(), None);
+        let result = manager.with_process_timeout(tokio::time::sleep(Duration::from_millis(20))).await;
+        assert!(result.is_ok());
+
+        let manager = CliManager::default().with_timeout(Duration::from_millis(5));
+        let result = manager.with_process_timeout(tokio::time::sleep(Duration::from_millis(200))).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_execute_raw_command_with_echo() {
         // Use echo command which should be available on most systems
@@ -1344,7 +2860,7 @@ mod tests {
         let manager = CliManager::default();
         let temp_dir = tempdir().unwrap();
         let file_path = temp_dir.path().join("test.m4a");
-        File::create(&file_path).unwrap();
+        std::fs::write(&file_path, b"audio").unwrap();
         
         let settings = AppSettings::default();
         let output = b"Test transcription output";
@@ -1361,6 +2877,1182 @@ mod tests {
         assert!(!result.id.is_empty());
     }
 
+    #[test]
+    fn test_build_cli_args_auto_language() {
+        let mut settings = AppSettings::default();
+        settings.language = "auto".to_string();
+        settings.include_metadata = false;
+        settings.output_directory = String::new();
+
+        let args = CliManager::build_cli_args("/tmp/audio.m4a", &settings);
+        assert_eq!(args, vec!["/tmp/audio.m4a", "--language", "auto", "--model-size", "base"]);
+
+        settings.language = "en".to_string();
+        let args = CliManager::build_cli_args("/tmp/audio.m4a", &settings);
+        assert_eq!(args[1..3], ["--language".to_string(), "en".to_string()]);
+    }
+
+    #[test]
+    fn test_build_cli_args_decoding_parameters() {
+        let settings = AppSettings { temperature: 0.2, beam_size: 8, ..AppSettings::default() };
+        let args = CliManager::build_cli_args("/tmp/meeting.m4a", &settings);
+
+        let value_after = |flag: &str| args.iter().position(|arg| arg == flag).map(|position| args[position + 1].as_str());
+        assert_eq!(value_after("--temperature"), Some("0.2"));
+        assert_eq!(value_after("--beam-size"), Some("8"));
+
+        let args = CliManager::build_cli_args("/tmp/meeting.m4a", &AppSettings::default());
+        assert!(!args.iter().any(|arg| arg == "--temperature" || arg == "--beam-size"), "{:?}", args);
+    }
+
+    #[test]
+    fn test_build_cli_args_initial_prompt() {
+        let settings = AppSettings {
+            initial_prompt: Some("-- Q3 \"budget\" review;\n$HOME `date`".to_string()),
+            custom_vocabulary: vec!["김민수".to_string(), "  ".to_string(), "Kubernetes\t(k8s)".to_string()],
+            ..AppSettings::default()
+        };
+
+        let args = CliManager::build_cli_args("/tmp/meeting.m4a", &settings);
+        let prompt_args: Vec<&String> = args.iter().filter(|arg| arg.starts_with("--initial-prompt")).collect();
+        assert_eq!(prompt_args, vec!["--initial-prompt=-- Q3 \"budget\" review; $HOME `date` 김민수, Kubernetes (k8s)"]);
+
+        let vocabulary_only = AppSettings { initial_prompt: None, ..settings.clone() };
+        assert_eq!(CliManager::initial_prompt(&vocabulary_only).as_deref(), Some("김민수, Kubernetes (k8s)"));
+
+        let blank = AppSettings { initial_prompt: Some(" \n ".to_string()), custom_vocabulary: Vec::new(), ..settings };
+        assert!(CliManager::build_cli_args("/tmp/meeting.m4a", &blank).iter().all(|arg| !arg.starts_with("--initial-prompt")));
+    }
+
+    #[test]
+    fn test_output_dir_is_passed_and_read_directly() {
+        let temp_dir = tempdir().unwrap();
+        let mut settings = AppSettings::default();
+        settings.output_directory = temp_dir.path().to_string_lossy().to_string();
+
+        let args = CliManager::build_cli_args("/tmp/meeting.m4a", &settings);
+        let position = args.iter().position(|arg| arg == "--output-dir").unwrap();
+        assert_eq!(args[position + 1], settings.output_directory);
+
+        std::fs::write(temp_dir.path().join("meeting_transcription_202601011200.txt"), "hello").unwrap();
+        std::fs::write(temp_dir.path().join("meeting_notes_transcription_202601011200.txt"), "other").unwrap();
+        std::fs::write(temp_dir.path().join("meeting.txt"), "unrelated").unwrap();
+
+        let found = CliManager::find_output_in_directory(temp_dir.path(), "meeting", None).unwrap();
+        assert_eq!(found, temp_dir.path().join("meeting_transcription_202601011200.txt"));
+        assert!(CliManager::find_output_in_directory(temp_dir.path(), "missing", None).is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_concurrent_jobs_write_distinct_output_paths() {
+        // Both runs finish in the same minute, so the CLI gives their outputs the same name
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), r#"sleep 0.3
+write_output "$input""#);
+
+        let output_dir = temp_dir.path().join("out");
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let inputs: Vec<String> = ["a", "b"]
+            .iter()
+            .map(|folder| {
+                let folder = temp_dir.path().join(folder);
+                std::fs::create_dir(&folder).unwrap();
+                let input = folder.join("memo.m4a");
+                std::fs::write(&input, b"audio").unwrap();
+                input.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let (first, second) = tokio::join!(
+            manager.process_file(&inputs[0], &settings, None),
+            manager.process_file(&inputs[1], &settings, None),
+        );
+        let results = [first.unwrap(), second.unwrap()];
+
+        let mut names: Vec<String> = results
+            .iter()
+            .map(|result| std::path::Path::new(&result.output_path).file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["memo_transcription (1).txt", "memo_transcription.txt"]);
+        for result in &results {
+            assert!(std::path::Path::new(&result.output_path).starts_with(&output_dir));
+            assert_eq!(std::fs::read_to_string(&result.output_path).unwrap(), result.transcribed_text);
+        }
+        assert_ne!(results[0].transcribed_text, results[1].transcribed_text);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cli_binary_path_override() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let binary = temp_dir.path().join("my-speech-to-text");
+        std::fs::write(&binary, "#!/bin/sh\necho 'speech-to-text 9.9.9'\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        // The override wins over the sidecar lookup
+        let manager = CliManager::new().with_cli_binary_path(binary.to_string_lossy());
+        assert!(manager.check_cli_availability().await.unwrap());
+        assert_eq!(manager.get_cli_version().await.unwrap(), "speech-to-text 9.9.9");
+
+        let missing = CliManager::new().with_cli_binary_path(temp_dir.path().join("missing").to_string_lossy());
+        assert!(matches!(missing.check_cli_availability().await, Err(AppError::ConfigError(_))));
+
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let not_executable = CliManager::new().with_cli_binary_path(binary.to_string_lossy());
+        assert!(matches!(not_executable.get_cli_version().await, Err(AppError::ConfigError(_))));
+        assert!(not_executable.describe_resolved_cli().starts_with("custom binary (unusable): "));
+    }
+
+    #[test]
+    fn test_describe_resolved_cli() {
+        let description = CliManager::new_dev().describe_resolved_cli();
+        let path = description.strip_prefix("development: ").expect(&description);
+        assert!(!path.is_empty());
+
+        let temp_dir = tempdir().unwrap();
+        let binary = temp_dir.path().join("missing-cli");
+        let custom = CliManager::new().with_cli_binary_path(binary.to_string_lossy());
+        assert!(custom.describe_resolved_cli().contains("CLI binary not found"));
+    }
+
+    /// A CLI stand-in that writes which language it was asked to transcribe in
+    #[cfg(unix)]
+    fn write_language_echo_cli(dir: &std::path::Path) -> std::path::PathBuf {
+        crate::test_support::write_fake_cli(dir, r#"write_output "transcribed in $language""#)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_take_output_streams_leaves_streams_when_capture_fails() {
+        let mut child = tokio::process::Command::new("sh")
+            .args(["-c", "echo done >&2"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        assert!(CliManager::take_output_streams(&mut child).is_none());
+        let output = child.wait_with_output().await.unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stderr).trim(), "done");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_completion_reported_when_cli_closes_stdout_early() {
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), "exec 1>&- 2>&-\nwrite_output quiet");
+
+        let input = temp_dir.path().join("quiet.m4a");
+        std::fs::write(&input, b"audio").unwrap();
+        let settings = AppSettings {
+            output_directory: temp_dir.path().to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let callback: ProgressCallback = {
+            let updates = updates.clone();
+            Arc::new(move |progress: ProcessingProgress| updates.lock().unwrap().push(progress))
+        };
+
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let result = manager
+            .process_file_with_cancellation(&input.to_string_lossy(), &settings, Some(callback), None)
+            .await
+            .unwrap();
+
+        assert_eq!(result.transcribed_text, "quiet");
+        let last = updates.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(last.progress, SAVING_PROGRESS_BAND.1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_per_file_timeout_moves_on_to_next_file() {
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), r#"case "$input" in *wedged*) sleep 30 ;; esac
+write_output done"#);
+
+        let output_dir = temp_dir.path().join("out");
+        std::fs::create_dir(&output_dir).unwrap();
+        let file_paths: Vec<String> = ["wedged.m4a", "next.m4a"]
+            .iter()
+            .map(|name| {
+                let path = temp_dir.path().join(name);
+                std::fs::write(&path, b"audio").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            per_file_timeout_secs: 1,
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+
+        let error = manager.process_file_with_cancellation(&file_paths[0], &settings, None, None).await.unwrap_err();
+        assert!(matches!(error, AppError::Timeout(_)));
+
+        let started = std::time::Instant::now();
+        let results = manager.process_batch(&file_paths, &settings, None).await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].original_file.name, "next.m4a");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_per_file_timeout_excludes_wait_for_free_slot() {
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), "write_output done");
+        let audio = temp_dir.path().join("queued.m4a");
+        std::fs::write(&audio, b"audio").unwrap();
+
+        let settings = AppSettings {
+            output_directory: temp_dir.path().to_string_lossy().to_string(),
+            per_file_timeout_secs: 1,
+            max_concurrent_jobs: 1,
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let limiter = Arc::new(ConcurrencyLimiter::new(1));
+        let held = limiter.acquire().await.unwrap();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(1500)).await;
+            drop(held);
+        });
+
+        // The file queues past its time limit before the CLI even starts
+        let manager = CliManager::new_dev()
+            .with_cli_binary_path(binary.to_string_lossy())
+            .with_concurrency_limiter(limiter);
+        let result = manager.process_file(&audio.to_string_lossy(), &settings, None).await.unwrap();
+        assert_eq!(result.transcribed_text, "done");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_empty_output_reports_no_speech() {
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), "write_output ''");
+
+        let audio = temp_dir.path().join("silence.wav");
+        std::fs::write(&audio, b"audio").unwrap();
+        let output_dir = temp_dir.path().join("out");
+        std::fs::create_dir(&output_dir).unwrap();
+
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let result = manager.process_file(&audio.to_string_lossy(), &settings, None).await.unwrap();
+        assert!(result.no_speech_detected);
+        assert!(result.transcribed_text.is_empty());
+        assert!(std::path::Path::new(&result.output_path).is_file());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_detected_language_read_from_captured_output() {
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), "echo 'Detected language: Korean' >&2\nwrite_output '안녕하세요'");
+
+        let audio = temp_dir.path().join("greeting.m4a");
+        std::fs::write(&audio, b"audio").unwrap();
+        let settings = AppSettings {
+            language: "auto".to_string(),
+            output_directory: temp_dir.path().to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+
+        // Without a progress callback the output is captured instead of streamed
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let result = manager.process_file(&audio.to_string_lossy(), &settings, None).await.unwrap();
+        assert_eq!(result.metadata.language, "ko");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_captured_output_wait_honours_cancellation_and_timeout() {
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), "exec sleep 30");
+        let audio = temp_dir.path().join("stuck.m4a");
+        std::fs::write(&audio, b"audio").unwrap();
+        let audio_path = audio.to_string_lossy().to_string();
+        let settings = AppSettings {
+            output_directory: temp_dir.path().to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+
+        // Without a progress callback the CLI runs with captured output
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let token = CancellationToken::new();
+        let cancel_token = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            cancel_token.cancel();
+        });
+        let started = std::time::Instant::now();
+        let result = manager.process_file_with_cancellation(&audio_path, &settings, None, Some(token)).await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        let manager = manager.with_timeout(Duration::from_millis(200));
+        let started = std::time::Instant::now();
+        let error = manager.process_file(&audio_path, &settings, None).await.unwrap_err();
+        assert!(error.to_string().contains("timed out"), "unexpected error: {}", error);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_reprocess_result_with_new_language() {
+        let temp_dir = tempdir().unwrap();
+        let binary = write_language_echo_cli(temp_dir.path());
+
+        let audio = temp_dir.path().join("talk.m4a");
+        std::fs::write(&audio, b"audio").unwrap();
+        let output_dir = temp_dir.path().join("out");
+        std::fs::create_dir(&output_dir).unwrap();
+
+        let settings = AppSettings {
+            language: "ko".to_string(),
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let original = manager.process_file(&audio.to_string_lossy(), &settings, None).await.unwrap();
+        assert_eq!(original.metadata.language, "ko");
+
+        // Without an override the stored metadata reproduces the original run
+        let derived = CliManager::settings_for_result(&original, &AppSettings::default());
+        assert_eq!(derived.language, "ko");
+        assert_eq!(derived.model_size.to_string(), original.metadata.model_size);
+
+        let english = AppSettings { language: "en".to_string(), ..settings.clone() };
+        let reprocessed = manager.reprocess_result(&original, &english, None).await.unwrap();
+        assert_eq!(reprocessed.metadata.language, "en");
+        assert_eq!(reprocessed.transcribed_text, "transcribed in en");
+        assert_eq!(reprocessed.original_file.path, original.original_file.path);
+
+        std::fs::remove_file(&audio).unwrap();
+        assert!(matches!(manager.reprocess_result(&original, &english, None).await, Err(AppError::FileNotFound(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_finalizing_stage_precedes_saving() {
+        let temp_dir = tempdir().unwrap();
+        let binary = write_language_echo_cli(temp_dir.path());
+        let audio = temp_dir.path().join("memo.m4a");
+        std::fs::write(&audio, b"audio").unwrap();
+
+        let settings = AppSettings {
+            output_directory: temp_dir.path().to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let stages = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = stages.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| recorded.lock().unwrap().push(progress.stage));
+
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        manager.process_file(&audio.to_string_lossy(), &settings, Some(callback)).await.unwrap();
+
+        let stages = stages.lock().unwrap();
+        let finalizing = stages.iter().position(|stage| *stage == ProcessingStage::Finalizing).expect("no Finalizing stage");
+        let saving = stages.iter().rposition(|stage| *stage == ProcessingStage::Saving).expect("no Saving stage");
+        assert!(finalizing < saving, "{:?}", stages);
+        assert_eq!(stages.last(), Some(&ProcessingStage::Saving));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_process_batch_with_per_file_languages() {
+        let temp_dir = tempdir().unwrap();
+        let binary = write_language_echo_cli(temp_dir.path());
+        let output_dir = temp_dir.path().join("out");
+        std::fs::create_dir(&output_dir).unwrap();
+
+        let file_paths: Vec<String> = ["korean.m4a", "english.m4a", "default.m4a"]
+            .iter()
+            .map(|name| {
+                let path = temp_dir.path().join(name);
+                std::fs::write(&path, b"audio").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let settings = AppSettings {
+            language: "ja".to_string(),
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let file_settings = vec![
+            Some(AppSettings { language: "ko".to_string(), ..settings.clone() }),
+            Some(AppSettings { language: "en".to_string(), ..settings.clone() }),
+            None,
+        ];
+
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let results = manager.process_batch_with_overrides(&file_paths, &settings, &file_settings, None).await.unwrap();
+
+        let languages: Vec<&str> = results.iter().map(|result| result.metadata.language.as_str()).collect();
+        assert_eq!(languages, vec!["ko", "en", "ja"]);
+        assert_eq!(results[1].transcribed_text, "transcribed in en");
+
+        // Overrides that don't line up with the files are rejected
+        let misaligned = manager.process_batch_with_overrides(&file_paths, &settings, &file_settings[..2], None).await;
+        assert!(matches!(misaligned, Err(AppError::ConfigError(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_process_batch_stops_when_cancelled() {
+        let temp_dir = tempdir().unwrap();
+        let binary = write_language_echo_cli(temp_dir.path());
+        let output_dir = temp_dir.path().join("out");
+        std::fs::create_dir(&output_dir).unwrap();
+
+        let file_paths: Vec<String> = ["one.m4a", "two.m4a", "three.m4a"]
+            .iter()
+            .map(|name| {
+                let path = temp_dir.path().join(name);
+                std::fs::write(&path, b"audio").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+
+        // Cancel as soon as the second file is announced
+        let token = CancellationToken::new();
+        let cancel_token = token.clone();
+        let callback: ProgressCallback = Arc::new(move |progress: ProcessingProgress| {
+            if progress.file_index == Some(1) {
+                cancel_token.cancel();
+            }
+        });
+
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let results = manager
+            .process_batch_with_cancellation(&file_paths, &settings, Some(callback), Some(token))
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].original_file.name, "one.m4a");
+        let outputs = std::fs::read_dir(&output_dir)
+            .unwrap()
+            .filter(|entry| !entry.as_ref().unwrap().file_name().to_string_lossy().ends_with(".meta.json"))
+            .count();
+        assert_eq!(outputs, 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_process_batch_skips_existing_output() {
+        let temp_dir = tempdir().unwrap();
+        let binary = write_language_echo_cli(temp_dir.path());
+        let output_dir = temp_dir.path().join("out");
+        std::fs::create_dir(&output_dir).unwrap();
+
+        let file_paths: Vec<String> = ["done.m4a", "new.m4a"]
+            .iter()
+            .map(|name| {
+                let path = temp_dir.path().join(name);
+                std::fs::write(&path, b"audio").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let existing_output = output_dir.join("done_transcription.txt");
+        std::fs::write(&existing_output, "earlier run").unwrap();
+
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            skip_existing_output: true,
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let results = manager.process_batch(&file_paths, &settings, None).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].original_file.name, "new.m4a");
+        assert_eq!(std::fs::read_to_string(&existing_output).unwrap(), "earlier run");
+        assert!(!output_dir.join("done_transcription (1).txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_stale_workdir_output_is_not_returned() {
+        let work_dir = tempdir().unwrap();
+        std::fs::create_dir(work_dir.path().join("output")).unwrap();
+        // Unique, so nothing in the home directory fallback matches either
+        let base_name = format!("stale-{}", crate::utils::generate_id());
+        std::fs::write(work_dir.path().join(format!("{}_transcription_202501010000.txt", base_name)), "previous run").unwrap();
+        std::fs::write(work_dir.path().join("output").join(format!("{}.txt", base_name)), "previous run").unwrap();
+        let other_output = work_dir.path().join("other_transcription.txt");
+        std::fs::write(&other_output, "another file").unwrap();
+
+        let (text, _) = CliManager::search_output_file(work_dir.path(), &base_name, None).await.unwrap();
+        assert_eq!(text, "previous run");
+
+        let report = crate::utils::clear_stale_outputs(work_dir.path(), &base_name).unwrap();
+        assert_eq!(report.files_removed, 2);
+        assert!(CliManager::search_output_file(work_dir.path(), &base_name, None).await.is_err());
+        assert!(other_output.exists());
+    }
+
+    #[tokio::test]
+    async fn test_output_search_requires_exact_base_name_from_current_run() {
+        let work_dir = tempdir().unwrap();
+        let base_name = format!("meeting-{}", crate::utils::generate_id());
+        let output = work_dir.path().join(format!("{}_transcription_202601011200.txt", base_name));
+        std::fs::write(&output, "this run").unwrap();
+        // Written last, so it is the newest file that merely contains the base name
+        std::fs::write(work_dir.path().join(format!("{}_notes_transcription_202601011200.txt", base_name)), "other").unwrap();
+
+        let run_started = Some(std::time::SystemTime::now() - Duration::from_secs(1));
+        let (text, path) = CliManager::search_output_file(work_dir.path(), &base_name, run_started).await.unwrap();
+        assert_eq!(text, "this run");
+        assert_eq!(path, output.to_string_lossy());
+        assert_eq!(CliManager::find_output_in_directory(work_dir.path(), &base_name, run_started), Some(output.clone()));
+
+        // An output from before the run started belongs to an earlier run
+        let stale_time = std::time::SystemTime::now() - Duration::from_secs(3600);
+        std::fs::File::options().write(true).open(&output).unwrap().set_modified(stale_time).unwrap();
+        assert!(CliManager::find_output_in_directory(work_dir.path(), &base_name, run_started).is_none());
+        assert!(CliManager::search_output_file(work_dir.path(), &base_name, run_started).await.is_err());
+        assert!(CliManager::find_output_in_directory(work_dir.path(), &base_name, None).is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_raw_command_streaming_delivers_every_line() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let binary = temp_dir.path().join("fake-download");
+        std::fs::write(&binary, "#!/bin/sh\necho \"downloading $1\"\necho 'progress 50%' >&2\necho 'progress 100%' >&2\necho done\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let callback: OutputLineCallback = Arc::new(move |stream, line| {
+            received_clone.lock().unwrap().push((stream, line.to_string()));
+        });
+
+        let manager = CliManager::new_dev()
+            .with_cli_binary_path(binary.to_string_lossy())
+            .with_timeout(Duration::from_secs(10));
+        let result = manager.execute_raw_command_streaming(&["tiny"], callback).await.unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.output, "downloading tiny\ndone\n");
+        assert_eq!(result.error.as_deref(), Some("progress 50%\nprogress 100%\n"));
+
+        let received = received.lock().unwrap();
+        let stdout: Vec<&str> = received.iter().filter(|(stream, _)| *stream == CliLogStream::Stdout).map(|(_, line)| line.as_str()).collect();
+        let stderr: Vec<&str> = received.iter().filter(|(stream, _)| *stream == CliLogStream::Stderr).map(|(_, line)| line.as_str()).collect();
+        assert_eq!(stdout, vec!["downloading tiny", "done"]);
+        assert_eq!(stderr, vec!["progress 50%", "progress 100%"]);
+    }
+
+    #[test]
+    fn test_parse_detected_language() {
+        assert_eq!(CliManager::parse_detected_language("Detected language: Korean (ko)"), Some("ko".to_string()));
+        assert_eq!(CliManager::parse_detected_language("Detected language: English"), Some("en".to_string()));
+        assert_eq!(CliManager::parse_detected_language("Detected language: Haitian Creole"), Some("ht".to_string()));
+        assert_eq!(CliManager::parse_detected_language("Detected language: Klingon"), None);
+        assert_eq!(CliManager::parse_detected_language("Transcribing audio..."), None);
+    }
+
+    #[test]
+    fn test_parse_segment_line() {
+        let segment = CliManager::parse_segment_line("[00:01.500 --> 00:04.250]  안녕하세요 ").unwrap();
+        assert_eq!(segment, Segment { start: 1.5, end: 4.25, text: "안녕하세요".to_string(), speaker: None });
+
+        let segment = CliManager::parse_segment_line("[01:00:00.000 --> 01:00:02.000] Hello").unwrap();
+        assert_eq!(segment.start, 3600.0);
+        assert_eq!(segment.text, "Hello");
+
+        assert_eq!(CliManager::parse_segment_line("[00:00.000 --> 00:30.000]"), None);
+        assert_eq!(CliManager::parse_segment_line("Loading model..."), None);
+    }
+
+    #[test]
+    fn test_parse_download_progress() {
+        let line = " 45%|████▌     | 62.6M/139M [00:03<00:04, 18.5MiB/s]";
+        assert_eq!(
+            CliManager::parse_download_progress(line),
+            Some((45.0, "62.6M".to_string(), "139M".to_string()))
+        );
+
+        let redraws = " 10%|█         | 14.0M/139M [00:01<00:09, 13.2MiB/s]\r 20%|██        | 28.1M/139M [00:02<00:08, 13.4MiB/s]";
+        assert_eq!(CliManager::parse_download_progress(redraws).map(|(percent, _, _)| percent), Some(20.0));
+
+        // Transcription bars count frames, not bytes
+        let transcribing = "96%|█████████▌| 213478/222478 [03:06<00:07, 1146.02frames/s]";
+        assert_eq!(CliManager::parse_download_progress(transcribing), None);
+
+        // A finished download fills only its own band, not the file's progress
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let callback: ProgressCallback = Arc::new(move |progress: ProcessingProgress| {
+            received_clone.lock().unwrap().push(progress);
+        });
+        CliManager::parse_and_emit_progress("100%|██████████| 139M/139M [00:07<00:00, 18.5MiB/s]", &callback, "talk.m4a");
+        let received = received.lock().unwrap();
+        assert_eq!(received[0].stage, ProcessingStage::Downloading);
+        assert_eq!(received[0].progress, DOWNLOADING_PROGRESS_BAND.1);
+        assert!(received[0].progress <= PREPROCESSING_PROGRESS_BAND.0);
+    }
+
+    #[test]
+    fn test_parse_segments_json() {
+        let fixture = r#"{
+            "text": " 안녕하세요. 반갑습니다.",
+            "language": "ko",
+            "segments": [
+                {"id": 0, "start": 0.0, "end": 2.5, "text": " 안녕하세요."},
+                {"id": 1, "start": 2.5, "end": 4.8, "text": " 반갑습니다."}
+            ]
+        }"#;
+
+        let segments = CliManager::parse_segments_json(fixture).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].start, 2.5);
+        assert_eq!(segments[1].end, 4.8);
+        assert_eq!(CliManager::concatenate_segments(&segments), "안녕하세요. 반갑습니다.");
+
+        assert!(CliManager::parse_segments_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_segments_json_with_speakers() {
+        let fixture = r#"{
+            "text": " 안녕하세요. 네, 반갑습니다.",
+            "language": "ko",
+            "segments": [
+                {"id": 0, "start": 0.0, "end": 2.5, "text": " 안녕하세요.", "speaker": "SPEAKER_00"},
+                {"id": 1, "start": 2.5, "end": 4.8, "text": " 네, 반갑습니다.", "speaker": "SPEAKER_01"},
+                {"id": 2, "start": 4.8, "end": 6.0, "text": " 음."}
+            ]
+        }"#;
+
+        let segments = CliManager::parse_segments_json(fixture).unwrap();
+        let speakers: Vec<Option<&str>> = segments.iter().map(|segment| segment.speaker.as_deref()).collect();
+        assert_eq!(speakers, vec![Some("SPEAKER_00"), Some("SPEAKER_01"), None]);
+
+        let settings = AppSettings { enable_diarization: true, ..AppSettings::default() };
+        assert!(CliManager::build_cli_args("/tmp/meeting.m4a", &settings).contains(&"--diarize".to_string()));
+        assert!(!CliManager::build_cli_args("/tmp/meeting.m4a", &AppSettings::default()).contains(&"--diarize".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_extensionless_input_runs_under_detected_extension() {
+        // Like the Python CLI, the stand-in only accepts files with a supported suffix
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), r#"case "$input" in
+  *.wav|*.m4a|*.mp3|*.aac|*.flac) ;;
+  *) echo "Unsupported file format: $input" >&2; exit 1 ;;
+esac
+write_output "$input""#);
+
+        let input = temp_dir.path().join("recording");
+        let mut wav = b"RIFF\x24\x00\x00\x00WAVEfmt ".to_vec();
+        wav.resize(64, 0);
+        std::fs::write(&input, wav).unwrap();
+        let output_dir = temp_dir.path().join("out");
+        std::fs::create_dir(&output_dir).unwrap();
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let result = manager.process_file(&input.to_string_lossy(), &settings, None).await.unwrap();
+
+        // The CLI saw a .wav link, which is gone afterwards; the result names the real file
+        let staged = std::path::Path::new(&result.transcribed_text);
+        assert_eq!(staged.file_name().unwrap(), "recording.wav");
+        assert!(!staged.exists());
+        assert_eq!(result.original_file.path, input.to_string_lossy());
+        assert_eq!(result.original_file.format, "wav");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_second_run_loads_from_cache() {
+        let temp_dir = tempdir().unwrap();
+        let runs = temp_dir.path().join("runs");
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), &format!("echo run >> '{}'\nwrite_output 'cached text'", runs.display()));
+
+        let input = temp_dir.path().join("meeting.m4a");
+        std::fs::write(&input, b"audio").unwrap();
+        let settings = AppSettings {
+            output_directory: temp_dir.path().join("out").to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+        let manager = CliManager::new_dev()
+            .with_cli_binary_path(binary.to_string_lossy())
+            .with_transcription_cache(TranscriptionCache::new(temp_dir.path().join("cache")));
+
+        let first = manager.process_file(&input.to_string_lossy(), &settings, None).await.unwrap();
+        assert!(first.original_file.hash.is_some());
+        let second = manager.process_file(&input.to_string_lossy(), &settings, None).await.unwrap();
+        assert_eq!(second.transcribed_text, "cached text");
+        assert_eq!(second.processing_time, 0.0);
+        assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 1);
+
+        // Cache hits still get the finishing steps, such as the metadata sidecar
+        let sidecar = crate::export::metadata_sidecar_path(&second.output_path);
+        std::fs::remove_file(&sidecar).unwrap();
+        manager.process_file(&input.to_string_lossy(), &settings, None).await.unwrap();
+        assert!(sidecar.is_file());
+
+        // Without the transcript on disk the entry is useless, so the CLI runs again
+        std::fs::remove_file(&second.output_path).unwrap();
+        let rerun = manager.process_file(&input.to_string_lossy(), &settings, None).await.unwrap();
+        assert!(std::path::Path::new(&rerun.output_path).is_file());
+        assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 2);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_self_test_transcribes_sample_with_tiny_model() {
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), r#"case "$input" in --version) echo 'speech-to-text 1.0.0'; exit 0 ;; esac
+case " $args " in *" --model-size tiny "*) ;; *) echo "expected the tiny model: $args" >&2; exit 1 ;; esac
+write_output 'self test'"#);
+
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let result = manager.run_self_test(&AppSettings::default()).await.unwrap();
+        assert_eq!(result.transcribed_text, "self test");
+        assert_eq!(result.metadata.model_size, "tiny");
+        assert_eq!(result.original_file.format, "wav");
+    }
+
+    #[tokio::test]
+    async fn test_self_test_with_installed_cli() {
+        let manager = CliManager::new_dev();
+        if !manager.check_cli_availability().await.unwrap_or(false) {
+            println!("Skipping self-test: speech-to-text CLI is not installed");
+            return;
+        }
+
+        let result = manager.run_self_test(&AppSettings::default()).await.unwrap();
+        assert_eq!(result.metadata.model_size, "tiny");
+        assert_eq!(result.original_file.format, "wav");
+    }
+
+    #[test]
+    fn test_needs_wav_conversion() {
+        let settings = AppSettings::default();
+        for path in ["/audio/voice.ogg", "/audio/voice.OPUS", "/audio/voice.wma"] {
+            assert!(CliManager::needs_wav_conversion(path, &settings), "{}", path);
+        }
+        assert!(!CliManager::needs_wav_conversion("/audio/voice.mp3", &settings));
+        assert!(!CliManager::needs_wav_conversion("/audio/voice.m4a", &settings));
+
+        let transcoding = AppSettings { auto_transcode: true, ..AppSettings::default() };
+        assert!(CliManager::needs_wav_conversion("/audio/voice.mp3", &transcoding));
+        assert!(!CliManager::needs_wav_conversion("/audio/voice.wav", &transcoding));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_diarization_dropped_when_cli_lacks_option() {
+        // Like click, the stand-in rejects options it doesn't list in --help
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), r#"if [ "$input" = "--help" ]; then
+  echo "  --output-dir, -o TEXT  Output directory"
+  echo "  --language, -l TEXT    Language code"
+  exit 0
+fi
+case " $args " in *" --diarize "*) echo "Error: No such option: --diarize" >&2; exit 2 ;; esac
+write_output 'meeting notes'"#);
+
+        let input = temp_dir.path().join("meeting.m4a");
+        std::fs::write(&input, b"audio").unwrap();
+        let settings = AppSettings {
+            output_directory: temp_dir.path().to_string_lossy().to_string(),
+            enable_diarization: true,
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+
+        let manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        assert!(manager.cli_supports_option("--output-dir").await);
+        assert!(!manager.cli_supports_option("--diarize").await);
+
+        let result = manager.process_file(&input.to_string_lossy(), &settings, None).await.unwrap();
+        assert_eq!(result.transcribed_text, "meeting notes");
+        assert!(result.warnings.iter().any(|warning| warning.contains("diarization")), "{:?}", result.warnings);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_retry_with_backoff_recovers_after_failure() {
+        let temp_dir = tempdir().unwrap();
+        let marker = temp_dir.path().join("attempted");
+
+        // Mock command: fails on the first run, succeeds once the marker exists
+        let script = format!(
+            "if [ -f '{0}' ]; then exit 0; else touch '{0}'; exit 1; fi",
+            marker.display()
+        );
+
+        let mut retries = Vec::new();
+        let result = retry_with_backoff(2, Duration::from_millis(1), None, |attempt, total| retries.push((attempt, total)), || {
+            let script = script.clone();
+            async move {
+                let status = tokio::process::Command::new("sh").arg("-c").arg(&script).status().await?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(AppError::CliError(format!("Mock command failed: {:?}", status.code())))
+                }
+            }
+        }).await;
+
+        assert!(result.is_ok());
+        assert_eq!(retries, vec![(2, 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_skips_file_not_found() {
+        let mut attempts = 0;
+        let result: AppResult<()> = retry_with_backoff(3, Duration::from_millis(1), None, |_, _| {}, || {
+            attempts += 1;
+            async { Err(AppError::FileNotFound("missing.m4a".to_string())) }
+        }).await;
+
+        assert!(matches!(result, Err(AppError::FileNotFound(_))));
+        assert_eq!(attempts, 1);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let mut attempts = 0;
+        let result: AppResult<()> = retry_with_backoff(3, Duration::from_millis(1), Some(&token), |_, _| {}, || {
+            attempts += 1;
+            async { Err(AppError::ProcessingError("Processing was cancelled".to_string())) }
+        }).await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_classify_cli_failure() {
+        let model_missing = "Traceback (most recent call last):\n\
+            RuntimeError: Model large-v9 not found; available models = ['tiny', 'base', 'small', 'medium', 'large']";
+        assert!(matches!(classify_cli_failure(model_missing, Some(1)), AppError::ModelNotFound(line) if line.contains("large-v9")));
+
+        let ffmpeg_missing = "  File \"whisper/audio.py\", line 58, in load_audio\n\
+            FileNotFoundError: [Errno 2] No such file or directory: 'ffmpeg'";
+        assert!(matches!(classify_cli_failure(ffmpeg_missing, Some(1)), AppError::FfmpegMissing(_)));
+
+        let decode_failed = "RuntimeError: Failed to load audio: [mov,mp4,m4a] moov atom not found\n\
+            /audio/broken.m4a: Invalid data found when processing input";
+        assert!(matches!(classify_cli_failure(decode_failed, Some(1)), AppError::AudioDecodeError(_)));
+
+        match classify_cli_failure("Segmentation fault\n", Some(139)) {
+            AppError::CliError(message) => {
+                assert!(message.contains("Some(139)"));
+                assert!(message.contains("Segmentation fault"));
+            }
+            other => panic!("expected CliError, got {:?}", other),
+        }
+        assert!(matches!(classify_cli_failure("", None), AppError::CliError(_)));
+    }
+
+    #[test]
+    fn test_parse_missing_decoder() {
+        let stderr = "Input #0, amr, from 'call.amr':\n\
+            \x20 Stream #0:0: Audio: amr_wb, 16000 Hz, mono, flt\n\
+            Decoder (codec amr_wb) not found for input stream #0:0";
+        assert_eq!(parse_missing_decoder(stderr), Some("amr_wb".to_string()));
+
+        let newer = "[aist#0:0/atrac3p @ 0x600] Decoding requested, but no decoder found for: atrac3p";
+        assert_eq!(parse_missing_decoder(newer), Some("atrac3p".to_string()));
+
+        let unnamed = "Unsupported codec with id 86076 for input stream 0";
+        assert_eq!(parse_missing_decoder(unnamed), Some("unknown".to_string()));
+        assert_eq!(parse_missing_decoder("Invalid data found when processing input"), None);
+
+        match classify_cli_failure(&format!("RuntimeError: Failed to load audio: {}", stderr), Some(1)) {
+            AppError::AudioDecodeError(message) => {
+                assert!(message.contains("amr_wb"));
+                assert!(message.contains("Convert the file"));
+            }
+            other => panic!("expected AudioDecodeError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_skips_permanent_cli_failures() {
+        let mut attempts = 0;
+        let result: AppResult<()> = retry_with_backoff(3, Duration::from_millis(1), None, |_, _| {}, || {
+            attempts += 1;
+            async { Err(AppError::FfmpegMissing("ffmpeg".to_string())) }
+        }).await;
+
+        assert!(matches!(result, Err(AppError::FfmpegMissing(_))));
+        assert_eq!(attempts, 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_post_process_command_receives_output_path() {
+        let output_path = "/tmp/my recording_transcription.txt";
+
+        let stdout = run_post_process_command("echo", output_path, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(stdout.trim(), output_path);
+
+        // The path is also exported through the environment
+        let stdout = run_post_process_command("printf '%s|' \"$TRANSCRIPTION_PATH\"; echo", output_path, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(stdout.trim(), format!("{0}|{0}", output_path));
+
+        assert!(run_post_process_command("false", output_path, Duration::from_secs(5)).await.is_err());
+        assert!(run_post_process_command("sleep 5", output_path, Duration::from_millis(100)).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_finish_result_collects_warnings() {
+        let temp_dir = tempdir().unwrap();
+        let audio = temp_dir.path().join("quiet.m4a");
+        std::fs::write(&audio, b"audio").unwrap();
+
+        let manager = CliManager::default();
+        let settings = AppSettings {
+            include_metadata: false,
+            low_confidence_threshold: 0.6,
+            post_process_command: Some("false".to_string()),
+            ..AppSettings::default()
+        };
+        let mut result = manager
+            .parse_cli_output(&audio.to_string_lossy(), b"mumbled text", 1.0, &settings)
+            .await
+            .unwrap();
+        result.confidence = Some(0.3);
+
+        manager.finish_result(&mut result, &audio.to_string_lossy(), &settings).await;
+        assert_eq!(result.warnings.len(), 2, "{:?}", result.warnings);
+        assert!(result.warnings[0].starts_with("Low confidence (30%)"), "{:?}", result.warnings);
+        assert!(result.warnings[1].starts_with("Processing failed: Post-processing command failed"), "{:?}", result.warnings);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter_caps_running_jobs() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let limiter = Arc::new(ConcurrencyLimiter::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        // Mock "child processes" that only bump a counter while holding a permit
+        let mut handles = Vec::new();
+        for _ in 0..6 {
+            let limiter = limiter.clone();
+            let running = running.clone();
+            let max_running = max_running.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = limiter.acquire().await.unwrap();
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter_resize() {
+        let limiter = Arc::new(ConcurrencyLimiter::new(2));
+        let first = limiter.acquire().await.unwrap();
+        let second = limiter.acquire().await.unwrap();
+
+        // Shrinking while both permits are in use retires one once it is released
+        limiter.set_limit(1);
+        assert_eq!(limiter.limit(), 1);
+        drop(first);
+        assert!(timeout(Duration::from_millis(20), limiter.acquire()).await.is_err());
+        drop(second);
+        let _third = limiter.acquire().await.unwrap();
+
+        limiter.set_limit(3);
+        let _fourth = limiter.acquire().await.unwrap();
+        let _fifth = limiter.acquire().await.unwrap();
+    }
+
+    #[test]
+    fn test_preprocessing_progress_band() {
+        assert_eq!(CliManager::parse_preprocessing_progress("INFO - Preprocessing audio file: /audio/talk.m4a"), Some(0.0));
+        assert_eq!(CliManager::parse_preprocessing_progress("INFO - Audio preprocessing completed: /tmp/talk.wav"), Some(100.0));
+        assert_eq!(CliManager::parse_preprocessing_progress("Transcribing talk.m4a..."), None);
+
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let callback: ProgressCallback = Arc::new(move |progress: ProcessingProgress| {
+            received_clone.lock().unwrap().push(progress);
+        });
+
+        // Two and a half minutes of the assumed five converted
+        let line = "size=    2344kB time=00:02:30.00 bitrate= 128.0kbits/s speed=61.2x";
+        CliManager::parse_and_emit_progress(line, &callback, "talk.m4a");
+        CliManager::parse_and_emit_progress("Audio preprocessing completed: /tmp/talk.wav", &callback, "talk.m4a");
+        CliManager::parse_and_emit_progress("45%|████▌     | 450/1000 [00:10<00:12, 45.00frames/s]", &callback, "talk.m4a");
+
+        let received = received.lock().unwrap();
+        assert_eq!(received[0].stage, ProcessingStage::Preprocessing);
+        assert_eq!(received[0].progress, progress_in_band(50.0, PREPROCESSING_PROGRESS_BAND));
+        assert_eq!(received[1].progress, PREPROCESSING_PROGRESS_BAND.1);
+        // Transcription continues from the end of the preprocessing band
+        assert_eq!(received[2].stage, ProcessingStage::Transcribing);
+        assert_eq!(received[2].progress, progress_in_band(45.0, TRANSCRIBING_PROGRESS_BAND));
+        assert!(received[2].progress > received[1].progress);
+    }
+
+    #[test]
+    fn test_parse_finalization_progress() {
+        let (stage, progress, _) = CliManager::parse_finalization_progress("INFO - Postprocessing segments").unwrap();
+        assert_eq!(stage, ProcessingStage::Postprocessing);
+        assert_eq!(progress, SAVING_PROGRESS_BAND.0);
+        assert_eq!(CliManager::parse_finalization_progress("Post-processing transcript").unwrap().0, ProcessingStage::Postprocessing);
+
+        let (stage, progress, _) = CliManager::parse_finalization_progress("Saving transcription to /out/talk_transcription.txt").unwrap();
+        assert_eq!(stage, ProcessingStage::Saving);
+        assert_eq!(progress, progress_in_band(20.0, SAVING_PROGRESS_BAND));
+        assert_eq!(CliManager::parse_finalization_progress("INFO - Saving output to /out").unwrap().0, ProcessingStage::Saving);
+
+        let (stage, done_progress, _) = CliManager::parse_finalization_progress("Done.").unwrap();
+        assert_eq!(stage, ProcessingStage::Saving);
+        assert!(done_progress > progress);
+        assert_eq!(CliManager::parse_finalization_progress("2026-01-01 12:00:00 - INFO - Done").unwrap().1, done_progress);
+        assert_eq!(CliManager::parse_finalization_progress("✅ Transcription completed in 12.3s").unwrap().1, done_progress);
+
+        // Stays below the Finalizing update sent after the CLI exits
+        assert!(done_progress < progress_in_band(40.0, SAVING_PROGRESS_BAND));
+        assert!(progress > TRANSCRIBING_PROGRESS_BAND.1);
+
+        assert!(CliManager::parse_finalization_progress("Audio preprocessing completed: /tmp/talk.wav").is_none());
+        assert!(CliManager::parse_finalization_progress("Loading Whisper model base").is_none());
+        assert!(CliManager::parse_finalization_progress("Abandoned the saving plan").is_none());
+
+        // A segment whose text reads like one of these messages is still a segment
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let callback: ProgressCallback = Arc::new(move |progress: ProcessingProgress| {
+            received_clone.lock().unwrap().push(progress);
+        });
+        CliManager::parse_and_emit_progress("[00:01.000 --> 00:04.000] We're saving to buy a house. Done!", &callback, "talk.m4a");
+        CliManager::parse_and_emit_progress("Saving transcription to /out/talk_transcription.txt", &callback, "talk.m4a");
+        let received = received.lock().unwrap();
+        assert_eq!(received[0].stage, ProcessingStage::Transcribing);
+        assert_eq!(received[1].stage, ProcessingStage::Saving);
+    }
+
+    #[test]
+    fn test_progress_throttle_coalesces_bursts() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let callback: ProgressCallback = Arc::new(move |progress: ProcessingProgress| {
+            received_clone.lock().unwrap().push(progress);
+        });
+        let throttle = Arc::new(ProgressThrottle::new(callback, Duration::from_secs(60)));
+        let throttled = ProgressThrottle::callback(&throttle);
+
+        // A burst of tqdm redraws arriving far faster than the interval
+        let lines: Vec<String> = (0..200)
+            .map(|frame| format!("{}%|█▌        | {}/200 [00:01<00:08, 100.00frames/s]", frame / 2, frame))
+            .collect();
+        for line in &lines {
+            CliManager::parse_and_emit_progress(line, &throttled, "burst.m4a");
+        }
+
+        let emitted = received.lock().unwrap().len();
+        assert!(emitted >= 1);
+        assert!(emitted < lines.len() / 10, "{} events for {} lines", emitted, lines.len());
+
+        // Stage changes and completion always go through
+        throttled(ProcessingProgress {
+            stage: ProcessingStage::Saving,
+            progress: 100.0,
+            current_file: Some("burst.m4a".to_string()),
+            timestamp: Utc::now(),
+            message: None,
+            job_id: None,
+            file_index: None,
+            total_files: None,
+            can_cancel: false,
+            estimated_completion: None,
+        });
+        let received_now = received.lock().unwrap();
+        assert_eq!(received_now.len(), emitted + 1);
+        assert_eq!(received_now.last().unwrap().progress, 100.0);
+    }
+
+    #[test]
+    fn test_progress_throttle_flushes_latest() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let callback: ProgressCallback = Arc::new(move |progress: ProcessingProgress| {
+            received_clone.lock().unwrap().push(progress.progress);
+        });
+        let throttle = ProgressThrottle::new(callback, Duration::from_secs(60));
+
+        for percent in [10.0, 20.0, 30.0] {
+            throttle.emit(ProcessingProgress {
+                stage: ProcessingStage::Transcribing,
+                progress: percent,
+                current_file: None,
+                timestamp: Utc::now(),
+                message: None,
+                job_id: None,
+                file_index: None,
+                total_files: None,
+                can_cancel: false,
+                estimated_completion: None,
+            });
+        }
+        assert_eq!(*received.lock().unwrap(), vec![10.0]);
+
+        throttle.flush();
+        assert_eq!(*received.lock().unwrap(), vec![10.0, 30.0]);
+        throttle.flush();
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_monitor_progress() {
         use tokio::sync::mpsc;
@@ -16,6 +16,21 @@ pub enum AppError {
     
     #[error("CLI execution failed: {0}")]
     CliError(String),
+
+    #[error("Whisper model not found: {0}")]
+    ModelNotFound(String),
+
+    #[error("FFmpeg is not installed: {0}")]
+    FfmpegMissing(String),
+
+    #[error("Audio could not be decoded: {0}")]
+    AudioDecodeError(String),
+
+    #[error("File is too large: {0}")]
+    FileTooLarge(String),
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
     
     #[error("Configuration error: {0}")]
     ConfigError(String),
@@ -0,0 +1,71 @@
+/// Progress of a single file (`ProcessingProgress`)
+pub const FILE_PROGRESS: &str = "file-progress";
+/// A file finished transcribing (`TranscriptionResult`)
+pub const FILE_COMPLETED: &str = "file-completed";
+/// A file failed to transcribe (`file_path`, `error`, and `log_path` for batch jobs)
+pub const FILE_ERROR: &str = "file-error";
+/// A file ran past `per_file_timeout_secs` and was marked failed (`file_path`, `timeout_secs`)
+pub const FILE_TIMEOUT: &str = "file-timeout";
+/// A file was skipped, because its output already exists or by the user (`file_path`, `output_path`)
+pub const FILE_SKIPPED: &str = "file-skipped";
+/// A result's confidence is below the configured threshold
+pub const LOW_CONFIDENCE: &str = "low-confidence";
+/// A file was transcribed but Whisper heard no speech in it (`file_path`, `output_path`)
+pub const NO_SPEECH: &str = "no-speech";
+/// Segment text as it is transcribed (`PartialTranscription`)
+pub const TRANSCRIPTION_PARTIAL: &str = "transcription-partial";
+/// Overall progress of a batch job (`ProcessingProgress`)
+pub const BATCH_PROGRESS: &str = "batch-progress";
+/// A batch job finished
+pub const BATCH_COMPLETED: &str = "batch-completed";
+/// A batch job was cancelled (the job id)
+pub const BATCH_CANCELLED: &str = "batch-cancelled";
+/// A line of CLI output from a job with log streaming enabled (`CliLogLine`)
+pub const CLI_LOG: &str = "cli-log";
+/// A newer version of the app is available
+pub const UPDATE_AVAILABLE: &str = "update-available";
+/// A setting running jobs depend on changed; they keep the old value until restarted (`field`)
+pub const SETTINGS_RESTART_REQUIRED: &str = "settings-restart-required";
+
+/// Every event name, for the frontend to discover
+pub const ALL: &[&str] = &[
+    FILE_PROGRESS,
+    FILE_COMPLETED,
+    FILE_ERROR,
+    FILE_TIMEOUT,
+    FILE_SKIPPED,
+    LOW_CONFIDENCE,
+    NO_SPEECH,
+    TRANSCRIPTION_PARTIAL,
+    BATCH_PROGRESS,
+    BATCH_COMPLETED,
+    BATCH_CANCELLED,
+    CLI_LOG,
+    UPDATE_AVAILABLE,
+    SETTINGS_RESTART_REQUIRED,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_emitted_by_constant() {
+        let sources = [
+            ("lib.rs", include_str!("lib.rs")),
+            ("cli.rs", include_str!("cli.rs")),
+            ("updater.rs", include_str!("updater.rs")),
+            ("system.rs", include_str!("system.rs")),
+            ("macos_integration.rs", include_str!("macos_integration.rs")),
+        ];
+        let literal_emit = regex::Regex::new(r#"\.emit\(\s*""#).unwrap();
+        for (file, source) in sources {
+            assert!(!literal_emit.is_match(source), "{} emits an event by string literal", file);
+        }
+
+        let mut names = ALL.to_vec();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), ALL.len());
+    }
+}
@@ -0,0 +1,350 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{AppSettings, OutputFormat, Segment, TranscriptionMetadata, TranscriptionResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// File formats for batch result manifests
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+/// One manifest entry per transcribed file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchResultRecord {
+    pub file: String,
+    pub output_path: String,
+    pub duration: Option<f64>,
+    pub processing_time: f64,
+    pub confidence: Option<f64>,
+    pub language: String,
+}
+
+impl From<&TranscriptionResult> for BatchResultRecord {
+    fn from(result: &TranscriptionResult) -> Self {
+        // Prefer the probed duration; fall back to whatever the file list knew
+        let duration = Some(result.metadata.audio_info.duration)
+            .filter(|duration| *duration > 0.0)
+            .or(result.original_file.duration);
+
+        Self {
+            file: result.original_file.path.clone(),
+            output_path: result.output_path.clone(),
+            duration,
+            processing_time: result.processing_time,
+            confidence: result.confidence,
+            language: result.metadata.language.clone(),
+        }
+    }
+}
+
+/// Write a manifest of batch results to `path`
+pub fn export_results(results: &[TranscriptionResult], path: &str, format: ExportFormat) -> AppResult<()> {
+    let records: Vec<BatchResultRecord> = results.iter().map(BatchResultRecord::from).collect();
+
+    let content = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&records)?,
+        ExportFormat::Csv => records_to_csv(&records),
+    };
+
+    std::fs::write(path, content)?;
+    println!("📄 Exported {} results to {}", records.len(), path);
+    Ok(())
+}
+
+/// `{job_id}_combined.txt` in `output_dir`
+pub fn combined_output_path(job_id: &str, output_dir: &str) -> PathBuf {
+    Path::new(output_dir).join(format!("{}_combined.txt", job_id))
+}
+
+/// Write the results' texts, in order and each under a header naming its file, into one
+/// document in `output_dir`, returning its path
+pub fn write_combined_transcript(job_id: &str, results: &[TranscriptionResult], output_dir: &str) -> AppResult<String> {
+    if results.is_empty() {
+        return Err(AppError::ProcessingError("No transcriptions to combine".to_string()));
+    }
+
+    let sections: Vec<String> = results
+        .iter()
+        .map(|result| format!("=== {} ===\n\n{}", result.original_file.name, result.transcribed_text.trim()))
+        .collect();
+
+    let path = combined_output_path(job_id, output_dir);
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, sections.join("\n\n") + "\n")
+        .map_err(|e| AppError::IoError(format!("Failed to write {}: {}", path.display(), e)))?;
+
+    println!("📎 Combined {} transcriptions into {}", results.len(), path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Render records as CSV with a header row
+pub fn records_to_csv(records: &[BatchResultRecord]) -> String {
+    let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    let mut csv = String::from("file,output_path,duration,processing_time,confidence,language\n");
+    for record in records {
+        let row = [
+            csv_field(&record.file),
+            csv_field(&record.output_path),
+            optional(record.duration),
+            record.processing_time.to_string(),
+            optional(record.confidence),
+            csv_field(&record.language),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Write `result` to `output_path` as SRT or VTT subtitles built from its segments
+pub fn convert_transcription(result: &TranscriptionResult, target: OutputFormat, output_path: &str) -> AppResult<()> {
+    let segments = result.segments.as_deref().filter(|segments| !segments.is_empty()).ok_or_else(|| {
+        AppError::ProcessingError(format!(
+            "Converting to {} needs segment timestamps, but '{}' only has plain text. Transcribe it again to get segments",
+            target.extension().to_uppercase(),
+            result.original_file.name
+        ))
+    })?;
+
+    let content = render_subtitles(segments, target)?;
+    std::fs::write(output_path, content)?;
+    println!("🎬 Wrote {} cues to {}", segments.len(), output_path);
+    Ok(())
+}
+
+/// Everything needed to tell how a transcription was made, stored next to its output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataSidecar {
+    pub source_file: String,
+    pub metadata: TranscriptionMetadata,
+    pub processing_time: f64,
+    pub settings: AppSettings,
+}
+
+/// `{name}.meta.json` next to the output `{name}.{ext}`
+pub fn metadata_sidecar_path(output_path: &str) -> PathBuf {
+    let output_path = Path::new(output_path);
+    if output_path.to_string_lossy().ends_with(".meta.json") {
+        return output_path.to_path_buf();
+    }
+    let stem = output_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("output");
+    output_path.with_file_name(format!("{}.meta.json", stem))
+}
+
+/// Write the metadata sidecar for `result`, returning its path
+pub fn write_metadata_sidecar(result: &TranscriptionResult, settings: &AppSettings) -> AppResult<String> {
+    let sidecar = MetadataSidecar {
+        source_file: result.original_file.path.clone(),
+        metadata: result.metadata.clone(),
+        processing_time: result.processing_time,
+        settings: settings.clone(),
+    };
+    let path = metadata_sidecar_path(&result.output_path);
+    std::fs::write(&path, serde_json::to_string_pretty(&sidecar)?)
+        .map_err(|e| AppError::IoError(format!("Failed to write {}: {}", path.display(), e)))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Load the metadata sidecar for an output file, or the sidecar file itself
+pub fn read_metadata_sidecar(path: &str) -> AppResult<MetadataSidecar> {
+    let sidecar_path = metadata_sidecar_path(path.trim());
+    if !sidecar_path.is_file() {
+        return Err(AppError::FileNotFound(sidecar_path.to_string_lossy().to_string()));
+    }
+    let content = std::fs::read_to_string(&sidecar_path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Render segments as SRT or VTT cues
+pub fn render_subtitles(segments: &[Segment], format: OutputFormat) -> AppResult<String> {
+    let (mut output, separator) = match format {
+        OutputFormat::Srt => (String::new(), ','),
+        OutputFormat::Vtt => (String::from("WEBVTT\n\n"), '.'),
+        other => {
+            return Err(AppError::UnsupportedFormat(format!(
+                "Subtitles can only be written as SRT or VTT, not {}",
+                other.extension()
+            )));
+        }
+    };
+
+    for (index, segment) in segments.iter().enumerate() {
+        if matches!(format, OutputFormat::Srt) {
+            output.push_str(&format!("{}\n", index + 1));
+        }
+        output.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timecode(segment.start, separator),
+            format_timecode(segment.end, separator),
+            segment.text.trim()
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Format seconds as HH:MM:SS followed by `separator` and milliseconds
+/// (',' for SRT, '.' for VTT)
+pub fn format_timecode(seconds: f64, separator: char) -> String {
+    let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let hours = total_millis / 3_600_000;
+    let minutes = total_millis / 60_000 % 60;
+    let secs = total_millis / 1000 % 60;
+    let millis = total_millis % 1000;
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, minutes, secs, separator, millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn sample_result(path: &str, duration: f64, confidence: Option<f64>) -> TranscriptionResult {
+        let mut result = crate::test_support::sample_result(path);
+        result.metadata.audio_info.duration = duration;
+        result.output_path = format!("{}.txt", path);
+        result.processing_time = 2.5;
+        result.confidence = confidence;
+        result
+    }
+
+    #[test]
+    fn test_export_json_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("manifest.json");
+        let results = vec![
+            sample_result("/audio/one.m4a", 60.0, Some(0.9)),
+            sample_result("/audio/two.m4a", 0.0, None),
+        ];
+
+        export_results(&results, path.to_str().unwrap(), ExportFormat::Json).unwrap();
+
+        let records: Vec<BatchResultRecord> = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].file, "/audio/one.m4a");
+        assert_eq!(records[0].duration, Some(60.0));
+        assert_eq!(records[0].confidence, Some(0.9));
+        assert_eq!(records[1].duration, None);
+        assert_eq!(records[1].language, "ko");
+    }
+
+    #[test]
+    fn test_write_combined_transcript() {
+        let temp_dir = tempdir().unwrap();
+        let output_dir = temp_dir.path().join("out");
+        let mut first = sample_result("/audio/part1.m4a", 60.0, None);
+        first.transcribed_text = "회의를 시작하겠습니다.\n".to_string();
+        let mut second = sample_result("/audio/part2.m4a", 60.0, None);
+        second.transcribed_text = "That concludes the meeting.".to_string();
+
+        let path = write_combined_transcript("job-1", &[first, second], output_dir.to_str().unwrap()).unwrap();
+        assert_eq!(path, output_dir.join("job-1_combined.txt").to_string_lossy());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "=== part1.m4a ===\n\n회의를 시작하겠습니다.\n\n=== part2.m4a ===\n\nThat concludes the meeting.\n"
+        );
+
+        assert!(write_combined_transcript("job-2", &[], output_dir.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_format_timecode() {
+        assert_eq!(format_timecode(0.0, ','), "00:00:00,000");
+        assert_eq!(format_timecode(61.5, ','), "00:01:01,500");
+        assert_eq!(format_timecode(3723.004, '.'), "01:02:03.004");
+    }
+
+    #[test]
+    fn test_convert_transcription_to_subtitles() {
+        let temp_dir = tempdir().unwrap();
+        let mut result = sample_result("/audio/talk.m4a", 10.0, None);
+        result.segments = Some(vec![
+            Segment { start: 0.0, end: 2.5, text: " 안녕하세요".to_string(), speaker: None },
+            Segment { start: 2.5, end: 65.25, text: "Welcome back".to_string(), speaker: None },
+        ]);
+
+        let srt_path = temp_dir.path().join("talk.srt");
+        convert_transcription(&result, OutputFormat::Srt, srt_path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&srt_path).unwrap(),
+            "1\n00:00:00,000 --> 00:00:02,500\n안녕하세요\n\n2\n00:00:02,500 --> 00:01:05,250\nWelcome back\n\n"
+        );
+
+        let vtt_path = temp_dir.path().join("talk.vtt");
+        convert_transcription(&result, OutputFormat::Vtt, vtt_path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&vtt_path).unwrap(),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:02.500\n안녕하세요\n\n00:00:02.500 --> 00:01:05.250\nWelcome back\n\n"
+        );
+
+        assert!(matches!(
+            convert_transcription(&result, OutputFormat::Json, vtt_path.to_str().unwrap()),
+            Err(AppError::UnsupportedFormat(_))
+        ));
+
+        // Plain text alone can't be timed
+        result.segments = None;
+        let error = convert_transcription(&result, OutputFormat::Srt, srt_path.to_str().unwrap()).unwrap_err();
+        assert!(error.to_string().contains("segment timestamps"));
+    }
+
+    #[test]
+    fn test_export_csv_quotes_fields() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("manifest.csv");
+        let results = vec![sample_result("/audio/interview, part \"1\".m4a", 30.0, None)];
+
+        export_results(&results, path.to_str().unwrap(), ExportFormat::Csv).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[0], "file,output_path,duration,processing_time,confidence,language");
+        assert_eq!(
+            lines[1],
+            "\"/audio/interview, part \"\"1\"\".m4a\",\"/audio/interview, part \"\"1\"\".m4a.txt\",30,2.5,,ko"
+        );
+    }
+
+    #[test]
+    fn test_metadata_sidecar_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let mut result = sample_result("/audio/meeting.m4a", 61.5, Some(0.8));
+        result.output_path = temp_dir.path().join("meeting_transcription.txt").to_string_lossy().to_string();
+        result.metadata.language = "en".to_string();
+        let settings = AppSettings { beam_size: 7, initial_prompt: Some("Budget".to_string()), ..AppSettings::default() };
+
+        let path = write_metadata_sidecar(&result, &settings).unwrap();
+        assert_eq!(path, temp_dir.path().join("meeting_transcription.meta.json").to_string_lossy());
+
+        // Readable from either the output path or the sidecar path
+        let sidecar = read_metadata_sidecar(&result.output_path).unwrap();
+        assert_eq!(read_metadata_sidecar(&path).unwrap().source_file, sidecar.source_file);
+
+        assert_eq!(sidecar.source_file, "/audio/meeting.m4a");
+        assert_eq!(sidecar.metadata.language, "en");
+        assert_eq!(sidecar.metadata.model_size, result.metadata.model_size);
+        assert_eq!(sidecar.metadata.timestamp, result.metadata.timestamp);
+        assert_eq!(sidecar.metadata.audio_info.duration, 61.5);
+        assert_eq!(sidecar.processing_time, result.processing_time);
+        assert_eq!(sidecar.settings.beam_size, 7);
+        assert_eq!(sidecar.settings.initial_prompt.as_deref(), Some("Budget"));
+
+        assert!(matches!(read_metadata_sidecar("/missing/output.txt"), Err(AppError::FileNotFound(_))));
+    }
+}
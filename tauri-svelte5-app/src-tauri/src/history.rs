@@ -0,0 +1,428 @@
+use crate::error::{AppError, AppResult};
+use crate::models::TranscriptionResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Oldest entries are dropped once the history grows past this, unless configured otherwise
+pub const MAX_HISTORY_ENTRIES: usize = 1000;
+
+/// Page size used when `get_history` is called without a limit
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// Serializes read-modify-write cycles so concurrent completions don't drop entries
+static HISTORY_LOCK: once_cell::sync::Lazy<tokio::sync::Mutex<()>> =
+    once_cell::sync::Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// Wait for any history write in progress to finish
+pub async fn flush_pending_writes() {
+    let _guard = HISTORY_LOCK.lock().await;
+}
+
+/// A completed transcription recorded in the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub recorded_at: DateTime<Utc>,
+    /// The result without its segments, which would bloat the history file
+    pub result: TranscriptionResult,
+}
+
+/// Persists completed transcriptions as JSON in the config directory
+pub struct HistoryManager {
+    history_path: PathBuf,
+    // None keeps every entry
+    max_entries: Option<usize>,
+}
+
+impl HistoryManager {
+    /// Create a history manager with the default history path
+    pub fn new() -> AppResult<Self> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| AppError::ConfigError("Could not determine config directory".to_string()))?
+            .join("speech-to-text-gui");
+
+        Ok(Self::with_history_path(config_dir.join("history.json")))
+    }
+
+    /// Create a history manager with a custom path (useful for testing)
+    pub fn with_history_path(history_path: PathBuf) -> Self {
+        Self { history_path, max_entries: Some(MAX_HISTORY_ENTRIES) }
+    }
+
+    /// Keep at most `max_entries` entries, dropping the oldest on insert; 0 keeps every entry
+    pub fn with_max_entries(mut self, max_entries: u32) -> Self {
+        self.max_entries = (max_entries > 0).then_some(max_entries as usize);
+        self
+    }
+
+    /// Record a completed transcription
+    pub async fn add_entry(&self, result: &TranscriptionResult) -> AppResult<HistoryEntry> {
+        let _guard = HISTORY_LOCK.lock().await;
+        let mut entries = self.load_entries_or_reset().await?;
+
+        let mut result = result.clone();
+        result.segments = None;
+        let entry = HistoryEntry {
+            id: crate::utils::generate_id(),
+            recorded_at: Utc::now(),
+            result,
+        };
+        entries.push(entry.clone());
+
+        if let Some(max_entries) = self.max_entries.filter(|max_entries| entries.len() > *max_entries) {
+            let excess = entries.len() - max_entries;
+            entries.drain(..excess);
+        }
+
+        self.save_entries(&entries).await?;
+        Ok(entry)
+    }
+
+    /// Entries newest first, skipping `offset` and returning at most `limit`
+    pub async fn get_history(&self, limit: usize, offset: usize) -> AppResult<Vec<HistoryEntry>> {
+        let entries = self.load_entries().await?;
+        Ok(entries.into_iter().rev().skip(offset).take(limit).collect())
+    }
+
+    /// Results whose text or file name contains `query` (ignoring case), newest first.
+    /// A linear scan is fast enough for a history capped at `MAX_HISTORY_ENTRIES` by default.
+    pub async fn search(&self, query: &str) -> AppResult<Vec<TranscriptionResult>> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut matches: Vec<HistoryEntry> = self.load_entries().await?
+            .into_iter()
+            .rev()
+            .filter(|entry| {
+                entry.result.transcribed_text.to_lowercase().contains(&query)
+                    || entry.result.original_file.name.to_lowercase().contains(&query)
+            })
+            .collect();
+        // Stable, so entries recorded at the same instant stay newest first
+        matches.sort_by_key(|entry| std::cmp::Reverse(entry.recorded_at));
+
+        Ok(matches.into_iter().map(|entry| entry.result).collect())
+    }
+
+    /// The `paths` that have no finished transcription yet, in their original order. A file
+    /// counts as finished when the history has a result for it whose output still exists.
+    pub async fn unfinished_files(&self, paths: &[String]) -> AppResult<Vec<String>> {
+        let finished: std::collections::HashSet<String> = self.load_entries().await?
+            .into_iter()
+            .filter(|entry| std::path::Path::new(&entry.result.output_path).is_file())
+            .map(|entry| entry.result.original_file.path)
+            .collect();
+
+        Ok(paths
+            .iter()
+            .filter(|path| {
+                let path = path.trim();
+                !finished.contains(path) && !finished.contains(&crate::utils::resolve_file_path(path))
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Mean realtime factor of the newest `limit` entries that have one, or None if none do.
+    /// Entries recorded before the factor was stored derive it from their duration and time.
+    pub async fn average_realtime_factor(&self, limit: usize) -> AppResult<Option<f64>> {
+        let factors: Vec<f64> = self.load_entries().await?
+            .into_iter()
+            .rev()
+            .take(limit)
+            .filter_map(|entry| {
+                entry.result.realtime_factor.or_else(|| {
+                    crate::utils::realtime_factor(entry.result.metadata.audio_info.duration, entry.result.processing_time)
+                })
+            })
+            .collect();
+
+        if factors.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(factors.iter().sum::<f64>() / factors.len() as f64))
+    }
+
+    /// Remove one entry; returns whether it existed
+    pub async fn delete_entry(&self, id: &str) -> AppResult<bool> {
+        let _guard = HISTORY_LOCK.lock().await;
+        let mut entries = self.load_entries().await?;
+
+        let before = entries.len();
+        entries.retain(|entry| entry.id != id);
+        if entries.len() == before {
+            return Ok(false);
+        }
+
+        self.save_entries(&entries).await?;
+        Ok(true)
+    }
+
+    /// Remove every entry
+    pub async fn clear_history(&self) -> AppResult<()> {
+        let _guard = HISTORY_LOCK.lock().await;
+        self.save_entries(&[]).await
+    }
+
+    /// Where a corrupt history file found at `now` is kept, e.g. `history.json.20260105-120000.bak`
+    pub fn get_backup_path(&self, now: DateTime<Utc>) -> PathBuf {
+        let mut path = self.history_path.clone().into_os_string();
+        path.push(format!(".{}.bak", now.format("%Y%m%d-%H%M%S%.3f")));
+        PathBuf::from(path)
+    }
+
+    async fn read_history_file(&self) -> AppResult<Option<String>> {
+        if !self.history_path.exists() {
+            return Ok(None);
+        }
+
+        fs::read_to_string(&self.history_path).await
+            .map(Some)
+            .map_err(|e| AppError::ConfigError(format!("Failed to read history file: {}", e)))
+    }
+
+    async fn load_entries(&self) -> AppResult<Vec<HistoryEntry>> {
+        let Some(content) = self.read_history_file().await? else {
+            return Ok(Vec::new());
+        };
+
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::ConfigError(format!("Failed to parse history file: {}", e)))
+    }
+
+    /// Like `load_entries`, but a corrupt history file is backed up next to it and started
+    /// over, so new results are still recorded. Earlier backups are never overwritten.
+    async fn load_entries_or_reset(&self) -> AppResult<Vec<HistoryEntry>> {
+        let Some(content) = self.read_history_file().await? else {
+            return Ok(Vec::new());
+        };
+
+        match serde_json::from_str(&content) {
+            Ok(entries) => Ok(entries),
+            Err(e) => {
+                let backup_path = self.write_backup(&content).await
+                    .map_err(|e| AppError::ConfigError(format!("Failed to back up history file: {}", e)))?;
+                println!("🔧 Reset unreadable history file ({}); the original was saved to {}", e, backup_path.display());
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Save `content` under a backup name that isn't taken yet, returning its path
+    async fn write_backup(&self, content: &str) -> std::io::Result<PathBuf> {
+        let backup_path = self.get_backup_path(Utc::now());
+        let mut attempt = 0;
+        loop {
+            let path = match attempt {
+                0 => backup_path.clone(),
+                _ => backup_path.with_extension(format!("{}.bak", attempt)),
+            };
+            match fs::OpenOptions::new().write(true).create_new(true).open(&path).await {
+                Ok(mut backup) => {
+                    backup.write_all(content.as_bytes()).await?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn save_entries(&self, entries: &[HistoryEntry]) -> AppResult<()> {
+        if let Some(parent) = self.history_path.parent() {
+            fs::create_dir_all(parent).await
+                .map_err(|e| AppError::ConfigError(format!("Failed to create config directory: {}", e)))?;
+        }
+
+        let content = serde_json::to_string(entries)
+            .map_err(|e| AppError::ConfigError(format!("Failed to serialize history: {}", e)))?;
+
+        fs::write(&self.history_path, content).await
+            .map_err(|e| AppError::ConfigError(format!("Failed to write history file: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Segment;
+    use tempfile::TempDir;
+
+    fn create_test_history_manager() -> (HistoryManager, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = HistoryManager::with_history_path(temp_dir.path().join("history.json"));
+        (manager, temp_dir)
+    }
+
+    fn sample_result(name: &str, text: &str) -> TranscriptionResult {
+        TranscriptionResult {
+            transcribed_text: text.to_string(),
+            segments: Some(vec![Segment { start: 0.0, end: 1.0, text: text.to_string(), speaker: None }]),
+            ..crate::test_support::sample_result(&format!("/audio/{}", name))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_and_query_history() {
+        let (manager, _temp_dir) = create_test_history_manager();
+        assert!(manager.get_history(10, 0).await.unwrap().is_empty());
+
+        for name in ["one.m4a", "two.m4a", "three.m4a"] {
+            manager.add_entry(&sample_result(name, name)).await.unwrap();
+        }
+
+        // Newest first, paged by limit and offset
+        let names = |entries: Vec<HistoryEntry>| -> Vec<String> {
+            entries.into_iter().map(|entry| entry.result.original_file.name).collect()
+        };
+        assert_eq!(names(manager.get_history(10, 0).await.unwrap()), vec!["three.m4a", "two.m4a", "one.m4a"]);
+        assert_eq!(names(manager.get_history(1, 1).await.unwrap()), vec!["two.m4a"]);
+        assert!(manager.get_history(10, 3).await.unwrap().is_empty());
+
+        let entry = &manager.get_history(1, 0).await.unwrap()[0];
+        assert_eq!(entry.result.transcribed_text, "three.m4a");
+        assert!(entry.result.segments.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_history_prunes_oldest_beyond_cap() {
+        let (manager, _temp_dir) = create_test_history_manager();
+        let manager = manager.with_max_entries(3);
+        for index in 0..5 {
+            let name = format!("{}.m4a", index);
+            manager.add_entry(&sample_result(&name, &name)).await.unwrap();
+        }
+
+        let names: Vec<String> = manager.get_history(10, 0).await.unwrap()
+            .into_iter()
+            .map(|entry| entry.result.original_file.name)
+            .collect();
+        assert_eq!(names, vec!["4.m4a", "3.m4a", "2.m4a"]);
+
+        // 0 lifts the cap
+        let manager = manager.with_max_entries(0);
+        for index in 5..8 {
+            let name = format!("{}.m4a", index);
+            manager.add_entry(&sample_result(&name, &name)).await.unwrap();
+        }
+        assert_eq!(manager.get_history(10, 0).await.unwrap().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_search_history() {
+        let (manager, _temp_dir) = create_test_history_manager();
+        manager.add_entry(&sample_result("standup.m4a", "Budget review for Q3")).await.unwrap();
+        manager.add_entry(&sample_result("interview.m4a", "회의 내용을 정리합니다")).await.unwrap();
+        manager.add_entry(&sample_result("budget-call.m4a", "Vendor pricing")).await.unwrap();
+        manager.add_entry(&sample_result("lecture.m4a", "Photosynthesis basics")).await.unwrap();
+
+        // Text and file name both match, newest first
+        let names: Vec<String> = manager.search("BUDGET").await.unwrap()
+            .into_iter()
+            .map(|result| result.original_file.name)
+            .collect();
+        assert_eq!(names, vec!["budget-call.m4a", "standup.m4a"]);
+
+        let korean = manager.search("회의").await.unwrap();
+        assert_eq!(korean.len(), 1);
+        assert_eq!(korean[0].original_file.name, "interview.m4a");
+
+        assert!(manager.search("podcast").await.unwrap().is_empty());
+        assert!(manager.search("  ").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_entry_resets_corrupt_history() {
+        let (manager, temp_dir) = create_test_history_manager();
+        let history_path = temp_dir.path().join("history.json");
+        let backups = || -> Vec<String> {
+            let mut backups: Vec<String> = std::fs::read_dir(temp_dir.path())
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "bak"))
+                .map(|path| std::fs::read_to_string(path).unwrap())
+                .collect();
+            backups.sort();
+            backups
+        };
+
+        std::fs::write(&history_path, "[{\"id\": \"first").unwrap();
+        assert!(manager.get_history(10, 0).await.is_err());
+        manager.add_entry(&sample_result("after.m4a", "after")).await.unwrap();
+
+        // The corrupt original is kept and the history starts over with the new entry
+        assert_eq!(backups(), vec!["[{\"id\": \"first"]);
+        let entries = manager.get_history(10, 0).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].result.original_file.name, "after.m4a");
+
+        // A second corruption keeps the first backup
+        std::fs::write(&history_path, "[{\"id\": \"second").unwrap();
+        manager.add_entry(&sample_result("again.m4a", "again")).await.unwrap();
+        assert_eq!(backups(), vec!["[{\"id\": \"first", "[{\"id\": \"second"]);
+    }
+
+    #[tokio::test]
+    async fn test_average_realtime_factor() {
+        let (manager, _temp_dir) = create_test_history_manager();
+        assert_eq!(manager.average_realtime_factor(10).await.unwrap(), None);
+
+        // 30s of audio in 3s, derived for entries without a stored factor
+        manager.add_entry(&sample_result("old.m4a", "old")).await.unwrap();
+        let mut faster = sample_result("new.m4a", "new");
+        faster.realtime_factor = Some(20.0);
+        manager.add_entry(&faster).await.unwrap();
+        let mut unknown = sample_result("silent.m4a", "");
+        unknown.processing_time = 0.0;
+        manager.add_entry(&unknown).await.unwrap();
+
+        assert_eq!(manager.average_realtime_factor(10).await.unwrap(), Some(15.0));
+        assert_eq!(manager.average_realtime_factor(2).await.unwrap(), Some(20.0));
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_clear_history() {
+        let (manager, _temp_dir) = create_test_history_manager();
+        let first = manager.add_entry(&sample_result("one.m4a", "first")).await.unwrap();
+        let second = manager.add_entry(&sample_result("two.m4a", "second")).await.unwrap();
+
+        assert!(manager.delete_entry(&first.id).await.unwrap());
+        assert!(!manager.delete_entry(&first.id).await.unwrap());
+        let remaining = manager.get_history(10, 0).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, second.id);
+
+        manager.clear_history().await.unwrap();
+        assert!(manager.get_history(10, 0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unfinished_files_skips_completed() {
+        let (manager, temp_dir) = create_test_history_manager();
+        let output = |name: &str| {
+            let path = temp_dir.path().join(format!("{}_transcription.txt", name));
+            std::fs::write(&path, name).unwrap();
+            path.to_string_lossy().to_string()
+        };
+
+        let mut one = sample_result("one.m4a", "first");
+        one.output_path = output("one");
+        manager.add_entry(&one).await.unwrap();
+        let mut three = sample_result("three.m4a", "third");
+        three.output_path = output("three");
+        manager.add_entry(&three).await.unwrap();
+        // Transcribed once, but the output has since been deleted
+        manager.add_entry(&sample_result("four.m4a", "fourth")).await.unwrap();
+
+        let paths: Vec<String> = ["one.m4a", "two.m4a", "three.m4a", "four.m4a"]
+            .iter()
+            .map(|name| format!("/audio/{}", name))
+            .collect();
+        assert_eq!(manager.unfinished_files(&paths).await.unwrap(), vec!["/audio/two.m4a", "/audio/four.m4a"]);
+    }
+}
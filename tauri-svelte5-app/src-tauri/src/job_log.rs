@@ -0,0 +1,153 @@
+use crate::cli::LogLineCallback;
+use crate::error::{AppError, AppResult};
+use crate::models::{CliLogLine, CliLogStream};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// The full CLI output of one job, written to `{job_id}.log` so it can be attached to bug reports
+pub struct JobLog {
+    path: PathBuf,
+    file: Mutex<std::fs::File>,
+}
+
+impl JobLog {
+    /// Default log location under the user's local data directory
+    pub fn default_directory() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("speech-to-text-gui")
+            .join("logs")
+    }
+
+    /// `{job_id}.log` in `directory`
+    pub fn log_path(directory: &Path, job_id: &str) -> PathBuf {
+        directory.join(format!("{}.log", crate::utils::sanitize_filename(job_id)))
+    }
+
+    /// Create (or append to) the log of `job_id` in `directory`
+    pub fn create(directory: &Path, job_id: &str) -> AppResult<Self> {
+        std::fs::create_dir_all(directory)?;
+        let path = Self::log_path(directory, job_id);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| AppError::IoError(format!("Failed to open job log {}: {}", path.display(), e)))?;
+        Ok(Self { path, file: Mutex::new(file) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one line of CLI output, tagged with its time, stream and file
+    pub fn append(&self, log_line: &CliLogLine) {
+        let stream = match log_line.stream {
+            CliLogStream::Stdout => "stdout",
+            CliLogStream::Stderr => "stderr",
+        };
+        if let Ok(mut file) = self.file.lock() {
+            // A log that can't be written shouldn't fail the transcription
+            let _ = writeln!(
+                file,
+                "{} [{}] {}: {}",
+                log_line.timestamp.to_rfc3339(),
+                stream,
+                log_line.file_path,
+                log_line.line
+            );
+        }
+    }
+}
+
+/// Log callback that appends each line to `job_log` before passing it on to `inner`
+pub fn job_log_callback(job_log: Arc<JobLog>, inner: LogLineCallback) -> LogLineCallback {
+    Arc::new(move |log_line| {
+        job_log.append(&log_line);
+        inner(log_line);
+    })
+}
+
+/// Delete job logs in `directory` last written more than `retention_days` before `now`
+/// (0 keeps every log), returning how many were removed
+pub fn prune_job_logs(directory: &Path, retention_days: u32, now: SystemTime) -> AppResult<usize> {
+    if retention_days == 0 || !directory.is_dir() {
+        return Ok(0);
+    }
+    let Some(cutoff) = now.checked_sub(Duration::from_secs(retention_days as u64 * 24 * 60 * 60)) else {
+        return Ok(0);
+    };
+
+    let mut removed = 0;
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("log") {
+            continue;
+        }
+        let expired = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified < cutoff);
+        if expired && std::fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+
+    if removed > 0 {
+        println!("🧹 Removed {} job logs older than {} days", removed, retention_days);
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::CliManager;
+    use crate::models::AppSettings;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failed_job_writes_cli_output_to_log() {
+        let temp_dir = tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), "echo 'Loading model base'\necho 'RuntimeError: model weights are corrupted' >&2\nexit 1");
+
+        let audio = temp_dir.path().join("meeting.m4a");
+        std::fs::write(&audio, b"audio").unwrap();
+        let output_dir = temp_dir.path().join("out");
+        std::fs::create_dir(&output_dir).unwrap();
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+
+        let log_dir = temp_dir.path().join("logs");
+        let job_log = Arc::new(JobLog::create(&log_dir, "job-1").unwrap());
+        let manager = CliManager::new_dev()
+            .with_cli_binary_path(binary.to_string_lossy())
+            .with_log_line_callback(job_log_callback(job_log.clone(), Arc::new(|_| {})));
+
+        assert!(manager.process_file(&audio.to_string_lossy(), &settings, None).await.is_err());
+
+        assert_eq!(job_log.path(), log_dir.join("job-1.log"));
+        let log = std::fs::read_to_string(job_log.path()).unwrap();
+        assert!(log.contains("[stdout]") && log.contains("Loading model base"), "{}", log);
+        assert!(log.contains("[stderr]") && log.contains("model weights are corrupted"), "{}", log);
+    }
+
+    #[test]
+    fn test_prune_job_logs_by_age() {
+        let temp_dir = tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("job-1.log"), "old").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "kept").unwrap();
+        let now = SystemTime::now();
+
+        assert_eq!(prune_job_logs(temp_dir.path(), 0, now + Duration::from_secs(30 * 86400)).unwrap(), 0);
+        assert_eq!(prune_job_logs(temp_dir.path(), 7, now).unwrap(), 0);
+        assert_eq!(prune_job_logs(temp_dir.path(), 7, now + Duration::from_secs(8 * 86400)).unwrap(), 1);
+        assert!(!temp_dir.path().join("job-1.log").exists());
+        assert!(temp_dir.path().join("notes.txt").exists());
+    }
+}
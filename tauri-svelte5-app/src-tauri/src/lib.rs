@@ -5,17 +5,90 @@ pub mod cli;
 pub mod settings;
 pub mod system;
 pub mod macos_integration;
+pub mod progress_indicator;
+pub mod events;
+pub mod export;
+pub mod transcription_cache;
+pub mod history;
+pub mod job_log;
 pub mod updater;
+#[cfg(test)]
+mod test_support;
 
 /// Global batch processing manager
 static BATCH_MANAGER: once_cell::sync::Lazy<Arc<Mutex<BatchProcessingManager>>> = 
     once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(BatchProcessingManager::new())));
 
+/// Global cap on concurrently running CLI processes across all jobs
+static CLI_CONCURRENCY_LIMITER: once_cell::sync::Lazy<Arc<ConcurrencyLimiter>> =
+    once_cell::sync::Lazy::new(|| Arc::new(ConcurrencyLimiter::new(AppSettings::default().resolved_concurrent_jobs())));
+
+/// Jobs whose CLI output is forwarded as `cli-log` events
+static LOG_STREAMS: once_cell::sync::Lazy<std::sync::Mutex<HashSet<String>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(HashSet::new()));
+
 /// Batch processing manager for handling concurrent jobs
 pub struct BatchProcessingManager {
     active_jobs: HashMap<String, ProcessingJob>,
     job_handles: HashMap<String, tokio::task::JoinHandle<()>>,
     cancellation_tokens: HashMap<String, tokio_util::sync::CancellationToken>,
+    eta_estimators: HashMap<String, utils::EtaEstimator>,
+    // Results of recently finished jobs, oldest first, kept for export
+    retained_results: Vec<(String, Vec<TranscriptionResult>)>,
+    // Output files of each job, recorded as each file starts, so they can be cleaned up later
+    output_paths: JobOutputs,
+    // Indices of each job's files still waiting to start, in processing order
+    file_queues: HashMap<String, VecDeque<usize>>,
+    // Index of the file each job is processing right now
+    current_files: HashMap<String, usize>,
+    // Tail of each job's CLI output, filled from the CLI log callback
+    recent_output: RecentCliOutput,
+    // Job started most recently, whose output `recent_cli_output` returns
+    latest_job_id: Option<String>,
+    // CLI processes currently running for jobs, by PID
+    active_processes: ActiveProcesses,
+    // Cancels only the file each job is processing right now, for skipping it
+    file_tokens: HashMap<String, tokio_util::sync::CancellationToken>,
+    // Indices of each job's files skipped while they were being processed
+    skipped_files: HashMap<String, HashSet<usize>>,
+}
+
+/// Where a file skipped with `skip_file` was in its batch
+#[derive(Debug, PartialEq)]
+pub enum SkippedFile {
+    /// Taken off the queue before it started
+    Pending,
+    /// Stopped while being transcribed by these CLI processes
+    Running(Vec<u32>),
+}
+
+/// Running CLI processes by PID, shared with the process callbacks that track them
+pub type ActiveProcesses = Arc<std::sync::Mutex<HashMap<u32, models::ProcessInfo>>>;
+
+/// Output files per job, shared with the callbacks that record them before each file runs
+pub type JobOutputs = Arc<std::sync::Mutex<HashMap<String, Vec<String>>>>;
+
+/// Record `output_path` as an output of `job_id`, once
+pub fn record_job_output(job_outputs: &JobOutputs, job_id: &str, output_path: &str) {
+    if let Ok(mut job_outputs) = job_outputs.lock() {
+        let outputs = job_outputs.entry(job_id.to_string()).or_default();
+        if !outputs.iter().any(|output| output == output_path) {
+            outputs.push(output_path.to_string());
+        }
+    }
+}
+
+/// Recent CLI output lines per job, shared with the log callbacks that record them
+pub type RecentCliOutput = Arc<std::sync::Mutex<HashMap<String, utils::RecentLines>>>;
+
+/// Record a CLI output line for `job_id`
+pub fn record_recent_line(recent_output: &RecentCliOutput, job_id: &str, line: &str) {
+    if let Ok(mut recent_output) = recent_output.lock() {
+        recent_output
+            .entry(job_id.to_string())
+            .or_insert_with(|| utils::RecentLines::new(BatchProcessingManager::RECENT_OUTPUT_LINES))
+            .push(line);
+    }
 }
 
 impl BatchProcessingManager {
@@ -24,26 +97,308 @@ impl BatchProcessingManager {
             active_jobs: HashMap::new(),
             job_handles: HashMap::new(),
             cancellation_tokens: HashMap::new(),
+            eta_estimators: HashMap::new(),
+            retained_results: Vec::new(),
+            output_paths: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            file_queues: HashMap::new(),
+            current_files: HashMap::new(),
+            recent_output: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            latest_job_id: None,
+            active_processes: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            file_tokens: HashMap::new(),
+            skipped_files: HashMap::new(),
         }
     }
 
+    /// Number of finished jobs whose results are kept for export
+    const MAX_RETAINED_JOBS: usize = 20;
+
+    /// CLI output lines kept per job for `recent_cli_output`
+    pub const RECENT_OUTPUT_LINES: usize = 500;
+
     pub fn add_job(&mut self, job: ProcessingJob) {
+        self.file_queues.insert(job.id.clone(), (0..job.files.len()).collect());
+        self.latest_job_id = Some(job.id.clone());
         self.active_jobs.insert(job.id.clone(), job);
+        self.prune_recent_output();
+    }
+
+    /// Shared buffer the CLI log callback records into
+    pub fn recent_output(&self) -> RecentCliOutput {
+        self.recent_output.clone()
+    }
+
+    /// Record a CLI output line for `job_id`
+    pub fn record_cli_line(&self, job_id: &str, line: &str) {
+        record_recent_line(&self.recent_output, job_id, line);
+    }
+
+    /// The last `lines` lines of CLI output from the most recently started job
+    pub fn recent_cli_output(&self, lines: usize) -> Vec<String> {
+        let Some(job_id) = self.latest_job_id.as_deref() else {
+            return Vec::new();
+        };
+        self.recent_output.lock()
+            .ok()
+            .and_then(|recent_output| recent_output.get(job_id).map(|buffer| buffer.tail(lines)))
+            .unwrap_or_default()
+    }
+
+    /// Shared table the process callback records into
+    pub fn active_processes(&self) -> ActiveProcesses {
+        self.active_processes.clone()
+    }
+
+    /// Track `pid` as a CLI process transcribing `file_path` for `job_id`
+    pub fn register_process(&self, job_id: &str, pid: u32, file_path: &str) {
+        register_process(&self.active_processes, job_id, pid, file_path);
+    }
+
+    /// Running CLI processes, oldest first, with their elapsed time as of `now`
+    pub fn list_active_processes(&self, now: DateTime<Utc>) -> Vec<models::ProcessInfo> {
+        let Ok(active_processes) = self.active_processes.lock() else {
+            return Vec::new();
+        };
+        let mut processes: Vec<models::ProcessInfo> = active_processes
+            .values()
+            .map(|process| models::ProcessInfo {
+                elapsed_secs: (now - process.started_at).num_milliseconds().max(0) as f64 / 1000.0,
+                ..process.clone()
+            })
+            .collect();
+        processes.sort_by_key(|process| (process.started_at, process.pid));
+        processes
+    }
+
+    /// Whether `pid` is a CLI process started by one of our jobs
+    pub fn is_tracked_process(&self, pid: u32) -> bool {
+        self.active_processes.lock().is_ok_and(|active_processes| active_processes.contains_key(&pid))
+    }
+
+    // Keep output only for active jobs and the latest one
+    fn prune_recent_output(&mut self) {
+        let active_jobs = &self.active_jobs;
+        let latest_job_id = self.latest_job_id.as_deref();
+        if let Ok(mut recent_output) = self.recent_output.lock() {
+            recent_output.retain(|job_id, _| active_jobs.contains_key(job_id) || Some(job_id.as_str()) == latest_job_id);
+        }
+    }
+
+    /// Take the next file of `job_id` off its queue
+    pub fn next_file(&mut self, job_id: &str) -> Option<usize> {
+        let index = self.file_queues.get_mut(job_id)?.pop_front()?;
+        self.current_files.insert(job_id.to_string(), index);
+        Some(index)
+    }
+
+    /// Token for the file `job_id` is about to process, cancelled with the job or by skipping the file
+    pub fn file_cancellation_token(&mut self, job_id: &str, job_token: &tokio_util::sync::CancellationToken) -> tokio_util::sync::CancellationToken {
+        let token = job_token.child_token();
+        self.file_tokens.insert(job_id.to_string(), token.clone());
+        token
+    }
+
+    /// Skip the file of `job_id` at `file_path`: a pending file leaves the queue, the file in
+    /// flight has its token cancelled and its CLI processes returned so they can be killed
+    pub fn skip_file(&mut self, job_id: &str, file_path: &str) -> AppResult<SkippedFile> {
+        let job = self.active_jobs.get(job_id)
+            .ok_or_else(|| error::AppError::ProcessingError(format!("Job not found: {}", job_id)))?;
+        let resolved_path = utils::resolve_file_path(file_path);
+        let matches: Vec<usize> = job.files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| file.path == file_path || file.path == resolved_path)
+            .map(|(index, _)| index)
+            .collect();
+
+        if let Some(queue) = self.file_queues.get_mut(job_id) {
+            if let Some(position) = queue.iter().position(|index| matches.contains(index)) {
+                queue.remove(position);
+                return Ok(SkippedFile::Pending);
+            }
+        }
+
+        let current = self.current_files.get(job_id).copied().filter(|index| matches.contains(index));
+        let Some(current) = current else {
+            return Err(error::AppError::ConfigError(format!("{} is not pending or being processed in job {}", file_path, job_id)));
+        };
+        self.skipped_files.entry(job_id.to_string()).or_default().insert(current);
+        if let Some(token) = self.file_tokens.get(job_id) {
+            token.cancel();
+        }
+        let pids = self.active_processes.lock()
+            .map(|active_processes| active_processes
+                .values()
+                .filter(|process| process.job_id == job_id)
+                .map(|process| process.pid)
+                .collect())
+            .unwrap_or_default();
+        Ok(SkippedFile::Running(pids))
+    }
+
+    /// Whether file `file_index` of `job_id` was skipped while running, forgetting it
+    pub fn take_skipped(&mut self, job_id: &str, file_index: usize) -> bool {
+        self.skipped_files.get_mut(job_id).is_some_and(|skipped| skipped.remove(&file_index))
+    }
+
+    /// Files of `job_id` that haven't started yet, in the order they will run
+    pub fn pending_files(&self, job_id: &str) -> Vec<usize> {
+        self.file_queues
+            .get(job_id)
+            .map(|queue| queue.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Replace the pending order of `job_id`; `new_order` must list exactly the pending files
+    pub fn reorder_queue(&mut self, job_id: &str, new_order: Vec<usize>) -> AppResult<()> {
+        let queue = self.file_queues.get_mut(job_id)
+            .ok_or_else(|| error::AppError::ProcessingError(format!("Job not found: {}", job_id)))?;
+
+        let mut pending: Vec<usize> = queue.iter().copied().collect();
+        let mut requested = new_order.clone();
+        pending.sort_unstable();
+        requested.sort_unstable();
+        if pending != requested {
+            return Err(error::AppError::ConfigError(format!(
+                "New order {:?} must contain exactly the pending files {:?}",
+                new_order, queue
+            )));
+        }
+
+        *queue = new_order.into();
+        Ok(())
+    }
+
+    /// Move a pending file of `job_id` to the front of its queue
+    pub fn prioritize_file(&mut self, job_id: &str, file_index: usize) -> AppResult<()> {
+        let queue = self.file_queues.get_mut(job_id)
+            .ok_or_else(|| error::AppError::ProcessingError(format!("Job not found: {}", job_id)))?;
+
+        let position = queue.iter().position(|&index| index == file_index).ok_or_else(|| {
+            error::AppError::ConfigError(format!("File {} is not waiting to be processed", file_index))
+        })?;
+        if let Some(index) = queue.remove(position) {
+            queue.push_front(index);
+        }
+        Ok(())
     }
 
     pub fn get_job(&self, job_id: &str) -> Option<&ProcessingJob> {
         self.active_jobs.get(job_id)
     }
 
-    pub fn update_job_progress(&mut self, job_id: &str, progress: ProcessingProgress) {
+    /// Update job progress and return the refreshed completion estimate
+    pub fn update_job_progress(&mut self, job_id: &str, progress: ProcessingProgress) -> Option<DateTime<Utc>> {
+        let job = self.active_jobs.get_mut(job_id)?;
+        job.stage = progress.stage;
+
+        // Batch-level updates carry the file's position in the batch, file-level updates carry its progress
+        let current_file_fraction = match progress.file_index {
+            Some(index) => {
+                job.current_file_index = index;
+                0.0
+            }
+            None => progress.progress / 100.0,
+        };
+        // Files not queued and not in flight are done; before the queue is used, go by position
+        let total_files = job.files.len();
+        let (completed, current): (Vec<usize>, usize) = match (self.file_queues.get(job_id), self.current_files.get(job_id)) {
+            (Some(queue), Some(&current)) => {
+                ((0..total_files).filter(|index| *index != current && !queue.contains(index)).collect(), current)
+            }
+            _ => ((0..job.current_file_index.min(total_files)).collect(), job.current_file_index),
+        };
+        let durations: Vec<Option<f64>> = job.files.iter().map(|file| file.duration).collect();
+        job.progress = utils::overall_batch_progress(&durations, &completed, Some((current, current_file_fraction)));
+
+        if let Some(estimator) = self.eta_estimators.get(job_id) {
+            // The file in progress, then the queue in its current order
+            let remaining_durations: Vec<Option<f64>> = match self.file_queues.get(job_id) {
+                Some(queue) => self.current_files
+                    .get(job_id)
+                    .into_iter()
+                    .chain(queue.iter())
+                    .filter_map(|&index| job.files.get(index))
+                    .map(|file| file.duration)
+                    .collect(),
+                None => job.files
+                    .iter()
+                    .skip(job.current_file_index)
+                    .map(|file| file.duration)
+                    .collect(),
+            };
+
+            if let Some(seconds) = estimator.estimate_remaining(&remaining_durations, current_file_fraction) {
+                job.estimated_completion = Some(Utc::now() + chrono::Duration::milliseconds((seconds * 1000.0) as i64));
+            }
+        }
+
+        job.estimated_completion
+    }
+
+    /// Record a finished file so later completion estimates use its processing speed
+    pub fn record_file_completion(&mut self, job_id: &str, file_index: usize, processing_time: f64, audio_duration: Option<f64>) {
         if let Some(job) = self.active_jobs.get_mut(job_id) {
-            job.progress = progress.progress;
-            job.stage = progress.stage;
+            if let Some(file) = job.files.get_mut(file_index) {
+                if file.duration.is_none() {
+                    file.duration = audio_duration;
+                }
+            }
+        }
+
+        self.eta_estimators
+            .entry(job_id.to_string())
+            .or_default()
+            .record_file(processing_time, audio_duration);
+    }
+
+    /// Keep a finished job's results so they can be exported later
+    pub fn retain_results(&mut self, job_id: &str, results: Vec<TranscriptionResult>) {
+        self.retained_results.retain(|(id, _)| id != job_id);
+        self.retained_results.push((job_id.to_string(), results));
+        if self.retained_results.len() > Self::MAX_RETAINED_JOBS {
+            let (evicted, _) = self.retained_results.remove(0);
+            if let Ok(mut output_paths) = self.output_paths.lock() {
+                output_paths.remove(&evicted);
+            }
         }
     }
 
+    /// Remember an output file written by `job_id`
+    pub fn record_output(&mut self, job_id: &str, output_path: &str) {
+        record_job_output(&self.output_paths, job_id, output_path);
+    }
+
+    /// Shared handle to the recorded outputs, for `output_tracking_callback`
+    pub fn job_outputs(&self) -> JobOutputs {
+        self.output_paths.clone()
+    }
+
+    /// Forget everything about `job_id`, stopping it if it is still running, and return
+    /// the output files it wrote
+    pub fn cleanup_job(&mut self, job_id: &str) -> Vec<String> {
+        self.remove_job(job_id);
+        self.retained_results.retain(|(id, _)| id != job_id);
+        self.output_paths.lock()
+            .ok()
+            .and_then(|mut output_paths| output_paths.remove(job_id))
+            .unwrap_or_default()
+    }
+
+    pub fn get_results(&self, job_id: &str) -> Option<&Vec<TranscriptionResult>> {
+        self.retained_results
+            .iter()
+            .find(|(id, _)| id == job_id)
+            .map(|(_, results)| results)
+    }
+
     pub fn remove_job(&mut self, job_id: &str) {
         self.active_jobs.remove(job_id);
+        self.eta_estimators.remove(job_id);
+        self.file_queues.remove(job_id);
+        self.current_files.remove(job_id);
+        self.file_tokens.remove(job_id);
+        self.skipped_files.remove(job_id);
         if let Some(handle) = self.job_handles.remove(job_id) {
             handle.abort();
         }
@@ -64,6 +419,39 @@ impl BatchProcessingManager {
         }
     }
 
+    /// Cancel `job_id`, reporting how many of its files were done beforehand
+    pub fn cancel_job_with_progress(&mut self, job_id: &str) -> models::CancellationResult {
+        let completed = self.eta_estimators
+            .get(job_id)
+            .map(|estimator| estimator.completed_files())
+            .unwrap_or(0);
+        let in_progress = usize::from(self.current_files.contains_key(job_id));
+        let remaining = self.pending_files(job_id).len() + in_progress;
+
+        models::CancellationResult {
+            cancelled: self.cancel_job(job_id),
+            completed,
+            remaining,
+        }
+    }
+
+    /// Cancel jobs that started more than `max_age` before `now`, returning their ids
+    pub fn cancel_stale_jobs(&mut self, max_age: std::time::Duration, now: DateTime<Utc>) -> Vec<String> {
+        let max_age = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+        let mut stale: Vec<String> = self.active_jobs
+            .values()
+            .filter(|job| !job.is_cancelled && now.signed_duration_since(job.start_time) > max_age)
+            .map(|job| job.id.clone())
+            .collect();
+        stale.sort();
+
+        for job_id in &stale {
+            println!("🛑 Cancelling stale job {}", job_id);
+            self.cancel_job(job_id);
+        }
+        stale
+    }
+
     pub fn add_cancellation_token(&mut self, job_id: String, token: tokio_util::sync::CancellationToken) {
         self.cancellation_tokens.insert(job_id, token);
     }
@@ -72,36 +460,165 @@ impl BatchProcessingManager {
         self.job_handles.insert(job_id, handle);
     }
 
+    /// Drop the results kept for export and bookkeeping left by finished jobs; returns
+    /// the number of results released
+    pub fn release_cached_state(&mut self) -> usize {
+        let released = self.retained_results.iter().map(|(_, results)| results.len()).sum();
+        self.retained_results = Vec::new();
+
+        let active_jobs = &self.active_jobs;
+        self.eta_estimators.retain(|job_id, _| active_jobs.contains_key(job_id));
+        if let Ok(mut output_paths) = self.output_paths.lock() {
+            output_paths.retain(|job_id, _| active_jobs.contains_key(job_id));
+        }
+        self.file_queues.retain(|job_id, _| active_jobs.contains_key(job_id));
+        self.current_files.retain(|job_id, _| active_jobs.contains_key(job_id));
+        self.prune_recent_output();
+        self.eta_estimators.shrink_to_fit();
+        self.job_handles.retain(|_, handle| !handle.is_finished());
+        self.job_handles.shrink_to_fit();
+        released
+    }
+
     pub fn get_active_jobs(&self) -> Vec<&ProcessingJob> {
         self.active_jobs.values().collect()
     }
+
+    /// Cancel every job and hand back their task handles so the caller can wait for them
+    pub fn cancel_all(&mut self) -> Vec<tokio::task::JoinHandle<()>> {
+        for job in self.active_jobs.values_mut() {
+            job.is_cancelled = true;
+        }
+        for token in self.cancellation_tokens.values() {
+            token.cancel();
+        }
+        self.job_handles.drain().map(|(_, handle)| handle).collect()
+    }
+}
+
+/// How long shutdown waits for cancelled jobs to stop before aborting them
+pub const SHUTDOWN_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Cancel all batch jobs, give them `grace_period` to wind down, then wait for
+/// in-flight history writes so finished results aren't lost on exit
+pub async fn shutdown(manager: &Mutex<BatchProcessingManager>, grace_period: std::time::Duration) -> usize {
+    let handles = manager.lock().await.cancel_all();
+    let cancelled = handles.len();
+    println!("🛑 Shutting down, cancelling {} job(s)", cancelled);
+
+    let abort_handles: Vec<_> = handles.iter().map(|handle| handle.abort_handle()).collect();
+    if tokio::time::timeout(grace_period, futures::future::join_all(handles)).await.is_err() {
+        println!("⚠️ Jobs did not stop within {:?}, aborting", grace_period);
+        for handle in abort_handles {
+            handle.abort();
+        }
+    }
+
+    history::flush_pending_writes().await;
+    cancelled
+}
+
+/// Remove `job_id` from `manager` and delete the output files it wrote. Unknown jobs
+/// and outputs that are already gone are skipped.
+pub async fn cleanup_job_artifacts(manager: &Mutex<BatchProcessingManager>, job_id: &str) -> AppResult<models::CacheClearReport> {
+    let output_paths = manager.lock().await.cleanup_job(job_id);
+
+    let mut report = models::CacheClearReport::default();
+    for path in output_paths {
+        let size = match std::fs::metadata(&path) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => continue,
+        };
+        std::fs::remove_file(&path)?;
+        report.files_removed += 1;
+        report.bytes_freed += size;
+    }
+
+    println!("🧹 Cleaned up job {}: {} files removed", job_id, report.files_removed);
+    Ok(report)
+}
+
+/// Release in-memory caches to reduce the app's footprint between batches; returns the
+/// number of cached results dropped. History and cached transcriptions live on disk and
+/// are left alone.
+pub async fn release_memory(manager: &Mutex<BatchProcessingManager>) -> usize {
+    let mut manager = manager.lock().await;
+    let released = manager.release_cached_state();
+
+    // Streams of jobs that are no longer running
+    if let Ok(mut streams) = LOG_STREAMS.lock() {
+        streams.retain(|job_id| manager.get_job(job_id).is_some());
+        streams.shrink_to_fit();
+    }
+
+    println!("🧹 Released {} cached result(s)", released);
+    released
 }
 
 use error::AppResult;
-use models::{AppSettings, TranscriptionResult, AudioFile, ProcessingJob, ProcessingProgress, ProcessingStage};
-use cli::{CliManager, CliResult};
+use models::{AppSettings, TranscriptionResult, AudioFile, AudioInfo, OutputFormat, ProcessingJob, ProcessingProgress, ProcessingStage};
+use cli::{CliManager, CliResult, ConcurrencyLimiter};
 use settings::SettingsManager;
+use export::ExportFormat;
+use transcription_cache::TranscriptionCache;
+use history::{HistoryEntry, HistoryManager};
 use system::{SystemIntegration, FormatInfo, SystemInfo, SystemDependencyCheck};
 use macos_integration::{MacOSIntegration, NotificationOptions, DockBadgeInfo, FileAssociationStatus};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use std::collections::HashMap;
-use chrono::Utc;
+use std::collections::{HashMap, HashSet, VecDeque};
+use chrono::{DateTime, Utc};
 use tauri::Emitter;
 
-/// Create CLI manager based on environment
-fn create_cli_manager() -> CliManager {
+/// Create CLI manager based on environment and the user's settings
+fn create_cli_manager(settings: &AppSettings) -> CliManager {
     // Check if we're in development mode
-    if cfg!(debug_assertions) {
+    let manager = if cfg!(debug_assertions) {
         // Development mode - use development CLI paths
         CliManager::new_dev()
     } else {
         // Production mode - use sidecar
         CliManager::new()
+    };
+
+    let manager = match settings.cli_binary_path.as_deref().filter(|path| !path.trim().is_empty()) {
+        Some(path) => manager.with_cli_binary_path(path),
+        None => manager,
+    };
+
+    manager
+        .with_transcription_cache(TranscriptionCache::new(TranscriptionCache::default_directory()))
+        .with_timeout(std::time::Duration::from_secs(settings.processing_timeout_secs as u64))
+        .with_concurrency_limiter(CLI_CONCURRENCY_LIMITER.clone())
+}
+
+/// Record a completed transcription in the history; failures are only logged
+async fn record_in_history(result: &TranscriptionResult) {
+    let max_entries = load_saved_settings().await.max_history_entries;
+    let recorded = match HistoryManager::new() {
+        Ok(manager) => manager.with_max_entries(max_entries).add_entry(result).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = recorded {
+        println!("⚠️ Failed to record history entry: {}", e);
+    }
+}
+
+/// Saved settings for commands that aren't given settings, falling back to defaults
+async fn load_saved_settings() -> AppSettings {
+    match SettingsManager::new() {
+        Ok(manager) => manager.load_settings().await.unwrap_or_default(),
+        Err(_) => AppSettings::default(),
     }
 }
 
 // Basic Tauri commands for initial setup
+/// Names of every event the backend emits
+#[tauri::command]
+async fn list_event_names() -> Vec<String> {
+    events::ALL.iter().map(|name| name.to_string()).collect()
+}
+
 #[tauri::command]
 async fn get_app_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
@@ -117,11 +634,54 @@ async fn get_supported_formats_detailed() -> Vec<FormatInfo> {
     SystemIntegration::get_supported_formats_detailed()
 }
 
+#[tauri::command]
+async fn get_supported_languages() -> Vec<String> {
+    models::SUPPORTED_LANGUAGES.iter().map(|(code, _)| code.to_string()).collect()
+}
+
 #[tauri::command]
 async fn validate_audio_file(file_path: String) -> AppResult<models::AudioFile> {
     utils::create_audio_file(&file_path)
 }
 
+#[tauri::command]
+async fn preview_output_path(input_path: String, output_dir: String, format: OutputFormat) -> AppResult<String> {
+    let settings = load_saved_settings().await;
+    utils::get_output_path(&input_path, &output_dir, &settings, &format)
+}
+
+/// Rename past outputs to match a new filename template, returning (old, new) path pairs
+#[tauri::command]
+async fn rename_outputs(paths: Vec<String>, template: String) -> Vec<(String, String)> {
+    utils::rename_outputs(&paths, &template, &load_saved_settings().await)
+}
+
+/// Load the metadata written next to a transcription output
+#[tauri::command]
+async fn read_metadata_sidecar(path: String) -> AppResult<export::MetadataSidecar> {
+    export::read_metadata_sidecar(&path)
+}
+
+#[tauri::command]
+async fn merge_transcriptions(paths: Vec<String>, output_path: String, separator: Option<String>) -> AppResult<()> {
+    utils::merge_text_files(&paths, &output_path, separator.as_deref())
+}
+
+#[tauri::command]
+async fn transcode_to_wav(input_path: String, output_dir: String) -> AppResult<String> {
+    utils::transcode_to_wav(&input_path, &output_dir).await
+}
+
+#[tauri::command]
+async fn scan_directory_for_audio(dir_path: String, recursive: bool) -> AppResult<Vec<AudioFile>> {
+    utils::scan_directory_for_audio(&dir_path, recursive)
+}
+
+#[tauri::command]
+async fn get_audio_metadata(file_path: String) -> AppResult<AudioInfo> {
+    utils::probe_audio_metadata(&file_path)
+}
+
 #[tauri::command]
 async fn get_default_settings() -> AppSettings {
     AppSettings::default()
@@ -130,10 +690,16 @@ async fn get_default_settings() -> AppSettings {
 // Settings Management Commands
 #[tauri::command]
 async fn load_settings() -> AppResult<AppSettings> {
-    let manager = SettingsManager::new()?;
+    let manager = SettingsManager::new()?.with_auto_repair(true);
     manager.load_settings().await
 }
 
+#[tauri::command]
+async fn repair_settings() -> AppResult<AppSettings> {
+    let manager = SettingsManager::new()?;
+    manager.repair_settings().await
+}
+
 #[tauri::command]
 async fn save_settings(settings: AppSettings) -> AppResult<()> {
     let manager = SettingsManager::new()?;
@@ -141,44 +707,26 @@ async fn save_settings(settings: AppSettings) -> AppResult<()> {
 }
 
 #[tauri::command]
-async fn update_settings_field(field: String, value: serde_json::Value) -> AppResult<AppSettings> {
+async fn update_settings_field(app_handle: tauri::AppHandle, field: String, value: serde_json::Value) -> AppResult<AppSettings> {
     let manager = SettingsManager::new()?;
+    let old_settings = manager.load_settings().await?;
     
-    manager.update_settings(|settings| {
-        match field.as_str() {
-            "language" => {
-                if let Some(lang) = value.as_str() {
-                    settings.language = lang.to_string();
-                }
-            }
-            "model_size" => {
-                if let Ok(model_size) = serde_json::from_value(value) {
-                    settings.model_size = model_size;
-                }
-            }
-            "output_directory" => {
-                if let Some(dir) = value.as_str() {
-                    settings.output_directory = dir.to_string();
-                }
-            }
-            "include_metadata" => {
-                if let Some(include) = value.as_bool() {
-                    settings.include_metadata = include;
-                }
-            }
-            "auto_save" => {
-                if let Some(auto_save) = value.as_bool() {
-                    settings.auto_save = auto_save;
-                }
-            }
-            "theme" => {
-                if let Ok(theme) = serde_json::from_value(value) {
-                    settings.theme = theme;
-                }
-            }
-            _ => {} // Ignore unknown fields
-        }
-    }).await
+    let mut updated = old_settings.clone();
+    settings::apply_settings_field(&mut updated, &field, &value)?;
+    manager.save_settings(&updated).await?;
+
+    // Running jobs keep the settings they started with
+    if settings::settings_changed_requires_restart(&old_settings, &updated)
+        && !BATCH_MANAGER.lock().await.get_active_jobs().is_empty()
+    {
+        let _ = app_handle.emit(events::SETTINGS_RESTART_REQUIRED, serde_json::json!({ "field": field }));
+    }
+    Ok(updated)
+}
+
+#[tauri::command]
+fn validate_settings_field(field: String, value: serde_json::Value) -> AppResult<()> {
+    settings::validate_settings_field(&field, &value)
 }
 
 #[tauri::command]
@@ -194,6 +742,11 @@ async fn validate_settings(settings: AppSettings) -> AppResult<bool> {
     Ok(true)
 }
 
+#[tauri::command]
+fn get_settings_warnings(settings: AppSettings) -> Vec<String> {
+    settings::settings_warnings(&settings)
+}
+
 #[tauri::command]
 async fn get_settings_config_path() -> AppResult<String> {
     let manager = SettingsManager::new()?;
@@ -212,6 +765,12 @@ async fn export_settings_to_file(export_path: String) -> AppResult<()> {
     manager.export_settings(&export_path).await
 }
 
+/// Settings that differ between `a` and `b`, e.g. to flag unsaved changes
+#[tauri::command]
+async fn diff_settings(a: AppSettings, b: AppSettings) -> Vec<models::SettingsDiff> {
+    settings::diff_settings(&a, &b)
+}
+
 #[tauri::command]
 async fn import_settings_from_file(import_path: String) -> AppResult<AppSettings> {
     let manager = SettingsManager::new()?;
@@ -220,13 +779,13 @@ async fn import_settings_from_file(import_path: String) -> AppResult<AppSettings
 
 // System Integration Commands
 #[tauri::command]
-async fn select_directory() -> AppResult<Option<String>> {
-    SystemIntegration::select_directory().await
+async fn select_directory(app_handle: tauri::AppHandle) -> AppResult<Option<String>> {
+    SystemIntegration::select_directory_with(&app_handle).await
 }
 
 #[tauri::command]
-async fn select_files(multiple: bool) -> AppResult<Vec<String>> {
-    SystemIntegration::select_files(multiple).await
+async fn select_files(multiple: bool, app_handle: tauri::AppHandle) -> AppResult<Vec<String>> {
+    SystemIntegration::select_files_with(&app_handle, multiple).await
 }
 
 #[tauri::command]
@@ -234,6 +793,13 @@ async fn reveal_file_in_explorer(file_path: String) -> AppResult<()> {
     SystemIntegration::reveal_file_in_explorer(&file_path).await
 }
 
+/// Show the configured output directory in the file manager
+#[tauri::command]
+async fn open_output_directory() -> AppResult<()> {
+    let settings = SettingsManager::new()?.load_settings().await?;
+    SystemIntegration::open_directory(&settings.output_directory_for(""))
+}
+
 #[tauri::command]
 async fn open_file_with_default_app(file_path: String) -> AppResult<()> {
     SystemIntegration::open_file_with_default_app(&file_path).await
@@ -290,18 +856,25 @@ async fn select_output_directory() -> AppResult<Option<String>> {
     SystemIntegration::select_directory().await
 }
 
+/// Check that transcriptions can be written to `path`, e.g. right after the user picks it
+#[tauri::command]
+async fn check_output_directory_writable(path: String) -> AppResult<()> {
+    utils::check_output_directory_writable(&path)
+}
+
+/// Save text in the encoding chosen in settings
 #[tauri::command]
 async fn save_text_file(content: String, file_path: String) -> AppResult<()> {
-    tokio::fs::write(&file_path, content).await?;
-    Ok(())
+    let encoding = load_saved_settings().await.output_encoding;
+    utils::write_output_text(&file_path, &content, &encoding)
 }
 
 #[tauri::command]
-async fn save_binary_file(filename: String, content: String, is_base64: bool) -> AppResult<String> {
+async fn save_binary_file(filename: String, content: String, is_base64: bool, app_handle: tauri::AppHandle) -> AppResult<String> {
 
     
     // Use system file dialog to get save location
-    let save_path = if let Some(path) = SystemIntegration::save_file_dialog(&filename).await? {
+    let save_path = if let Some(path) = SystemIntegration::save_file_dialog_with(&app_handle, &filename).await? {
         path
     } else {
         return Err(error::AppError::FileNotFound("Save cancelled by user".to_string()));
@@ -325,51 +898,80 @@ async fn open_file_in_finder(file_path: String) -> AppResult<()> {
     SystemIntegration::reveal_file_in_explorer(&file_path).await
 }
 
+/// Remove transcription outputs from `directory` (default: the CLI's cache output folder)
 #[tauri::command]
-async fn clear_output_cache() -> AppResult<()> {
-    use std::path::PathBuf;
-
-    // Get the app cache directory
-    let cache_dir = dirs::cache_dir()
-        .ok_or_else(|| error::AppError::ProcessingError("Could not find cache directory".to_string()))?
-        .join("SpeechToText")
-        .join("output");
-
-    if !cache_dir.exists() {
-        return Ok(()); // Directory doesn't exist, nothing to clear
-    }
+async fn clear_output_cache(
+    directory: Option<String>,
+    extensions: Option<Vec<String>>,
+) -> AppResult<models::CacheClearReport> {
+    let cache_dir = match directory.filter(|dir| !dir.trim().is_empty()) {
+        Some(dir) => std::path::PathBuf::from(dir.trim()),
+        None => dirs::cache_dir()
+            .ok_or_else(|| error::AppError::ProcessingError("Could not find cache directory".to_string()))?
+            .join("SpeechToText")
+            .join("output"),
+    };
 
-    // Read directory contents and remove transcription files
-    let mut entries = tokio::fs::read_dir(&cache_dir).await?;
+    utils::clear_transcription_outputs(&cache_dir, extensions.as_deref())
+}
 
-    while let Some(entry) = entries.next_entry().await? {
-        let path = entry.path();
-        if path.is_file() {
-            // Remove transcription files (files containing "_transcription" and ending with .txt)
-            if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-                if file_name.contains("_transcription") && file_name.ends_with(".txt") {
-                    tokio::fs::remove_file(&path).await?;
-                }
-            }
-        }
-    }
+/// Intermediate files left in the CLI working directory, with their sizes in bytes
+#[tauri::command]
+async fn list_temp_files() -> Vec<(String, u64)> {
+    // Cached transcriptions are kept on purpose, not leftovers
+    utils::list_files_under(&utils::work_directory(), Some(&TranscriptionCache::default_directory()))
+}
 
-    Ok(())
+/// Delete files returned by `list_temp_files`; anything outside the working directory is refused
+#[tauri::command]
+async fn delete_temp_files(paths: Vec<String>) -> AppResult<models::CacheClearReport> {
+    utils::delete_files_under(&utils::work_directory(), &paths)
 }
 
 // CLI Integration Commands
 #[tauri::command]
 async fn check_cli_availability() -> AppResult<bool> {
-    let manager = create_cli_manager();
+    let manager = create_cli_manager(&load_saved_settings().await);
     manager.check_cli_availability().await
 }
 
+/// Transcribe a short sample with the tiny model to confirm the whole pipeline works
+#[tauri::command]
+async fn run_self_test() -> AppResult<TranscriptionResult> {
+    let settings = load_saved_settings().await;
+    create_cli_manager(&settings).run_self_test(&settings).await
+}
+
 #[tauri::command]
 async fn get_cli_version() -> AppResult<String> {
-    let manager = create_cli_manager();
+    let manager = create_cli_manager(&load_saved_settings().await);
     manager.get_cli_version().await
 }
 
+#[tauri::command]
+async fn get_app_health() -> system::AppHealth {
+    let settings = load_saved_settings().await;
+    let manager = create_cli_manager(&settings);
+    SystemIntegration::collect_app_health(&manager, &settings.output_directory).await
+}
+
+/// Probe for the CLI again, e.g. after it was installed while the app is running. Discovery
+/// isn't cached, so a manager built from freshly loaded settings finds the new install.
+#[tauri::command]
+async fn refresh_cli() -> system::AppHealth {
+    let settings = load_saved_settings().await;
+    let manager = create_cli_manager(&settings);
+    let health = SystemIntegration::collect_app_health(&manager, &settings.output_directory).await;
+    println!("🔄 CLI refreshed: {}", manager.describe_resolved_cli());
+    health
+}
+
+#[tauri::command]
+async fn get_resolved_cli_path() -> String {
+    let settings = load_saved_settings().await;
+    create_cli_manager(&settings).describe_resolved_cli()
+}
+
 #[tauri::command]
 async fn process_audio_file(
     file_path: String,
@@ -379,33 +981,85 @@ async fn process_audio_file(
     println!("🔥 process_audio_file called with: {}", file_path);
     println!("🔥 settings: {:?}", settings);
 
-    let manager = create_cli_manager();
+    // Stream segment text as it is transcribed
+    let partial_app_handle = app_handle.clone();
+    let manager = create_cli_manager(&settings).with_partial_text_callback(Arc::new(move |partial| {
+        let _ = partial_app_handle.emit(events::TRANSCRIPTION_PARTIAL, &partial);
+    }));
     println!("🔥 CliManager created, about to call process_file");
 
     // Create progress callback to emit events
     let app_handle_clone = app_handle.clone();
     let progress_callback: cli::ProgressCallback = Arc::new(move |progress| {
         println!("🔥 Single file progress: {:?}", progress);
-        let _ = app_handle_clone.emit("file-progress", &progress);
+        let _ = app_handle_clone.emit(events::FILE_PROGRESS, &progress);
     });
 
     let result = manager.process_file(&file_path, &settings, Some(progress_callback)).await;
 
     match &result {
-        Ok(transcription) => println!("🔥 process_file completed successfully: {:?}", transcription),
-        Err(e) => println!("🔥 process_file failed: {:?}", e),
+        Ok(transcription) => {
+            println!("🔥 process_file completed successfully: {:?}", transcription);
+            emit_file_completed(&app_handle, transcription);
+            emit_low_confidence(&app_handle, transcription, settings.low_confidence_threshold);
+            emit_no_speech(&app_handle, transcription);
+            record_in_history(transcription).await;
+        }
+        Err(e) => {
+            println!("🔥 process_file failed: {:?}", e);
+            emit_file_error(&app_handle, &file_path, e, None);
+        }
     }
 
     result
 }
 
+#[tauri::command]
+async fn reprocess_result(
+    result: TranscriptionResult,
+    settings_override: Option<AppSettings>,
+    app_handle: tauri::AppHandle,
+) -> AppResult<TranscriptionResult> {
+    let settings = match settings_override {
+        Some(settings) => settings,
+        None => CliManager::settings_for_result(&result, &load_saved_settings().await),
+    };
+
+    let partial_app_handle = app_handle.clone();
+    let manager = create_cli_manager(&settings).with_partial_text_callback(Arc::new(move |partial| {
+        let _ = partial_app_handle.emit(events::TRANSCRIPTION_PARTIAL, &partial);
+    }));
+
+    let progress_app_handle = app_handle.clone();
+    let progress_callback: cli::ProgressCallback = Arc::new(move |progress| {
+        let _ = progress_app_handle.emit(events::FILE_PROGRESS, &progress);
+    });
+
+    let outcome = manager.reprocess_result(&result, &settings, Some(progress_callback)).await;
+    match &outcome {
+        Ok(transcription) => {
+            emit_file_completed(&app_handle, transcription);
+            emit_low_confidence(&app_handle, transcription, settings.low_confidence_threshold);
+            emit_no_speech(&app_handle, transcription);
+            record_in_history(transcription).await;
+        }
+        Err(e) => emit_file_error(&app_handle, &result.original_file.path, e, None),
+    }
+
+    outcome
+}
+
 #[tauri::command]
 async fn process_batch_files(
     file_paths: Vec<String>,
     settings: AppSettings,
 ) -> AppResult<Vec<TranscriptionResult>> {
-    let manager = create_cli_manager();
-    manager.process_batch(&file_paths, &settings, None).await
+    let manager = create_cli_manager(&settings);
+    let results = manager.process_batch(&file_paths, &settings, None).await?;
+    for result in &results {
+        record_in_history(result).await;
+    }
+    Ok(results)
 }
 
 // Enhanced Batch Processing Commands
@@ -414,11 +1068,16 @@ async fn start_batch_processing(
     app_handle: tauri::AppHandle,
     file_paths: Vec<String>,
     settings: AppSettings,
+    file_settings: Option<Vec<Option<AppSettings>>>,
 ) -> AppResult<String> {
+    // Per-file overrides replace the batch settings for their file only
+    let file_settings = file_settings.unwrap_or_default();
+    CliManager::validate_file_settings(&file_paths, &file_settings)?;
+
     // Validate all files first
     let mut audio_files = Vec::new();
     for path in &file_paths {
-        let audio_file = utils::create_audio_file(path)?;
+        let audio_file = utils::create_audio_file_with_limit(path, settings.max_file_size_mb)?;
         audio_files.push(audio_file);
     }
 
@@ -434,11 +1093,16 @@ async fn start_batch_processing(
         estimated_completion: None,
         is_cancelled: false,
         can_cancel: true,
+        concurrency: settings.resolved_concurrent_jobs(),
     };
 
     // Create cancellation token
     let cancellation_token = tokio_util::sync::CancellationToken::new();
 
+    if settings.unload_model_when_idle && BATCH_MANAGER.lock().await.get_active_jobs().is_empty() {
+        release_memory(&BATCH_MANAGER).await;
+    }
+
     // Add job to manager
     {
         let mut manager = BATCH_MANAGER.lock().await;
@@ -453,7 +1117,7 @@ async fn start_batch_processing(
     let app_handle_clone = app_handle.clone();
 
     let handle = tokio::spawn(async move {
-        process_batch_with_events(app_handle_clone, job_id_clone, file_paths_clone, settings_clone, cancellation_token).await;
+        process_batch_with_events(app_handle_clone, job_id_clone, file_paths_clone, settings_clone, file_settings, cancellation_token).await;
     });
 
     // Store the handle
@@ -465,47 +1129,169 @@ async fn start_batch_processing(
     Ok(job_id)
 }
 
+/// Start a fresh batch for the files of an interrupted batch that the history doesn't
+/// have a finished transcription for
 #[tauri::command]
-async fn get_batch_progress(job_id: String) -> AppResult<Option<ProcessingJob>> {
-    let manager = BATCH_MANAGER.lock().await;
-    Ok(manager.get_job(&job_id).cloned())
+async fn resume_batch(
+    app_handle: tauri::AppHandle,
+    original_file_paths: Vec<String>,
+    settings: AppSettings,
+) -> AppResult<models::ResumedBatch> {
+    let remaining = HistoryManager::new()?.unfinished_files(&original_file_paths).await?;
+    let skipped_files = original_file_paths.len() - remaining.len();
+    println!("🔁 Resuming batch: {} files left, {} already done", remaining.len(), skipped_files);
+
+    if remaining.is_empty() {
+        return Ok(models::ResumedBatch { job_id: None, skipped_files });
+    }
+    let job_id = start_batch_processing(app_handle, remaining, settings, None).await?;
+    Ok(models::ResumedBatch { job_id: Some(job_id), skipped_files })
 }
 
 #[tauri::command]
-async fn cancel_batch_processing(job_id: String) -> AppResult<bool> {
-    let mut manager = BATCH_MANAGER.lock().await;
-    let cancelled = manager.cancel_job(&job_id);
-    Ok(cancelled)
+async fn start_log_stream(job_id: String) -> AppResult<()> {
+    enable_log_stream(&job_id);
+    Ok(())
 }
 
 #[tauri::command]
-async fn cancel_processing_job(job_id: String) -> AppResult<bool> {
-    let mut manager = BATCH_MANAGER.lock().await;
-    let cancelled = manager.cancel_job(&job_id);
-    Ok(cancelled)
+async fn stop_log_stream(job_id: String) -> AppResult<bool> {
+    Ok(disable_log_stream(&job_id))
 }
 
 #[tauri::command]
-async fn get_active_batch_jobs() -> AppResult<Vec<ProcessingJob>> {
-    let manager = BATCH_MANAGER.lock().await;
-    Ok(manager.get_active_jobs().into_iter().cloned().collect())
+async fn prepare_shutdown() -> AppResult<usize> {
+    Ok(shutdown(&BATCH_MANAGER, SHUTDOWN_GRACE_PERIOD).await)
 }
 
+/// Stop `job_id` if needed and delete the output files it produced
 #[tauri::command]
-async fn estimate_batch_processing_time(file_paths: Vec<String>) -> AppResult<f64> {
-    // Simple estimation based on file count and average processing time
-    // In a real implementation, this could consider file sizes and system performance
-    let file_count = file_paths.len() as f64;
-    let average_time_per_file = 30.0; // seconds, rough estimate
-    Ok(file_count * average_time_per_file)
+async fn cleanup_job(job_id: String) -> AppResult<models::CacheClearReport> {
+    cleanup_job_artifacts(&BATCH_MANAGER, &job_id).await
 }
 
 #[tauri::command]
-async fn validate_batch_requirements(
-    file_paths: Vec<String>,
-    output_directory: String,
-) -> AppResult<models::BatchValidationResult> {
-    let mut validation_result = models::BatchValidationResult {
+async fn free_memory() -> AppResult<usize> {
+    Ok(release_memory(&BATCH_MANAGER).await)
+}
+
+#[tauri::command]
+async fn get_batch_progress(job_id: String) -> AppResult<Option<ProcessingJob>> {
+    let manager = BATCH_MANAGER.lock().await;
+    Ok(manager.get_job(&job_id).cloned())
+}
+
+/// Set the order of the files in `job_id` that haven't started yet; returns the new queue
+#[tauri::command]
+async fn reorder_job_queue(job_id: String, new_order: Vec<usize>) -> AppResult<Vec<usize>> {
+    let mut manager = BATCH_MANAGER.lock().await;
+    manager.reorder_queue(&job_id, new_order)?;
+    Ok(manager.pending_files(&job_id))
+}
+
+/// Process a waiting file of `job_id` next; returns the new queue
+#[tauri::command]
+async fn prioritize_file(job_id: String, file_index: usize) -> AppResult<Vec<usize>> {
+    let mut manager = BATCH_MANAGER.lock().await;
+    manager.prioritize_file(&job_id, file_index)?;
+    Ok(manager.pending_files(&job_id))
+}
+
+#[tauri::command]
+async fn cancel_batch_processing(job_id: String) -> AppResult<models::CancellationResult> {
+    let mut manager = BATCH_MANAGER.lock().await;
+    Ok(manager.cancel_job_with_progress(&job_id))
+}
+
+#[tauri::command]
+async fn cancel_processing_job(job_id: String) -> AppResult<models::CancellationResult> {
+    let mut manager = BATCH_MANAGER.lock().await;
+    Ok(manager.cancel_job_with_progress(&job_id))
+}
+
+/// CLI processes running for batch jobs
+#[tauri::command]
+async fn list_active_processes() -> Vec<models::ProcessInfo> {
+    BATCH_MANAGER.lock().await.list_active_processes(Utc::now())
+}
+
+/// Kill a CLI process of one of our jobs; other PIDs are refused
+#[tauri::command]
+async fn force_kill_process(pid: u32) -> AppResult<()> {
+    if !BATCH_MANAGER.lock().await.is_tracked_process(pid) {
+        return Err(error::AppError::ProcessingError(format!("Process {} is not a running transcription", pid)));
+    }
+    SystemIntegration::kill_process(pid)
+}
+
+/// Skip one file of a running batch without cancelling the rest
+#[tauri::command]
+async fn skip_file_in_batch(job_id: String, file_path: String, app_handle: tauri::AppHandle) -> AppResult<()> {
+    let skipped = BATCH_MANAGER.lock().await.skip_file(&job_id, &file_path)?;
+    match skipped {
+        SkippedFile::Pending => emit_file_skipped(&app_handle, &file_path, None),
+        // The batch reports the skip once the file's run returns
+        SkippedFile::Running(pids) => {
+            for pid in pids {
+                if let Err(e) = SystemIntegration::kill_process(pid) {
+                    println!("⚠️ Failed to kill process {}: {}", pid, e);
+                }
+            }
+        }
+    }
+    println!("⏭️ Skipped {} in job {}", file_path, job_id);
+    Ok(())
+}
+
+/// Cancel jobs running for longer than `max_age_secs`, e.g. ones stuck on a hung CLI
+#[tauri::command]
+async fn cancel_stale_jobs(max_age_secs: u64) -> Vec<String> {
+    BATCH_MANAGER.lock().await.cancel_stale_jobs(std::time::Duration::from_secs(max_age_secs), Utc::now())
+}
+
+#[tauri::command]
+async fn get_active_batch_jobs() -> AppResult<Vec<ProcessingJob>> {
+    let manager = BATCH_MANAGER.lock().await;
+    Ok(manager.get_active_jobs().into_iter().cloned().collect())
+}
+
+#[tauri::command]
+async fn estimate_batch_processing_time(file_paths: Vec<String>, settings: Option<AppSettings>) -> AppResult<f64> {
+    let settings = match settings {
+        Some(settings) => settings,
+        None => load_saved_settings().await,
+    };
+
+    // Files whose duration can't be probed fall back to a flat per-file estimate
+    let durations = probe_durations(&file_paths);
+    Ok(utils::estimate_batch_duration(&durations, &settings.model_size, settings.resolved_concurrent_jobs()))
+}
+
+/// Audio durations in seconds, `None` where the file can't be probed
+fn probe_durations(file_paths: &[String]) -> Vec<Option<f64>> {
+    file_paths
+        .iter()
+        .map(|path| utils::probe_audio_metadata(path).ok().map(|info| info.duration).filter(|d| *d > 0.0))
+        .collect()
+}
+
+/// Estimate the disk space in bytes the outputs of a batch need (default: the saved output format)
+#[tauri::command]
+async fn estimate_batch_output_size(file_paths: Vec<String>, output_format: Option<OutputFormat>) -> AppResult<u64> {
+    let output_format = match output_format {
+        Some(output_format) => output_format,
+        None => load_saved_settings().await.output_format,
+    };
+    Ok(utils::estimate_output_size(&probe_durations(&file_paths), &output_format))
+}
+
+#[tauri::command]
+async fn validate_batch_requirements(
+    file_paths: Vec<String>,
+    output_directory: String,
+    model_size: Option<models::ModelSize>,
+) -> AppResult<models::BatchValidationResult> {
+    let mut validation_result = models::BatchValidationResult {
         valid_files: Vec::new(),
         invalid_files: Vec::new(),
         total_size: 0,
@@ -520,14 +1306,24 @@ async fn validate_batch_requirements(
         validation_result.warnings.push(format!("Output directory issue: {}", e));
     }
 
+    let saved_settings = load_saved_settings().await;
+
     // Validate each file
+    let mut oversized = Vec::new();
+    let mut empty = Vec::new();
     for path in file_paths {
-        match utils::create_audio_file(&path) {
+        match utils::create_audio_file_with_limit(&path, saved_settings.max_file_size_mb) {
             Ok(audio_file) => {
                 validation_result.total_size += audio_file.size;
                 validation_result.valid_files.push(audio_file);
             }
             Err(e) => {
+                let name = std::path::Path::new(&path).file_name().map(|name| name.to_string_lossy().to_string());
+                match e {
+                    error::AppError::FileTooLarge(_) => oversized.push(name.unwrap_or_else(|| path.clone())),
+                    error::AppError::AudioDecodeError(_) => empty.push(name.unwrap_or_else(|| path.clone())),
+                    _ => {}
+                }
                 validation_result.invalid_files.push(models::FileValidationError {
                     file_path: path,
                     error_message: e.to_string(),
@@ -536,8 +1332,57 @@ async fn validate_batch_requirements(
         }
     }
 
-    // Estimate output size (rough estimate: 1KB per minute of audio)
-    validation_result.estimated_output_size = validation_result.valid_files.len() as u64 * 1024;
+    if let Some(max_file_size_mb) = saved_settings.max_file_size_mb.filter(|_| !oversized.is_empty()) {
+        validation_result.warnings.push(format!(
+            "{} file(s) exceed the {} MB size limit and will be skipped: {}",
+            oversized.len(),
+            max_file_size_mb,
+            oversized.join(", ")
+        ));
+    }
+
+    if !empty.is_empty() {
+        validation_result.warnings.push(format!(
+            "{} file(s) are empty (0 bytes) and will be skipped: {}",
+            empty.len(),
+            empty.join(", ")
+        ));
+    }
+
+    // Formats Whisper can only read through FFmpeg would fail without it
+    let needs_ffmpeg: Vec<&str> = validation_result.valid_files.iter()
+        .filter(|file| models::requires_ffmpeg(&file.format))
+        .map(|file| file.name.as_str())
+        .collect();
+    if !needs_ffmpeg.is_empty() && !SystemIntegration::is_ffmpeg_available() {
+        validation_result.warnings.push(format!(
+            "FFmpeg is not installed but is required for: {}",
+            needs_ffmpeg.join(", ")
+        ));
+    }
+
+    // A missing model isn't fatal, but the first file will stall while it downloads
+    let model_size = model_size.unwrap_or(saved_settings.model_size);
+    if !SystemIntegration::check_model_available(&model_size) {
+        validation_result.warnings.push(format!(
+            "Whisper model '{}' is not downloaded yet and will be fetched before the first file is processed",
+            model_size
+        ));
+    }
+
+    // Estimate output size from the audio length in the output format
+    let valid_paths: Vec<String> = validation_result.valid_files.iter().map(|file| file.path.clone()).collect();
+    validation_result.estimated_output_size = utils::estimate_output_size(&probe_durations(&valid_paths), &saved_settings.output_format);
+    if let Ok(available) = utils::check_available_space(&output_directory) {
+        if available < validation_result.estimated_output_size {
+            validation_result.can_proceed = false;
+            validation_result.warnings.push(format!(
+                "Not enough disk space: about {} needed, {} available",
+                utils::format_file_size(validation_result.estimated_output_size),
+                utils::format_file_size(available)
+            ));
+        }
+    }
 
     // Check if we have any valid files
     if validation_result.valid_files.is_empty() {
@@ -548,81 +1393,314 @@ async fn validate_batch_requirements(
     Ok(validation_result)
 }
 
+#[tauri::command]
+fn estimate_model_memory(model_size: models::ModelSize) -> u64 {
+    SystemIntegration::estimate_model_memory(&model_size)
+}
+
+#[tauri::command]
+async fn recommend_model() -> system::ModelRecommendation {
+    tokio::task::spawn_blocking(SystemIntegration::recommend_model)
+        .await
+        .unwrap_or_else(|_| SystemIntegration::recommend_model_for(None, num_cpus::get(), false))
+}
+
+#[tauri::command]
+async fn check_model_available(model_size: models::ModelSize) -> bool {
+    SystemIntegration::check_model_available(&model_size)
+}
+
+#[tauri::command]
+async fn validate_batch_pipeline(file_paths: Vec<String>, settings: AppSettings) -> models::PipelineReadinessReport {
+    let manager = create_cli_manager(&settings);
+    SystemIntegration::check_batch_pipeline(&manager, &file_paths, &settings).await
+}
+
+// History Commands
+#[tauri::command]
+async fn add_history_entry(result: TranscriptionResult) -> AppResult<HistoryEntry> {
+    let max_entries = load_saved_settings().await.max_history_entries;
+    HistoryManager::new()?.with_max_entries(max_entries).add_entry(&result).await
+}
+
+#[tauri::command]
+async fn get_history(limit: Option<usize>, offset: Option<usize>) -> AppResult<Vec<HistoryEntry>> {
+    HistoryManager::new()?
+        .get_history(limit.unwrap_or(history::DEFAULT_HISTORY_LIMIT), offset.unwrap_or(0))
+        .await
+}
+
+/// Average speed, as a multiple of realtime, over the most recent history entries
+#[tauri::command]
+async fn get_average_realtime_factor(limit: Option<usize>) -> AppResult<Option<f64>> {
+    HistoryManager::new()?
+        .average_realtime_factor(limit.unwrap_or(history::DEFAULT_HISTORY_LIMIT))
+        .await
+}
+
+#[tauri::command]
+async fn search_history(query: String) -> AppResult<Vec<TranscriptionResult>> {
+    HistoryManager::new()?.search(&query).await
+}
+
+#[tauri::command]
+async fn delete_history_entry(id: String) -> AppResult<bool> {
+    HistoryManager::new()?.delete_entry(&id).await
+}
+
+#[tauri::command]
+async fn clear_history() -> AppResult<()> {
+    HistoryManager::new()?.clear_history().await
+}
+
+#[tauri::command]
+async fn export_batch_results(job_id: String, path: String, format: ExportFormat) -> AppResult<()> {
+    let results = {
+        let manager = BATCH_MANAGER.lock().await;
+        manager.get_results(&job_id).cloned()
+    }
+    .ok_or_else(|| error::AppError::ProcessingError(format!("Results for job {} are no longer available", job_id)))?;
+
+    export::export_results(&results, &path, format)
+}
+
+#[tauri::command]
+async fn convert_transcription(result: TranscriptionResult, target: OutputFormat, output_path: String) -> AppResult<()> {
+    export::convert_transcription(&result, target, &output_path)
+}
+
+/// A file's progress on its way to the frontend, or a request to be told once every update
+/// sent before it has been emitted
+enum ProgressUpdate {
+    File { position: usize, progress: ProcessingProgress },
+    Flush(tokio::sync::oneshot::Sender<()>),
+}
+
+/// Apply a batch's file progress to its job and emit `file-progress` and `batch-progress`,
+/// one update at a time in the order they were sent
+fn spawn_progress_forwarder(
+    app_handle: tauri::AppHandle,
+    job_id: String,
+    total_files: usize,
+) -> tokio::sync::mpsc::UnboundedSender<ProgressUpdate> {
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Some(update) = receiver.recv().await {
+            let (position, mut progress) = match update {
+                ProgressUpdate::File { position, progress } => (position, progress),
+                ProgressUpdate::Flush(flushed) => {
+                    let _ = flushed.send(());
+                    continue;
+                }
+            };
+
+            // Update job progress, then emit with the refreshed completion estimate
+            let overall_progress = {
+                let mut manager = BATCH_MANAGER.lock().await;
+                progress.estimated_completion = manager.update_job_progress(&job_id, progress.clone());
+                manager.get_job(&job_id).map(|job| job.progress)
+            };
+            let _ = app_handle.emit(events::FILE_PROGRESS, &progress);
+
+            // Keep the batch bar moving within each file
+            if let Some(overall_progress) = overall_progress {
+                let _ = app_handle.emit(events::BATCH_PROGRESS, &ProcessingProgress {
+                    progress: overall_progress,
+                    job_id: Some(job_id.clone()),
+                    file_index: Some(position),
+                    total_files: Some(total_files),
+                    ..progress
+                });
+            }
+        }
+    });
+    sender
+}
+
+/// Merge a finished batch's transcripts into one document next to its outputs, returning its path
+async fn write_combined_output(job_id: &str, results: &[TranscriptionResult], settings: &AppSettings) -> Option<String> {
+    let first = results.first()?;
+    let mut output_dir = settings.output_directory_for(&first.original_file.path);
+    if output_dir.is_empty() {
+        // The CLI picked the location, so put the combined file beside its outputs
+        output_dir = std::path::Path::new(&first.output_path)
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string())
+            .unwrap_or_default();
+    }
+
+    match export::write_combined_transcript(job_id, results, &output_dir) {
+        Ok(path) => {
+            BATCH_MANAGER.lock().await.record_output(job_id, &path);
+            Some(path)
+        }
+        Err(e) => {
+            println!("⚠️ Failed to write combined transcript for job {}: {}", job_id, e);
+            None
+        }
+    }
+}
+
 /// Process batch files with real-time progress events
 async fn process_batch_with_events(
     app_handle: tauri::AppHandle,
     job_id: String,
     file_paths: Vec<String>,
     settings: AppSettings,
+    file_settings: Vec<Option<AppSettings>>,
     cancellation_token: tokio_util::sync::CancellationToken,
 ) {
-    let cli_manager = create_cli_manager();
+    // Keep the job's full CLI output on disk so failures can be reported with it
+    let log_dir = job_log::JobLog::default_directory();
+    if let Err(e) = job_log::prune_job_logs(&log_dir, settings.job_log_retention_days, std::time::SystemTime::now()) {
+        println!("⚠️ Failed to prune old job logs: {}", e);
+    }
+    let job_log = match job_log::JobLog::create(&log_dir, &job_id) {
+        Ok(job_log) => Some(Arc::new(job_log)),
+        Err(e) => {
+            println!("⚠️ Failed to create log for job {}: {}", job_id, e);
+            None
+        }
+    };
+    let log_path = job_log.as_ref().map(|job_log| job_log.path().to_string_lossy().to_string());
+    let mut log_callback = log_stream_callback(app_handle.clone(), job_id.clone());
+    if let Some(job_log) = job_log {
+        log_callback = job_log::job_log_callback(job_log, log_callback);
+    }
+
+    let partial_app_handle = app_handle.clone();
+    let partial_job_id = job_id.clone();
+    // Taken under one lock: a guard locked inside the builder chain below would be held to the
+    // end of the statement, so locking again there would deadlock
+    let (recent_output, active_processes, job_outputs) = {
+        let manager = BATCH_MANAGER.lock().await;
+        (manager.recent_output(), manager.active_processes(), manager.job_outputs())
+    };
+    let cli_manager = create_cli_manager(&settings)
+        .with_partial_text_callback(Arc::new(move |mut partial| {
+            partial.job_id = Some(partial_job_id.clone());
+            let _ = partial_app_handle.emit(events::TRANSCRIPTION_PARTIAL, &partial);
+        }))
+        .with_log_line_callback(recording_log_callback(
+            recent_output,
+            job_id.clone(),
+            log_callback,
+        ))
+        .with_process_callback(process_tracking_callback(
+            active_processes,
+            job_id.clone(),
+        ))
+        // Recorded before each file runs, so cleanup also finds outputs of files that failed midway
+        .with_output_path_callback(output_tracking_callback(job_outputs, job_id.clone()));
     let total_files = file_paths.len();
     let mut results = Vec::new();
+    let progress_updates = spawn_progress_forwarder(app_handle.clone(), job_id.clone(), total_files);
+
+    // Files run in queue order, which may change while the batch runs
+    for position in 0..total_files {
+        let Some(index) = BATCH_MANAGER.lock().await.next_file(&job_id) else {
+            break;
+        };
+        let file_path = &file_paths[index];
 
-    for (index, file_path) in file_paths.iter().enumerate() {
         // Check for cancellation
         if cancellation_token.is_cancelled() {
-            let _ = app_handle.emit("batch-cancelled", &job_id);
+            let _ = update_dock_badge(&app_handle, DockBadgeInfo { count: None, text: None, progress: None });
+            let _ = app_handle.emit(events::BATCH_CANCELLED, &job_id);
+            disable_log_stream(&job_id);
             return;
         }
 
+        // Show the number of files still to process
+        let _ = update_dock_badge(&app_handle, DockBadgeInfo {
+            count: Some((total_files - position) as u32),
+            text: None,
+            progress: None,
+        });
+
         // Update current file progress
-        let progress = ProcessingProgress {
+        let mut progress = ProcessingProgress {
             stage: ProcessingStage::Initializing,
-            progress: (index as f64 / total_files as f64) * 100.0,
+            progress: (position as f64 / total_files as f64) * 100.0,
             current_file: Some(file_path.clone()),
             timestamp: Utc::now(),
-            message: Some(format!("Processing file {} of {}", index + 1, total_files)),
+            message: Some(format!("Processing file {} of {}", position + 1, total_files)),
             job_id: Some(job_id.clone()),
-            file_index: Some(index),
+            file_index: Some(position),
             total_files: Some(total_files),
             can_cancel: true,
+            estimated_completion: None,
         };
 
-        // Update job in manager
+        // Update job in manager; the bar reflects the duration-weighted job progress
         {
             let mut manager = BATCH_MANAGER.lock().await;
-            manager.update_job_progress(&job_id, progress.clone());
+            progress.estimated_completion = manager.update_job_progress(&job_id, progress.clone());
+            if let Some(job) = manager.get_job(&job_id) {
+                progress.progress = job.progress;
+            }
         }
 
         // Emit progress event
-        let _ = app_handle.emit("batch-progress", &progress);
-
-        // Create progress callback for individual file processing
-        let app_handle_clone = app_handle.clone();
-        let job_id_clone = job_id.clone();
-        let progress_callback: cli::ProgressCallback = Arc::new(move |file_progress| {
-            let _ = app_handle_clone.emit("file-progress", &file_progress);
-            
-            // Update job progress
-            tokio::spawn({
-                let job_id = job_id_clone.clone();
-                let progress = file_progress.clone();
-                async move {
-                    let mut manager = BATCH_MANAGER.lock().await;
-                    manager.update_job_progress(&job_id, progress);
-                }
-            });
+        let _ = app_handle.emit(events::BATCH_PROGRESS, &progress);
+
+        // Files transcribed by an earlier run are left alone
+        if let Some(output_path) = CliManager::existing_output_for(file_path, CliManager::settings_for_file(&settings, &file_settings, index)) {
+            emit_file_skipped(&app_handle, file_path, Some(&output_path));
+            continue;
+        }
+
+        // Progress is emitted by the forwarder, so updates can't overtake each other
+        let progress_sender = progress_updates.clone();
+        let progress_callback: cli::ProgressCallback = Arc::new(move |progress| {
+            let _ = progress_sender.send(ProgressUpdate::File { position, progress });
         });
 
-        // Process individual file with cancellation support
-        match cli_manager.process_file_with_cancellation(
+        // Process individual file with cancellation support; skipping it cancels only its token
+        let file_token = BATCH_MANAGER.lock().await.file_cancellation_token(&job_id, &cancellation_token);
+        let outcome = cli_manager.process_file_with_cancellation(
             file_path, 
-            &settings, 
+            CliManager::settings_for_file(&settings, &file_settings, index), 
             Some(progress_callback),
-            Some(cancellation_token.clone())
-        ).await {
+            Some(file_token)
+        ).await;
+        // Let this file's progress out before its completion or error
+        let (flushed, flush_done) = tokio::sync::oneshot::channel();
+        if progress_updates.send(ProgressUpdate::Flush(flushed)).is_ok() {
+            let _ = flush_done.await;
+        }
+        let skipped = BATCH_MANAGER.lock().await.take_skipped(&job_id, index);
+        match outcome {
+            Err(_) if skipped => emit_file_skipped(&app_handle, file_path, None),
             Ok(result) => {
-                results.push(result.clone());
-                let _ = app_handle.emit("file-completed", &result);
+                {
+                    let audio_duration = Some(result.metadata.audio_info.duration)
+                        .filter(|duration| *duration > 0.0)
+                        .or(result.original_file.duration);
+                    let mut manager = BATCH_MANAGER.lock().await;
+                    manager.record_file_completion(&job_id, index, result.processing_time, audio_duration);
+                    manager.record_output(&job_id, &result.output_path);
+                    let sidecar_path = export::metadata_sidecar_path(&result.output_path);
+                    if sidecar_path.is_file() {
+                        manager.record_output(&job_id, &sidecar_path.to_string_lossy());
+                    }
+                }
+                emit_file_completed(&app_handle, &result);
+                emit_low_confidence(
+                    &app_handle,
+                    &result,
+                    CliManager::settings_for_file(&settings, &file_settings, index).low_confidence_threshold,
+                );
+                emit_no_speech(&app_handle, &result);
+                record_in_history(&result).await;
+                results.push(result);
             }
             Err(e) => {
-                let error_event = serde_json::json!({
-                    "file_path": file_path,
-                    "error": e.to_string()
-                });
-                let _ = app_handle.emit("file-error", &error_event);
+                if matches!(e, error::AppError::Timeout(_)) {
+                    let timeout_secs = CliManager::settings_for_file(&settings, &file_settings, index).per_file_timeout_secs;
+                    emit_file_timeout(&app_handle, file_path, timeout_secs);
+                }
+                emit_file_error(&app_handle, file_path, &e, log_path.as_deref());
             }
         }
 
@@ -630,64 +1708,231 @@ async fn process_batch_with_events(
         {
             let manager = BATCH_MANAGER.lock().await;
             if manager.get_job(&job_id).is_none() {
-                let _ = app_handle.emit("batch-cancelled", &job_id);
+                let _ = update_dock_badge(&app_handle, DockBadgeInfo { count: None, text: None, progress: None });
+                let _ = app_handle.emit(events::BATCH_CANCELLED, &job_id);
+                disable_log_stream(&job_id);
                 return;
             }
         }
     }
 
     // Batch completed
+    let _ = update_dock_badge(&app_handle, DockBadgeInfo { count: None, text: None, progress: None });
+    let combined_output_path = if settings.produce_combined_output {
+        write_combined_output(&job_id, &results, &settings).await
+    } else {
+        None
+    };
     let completion_event = serde_json::json!({
         "job_id": job_id,
         "total_files": total_files,
         "successful": results.len(),
-        "results": results
+        "results": results,
+        "combined_output_path": combined_output_path
     });
-    let _ = app_handle.emit("batch-completed", &completion_event);
+    let _ = app_handle.emit(events::BATCH_COMPLETED, &completion_event);
+    disable_log_stream(&job_id);
+
+    let notification = NotificationOptions {
+        title: "Batch processing complete".to_string(),
+        body: format!("{} of {} files transcribed", results.len(), total_files),
+        sound: Some("default".to_string()),
+        identifier: Some(job_id.clone()),
+    };
+    if let Err(e) = MacOSIntegration::new().show_notification(&app_handle, notification, settings.enable_notifications) {
+        println!("Failed to show batch completion notification: {}", e);
+    }
 
-    // Remove job from manager
+    // Remove job from manager, keeping its results for export
     {
         let mut manager = BATCH_MANAGER.lock().await;
+        manager.retain_results(&job_id, results);
         manager.remove_job(&job_id);
     }
 }
 
+/// The last `lines` lines of CLI output from the most recently started job
+#[tauri::command]
+async fn get_recent_cli_output(lines: usize) -> Vec<String> {
+    BATCH_MANAGER.lock().await.recent_cli_output(lines)
+}
+
 #[tauri::command]
 async fn execute_cli_command(args: Vec<String>) -> AppResult<CliResult> {
-    let manager = create_cli_manager();
+    let manager = create_cli_manager(&load_saved_settings().await);
     let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
     manager.execute_raw_command(&args_refs).await
 }
 
+/// Start forwarding the CLI output of `job_id` as `cli-log` events
+pub fn enable_log_stream(job_id: &str) {
+    if let Ok(mut streams) = LOG_STREAMS.lock() {
+        streams.insert(job_id.to_string());
+    }
+}
+
+/// Stop forwarding the CLI output of `job_id`; returns whether it was being streamed
+pub fn disable_log_stream(job_id: &str) -> bool {
+    LOG_STREAMS.lock().map(|mut streams| streams.remove(job_id)).unwrap_or(false)
+}
+
+/// Log callback for a job's CLI manager that emits `cli-log` only while the job's stream is enabled
+pub fn log_stream_callback<R, E>(emitter: E, job_id: String) -> cli::LogLineCallback
+where
+    R: tauri::Runtime,
+    E: Emitter<R> + Send + Sync + 'static,
+{
+    Arc::new(move |mut log_line| {
+        let streaming = LOG_STREAMS.lock().map(|streams| streams.contains(&job_id)).unwrap_or(false);
+        if streaming {
+            log_line.job_id = Some(job_id.clone());
+            let _ = emitter.emit(events::CLI_LOG, &log_line);
+        }
+    })
+}
+
+/// Track `pid` in `active_processes` as transcribing `file_path` for `job_id`
+pub fn register_process(active_processes: &ActiveProcesses, job_id: &str, pid: u32, file_path: &str) {
+    if let Ok(mut active_processes) = active_processes.lock() {
+        active_processes.insert(pid, models::ProcessInfo {
+            job_id: job_id.to_string(),
+            pid,
+            file_path: file_path.to_string(),
+            started_at: Utc::now(),
+            elapsed_secs: 0.0,
+        });
+    }
+}
+
+/// Process callback that keeps `active_processes` in step with the CLI processes of `job_id`
+pub fn process_tracking_callback(active_processes: ActiveProcesses, job_id: String) -> cli::ProcessCallback {
+    Arc::new(move |pid, file_path| match file_path {
+        Some(file_path) => register_process(&active_processes, &job_id, pid, file_path),
+        None => {
+            if let Ok(mut active_processes) = active_processes.lock() {
+                active_processes.remove(&pid);
+            }
+        }
+    })
+}
+
+/// Output path callback that records each file's output, and the metadata sidecar next to it,
+/// as outputs of `job_id`
+pub fn output_tracking_callback(job_outputs: JobOutputs, job_id: String) -> cli::OutputPathCallback {
+    Arc::new(move |output_path| {
+        record_job_output(&job_outputs, &job_id, output_path);
+        record_job_output(&job_outputs, &job_id, &export::metadata_sidecar_path(output_path).to_string_lossy());
+    })
+}
+
+/// Log callback that keeps each line in `recent_output` before passing it on to `inner`
+pub fn recording_log_callback(recent_output: RecentCliOutput, job_id: String, inner: cli::LogLineCallback) -> cli::LogLineCallback {
+    Arc::new(move |log_line| {
+        record_recent_line(&recent_output, &job_id, &log_line.line);
+        inner(log_line);
+    })
+}
+
+/// Emit `file-completed` for a successfully transcribed file
+pub fn emit_file_completed<R: tauri::Runtime>(emitter: &impl Emitter<R>, result: &TranscriptionResult) {
+    let _ = emitter.emit(events::FILE_COMPLETED, result);
+}
+
+/// Emit `file-error` for a file that failed to transcribe, pointing at the job's CLI log if it has one
+pub fn emit_file_error<R: tauri::Runtime>(emitter: &impl Emitter<R>, file_path: &str, error: &error::AppError, log_path: Option<&str>) {
+    let error_event = serde_json::json!({
+        "file_path": file_path,
+        "error": error.to_string(),
+        "log_path": log_path
+    });
+    let _ = emitter.emit(events::FILE_ERROR, &error_event);
+}
+
+/// Emit `file-timeout` for a file that ran past its per-file timeout
+pub fn emit_file_timeout<R: tauri::Runtime>(emitter: &impl Emitter<R>, file_path: &str, timeout_secs: u32) {
+    let timeout_event = serde_json::json!({
+        "file_path": file_path,
+        "timeout_secs": timeout_secs
+    });
+    let _ = emitter.emit(events::FILE_TIMEOUT, &timeout_event);
+}
+
+/// Emit `low-confidence` when `result` is below `threshold`
+pub fn emit_low_confidence<R: tauri::Runtime>(emitter: &impl Emitter<R>, result: &TranscriptionResult, threshold: f64) {
+    if !result.is_low_confidence(threshold) {
+        return;
+    }
+    let low_confidence_event = serde_json::json!({
+        "file_path": result.original_file.path,
+        "output_path": result.output_path,
+        "confidence": result.confidence,
+        "threshold": threshold
+    });
+    let _ = emitter.emit(events::LOW_CONFIDENCE, &low_confidence_event);
+}
+
+/// Emit `no-speech` when `result` came back empty because the audio had no speech
+pub fn emit_no_speech<R: tauri::Runtime>(emitter: &impl Emitter<R>, result: &TranscriptionResult) {
+    if !result.no_speech_detected {
+        return;
+    }
+    let no_speech_event = serde_json::json!({
+        "file_path": result.original_file.path,
+        "output_path": result.output_path
+    });
+    let _ = emitter.emit(events::NO_SPEECH, &no_speech_event);
+}
+
+/// Emit `file-skipped` for a file whose output from an earlier run was kept, or that the user
+/// skipped (no `output_path`)
+pub fn emit_file_skipped<R: tauri::Runtime>(emitter: &impl Emitter<R>, file_path: &str, output_path: Option<&str>) {
+    let skipped_event = serde_json::json!({
+        "file_path": file_path,
+        "output_path": output_path
+    });
+    let _ = emitter.emit(events::FILE_SKIPPED, &skipped_event);
+}
+
+/// Update the dock badge on the main thread, as AppKit requires
+fn update_dock_badge(app_handle: &tauri::AppHandle, badge_info: DockBadgeInfo) -> AppResult<()> {
+    app_handle
+        .run_on_main_thread(move || {
+            if let Err(e) = MacOSIntegration::new().set_dock_badge(badge_info) {
+                println!("Failed to update dock badge: {}", e);
+            }
+        })
+        .map_err(|e| error::AppError::SystemError(format!("Failed to update dock badge: {}", e)))
+}
+
 // macOS Integration Commands
 #[tauri::command]
-async fn set_dock_badge(badge_info: DockBadgeInfo) -> AppResult<()> {
-    let integration = MacOSIntegration::new();
-    integration.set_dock_badge(badge_info)
+async fn set_dock_badge(app_handle: tauri::AppHandle, badge_info: DockBadgeInfo) -> AppResult<()> {
+    update_dock_badge(&app_handle, badge_info)
 }
 
 #[tauri::command]
-async fn clear_dock_badge() -> AppResult<()> {
-    let integration = MacOSIntegration::new();
-    integration.clear_dock_badge()
+async fn clear_dock_badge(app_handle: tauri::AppHandle) -> AppResult<()> {
+    update_dock_badge(&app_handle, DockBadgeInfo { count: None, text: None, progress: None })
 }
 
 #[tauri::command]
-async fn show_notification(options: NotificationOptions) -> AppResult<()> {
+async fn show_notification(app_handle: tauri::AppHandle, options: NotificationOptions) -> AppResult<()> {
+    let settings = SettingsManager::new()?.load_settings().await.unwrap_or_default();
     let integration = MacOSIntegration::new();
-    integration.show_notification(options)
+    integration.show_notification(&app_handle, options, settings.enable_notifications)?;
+    Ok(())
 }
 
 #[tauri::command]
-async fn set_dock_progress(progress: f64) -> AppResult<()> {
-    let integration = MacOSIntegration::new();
-    integration.set_dock_progress(progress)
+async fn set_dock_progress(window: tauri::WebviewWindow, progress: f64) -> AppResult<()> {
+    let indicator = progress_indicator::for_window(&window)?;
+    indicator.set_progress(progress)
 }
 
 #[tauri::command]
-async fn clear_dock_progress() -> AppResult<()> {
-    let integration = MacOSIntegration::new();
-    integration.clear_dock_progress()
+async fn clear_dock_progress(window: tauri::WebviewWindow) -> AppResult<()> {
+    let indicator = progress_indicator::for_window(&window)?;
+    indicator.clear_progress()
 }
 
 #[tauri::command]
@@ -739,19 +1984,32 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             get_app_version,
+            list_event_names,
             get_supported_formats,
             get_supported_formats_detailed,
+            get_supported_languages,
             validate_audio_file,
+            get_audio_metadata,
+            scan_directory_for_audio,
+            preview_output_path,
+            merge_transcriptions,
+            rename_outputs,
+            read_metadata_sidecar,
+            transcode_to_wav,
             validate_multiple_files,
             get_file_info,
             select_output_directory,
+            check_output_directory_writable,
             select_directory,
             select_files,
             save_text_file,
             save_binary_file,
             open_file_in_finder,
             clear_output_cache,
+            list_temp_files,
+            delete_temp_files,
             reveal_file_in_explorer,
+            open_output_directory,
             open_file_with_default_app,
             check_file_format_support,
             get_system_info,
@@ -761,24 +2019,59 @@ pub fn run() {
             load_settings,
             save_settings,
             update_settings_field,
+            validate_settings_field,
             reset_settings_to_defaults,
+            repair_settings,
             validate_settings,
+            get_settings_warnings,
             get_settings_config_path,
             settings_config_exists,
             export_settings_to_file,
             import_settings_from_file,
+            diff_settings,
             check_cli_availability,
             get_cli_version,
+            run_self_test,
+            get_app_health,
+            get_resolved_cli_path,
+            refresh_cli,
             process_audio_file,
+            reprocess_result,
             process_batch_files,
             start_batch_processing,
+            resume_batch,
             get_batch_progress,
+            start_log_stream,
+            stop_log_stream,
+            prepare_shutdown,
+            free_memory,
+            cleanup_job,
+            reorder_job_queue,
+            prioritize_file,
             cancel_batch_processing,
             get_active_batch_jobs,
             estimate_batch_processing_time,
+            estimate_batch_output_size,
             validate_batch_requirements,
+            validate_batch_pipeline,
+            export_batch_results,
+            convert_transcription,
+            add_history_entry,
+            get_history,
+            get_average_realtime_factor,
+            search_history,
+            delete_history_entry,
+            clear_history,
+            check_model_available,
+            estimate_model_memory,
+            recommend_model,
             execute_cli_command,
+            get_recent_cli_output,
             cancel_processing_job,
+            cancel_stale_jobs,
+            list_active_processes,
+            skip_file_in_batch,
+            force_kill_process,
             set_dock_badge,
             clear_dock_badge,
             show_notification,
@@ -800,6 +2093,399 @@ pub fn run() {
             updater::get_update_check_frequency,
             updater::set_update_check_frequency
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app_handle, event| {
+            if let tauri::RunEvent::ExitRequested { .. } = event {
+                tauri::async_runtime::block_on(shutdown(&BATCH_MANAGER, SHUTDOWN_GRACE_PERIOD));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_registered_jobs() {
+        let manager = Mutex::new(BatchProcessingManager::new());
+        let tokens: Vec<_> = (0..2).map(|_| tokio_util::sync::CancellationToken::new()).collect();
+        {
+            let mut manager = manager.lock().await;
+            for (index, token) in tokens.iter().enumerate() {
+                let job_id = format!("job-{}", index);
+                manager.add_cancellation_token(job_id.clone(), token.clone());
+                // A job that stops as soon as it is cancelled
+                let token = token.clone();
+                manager.add_job_handle(job_id, tokio::spawn(async move { token.cancelled().await }));
+            }
+        }
+
+        let cancelled = shutdown(&manager, std::time::Duration::from_secs(1)).await;
+        assert_eq!(cancelled, 2);
+        assert!(tokens.iter().all(|token| token.is_cancelled()));
+        assert!(manager.lock().await.cancel_all().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_job_removes_outputs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let outputs: Vec<String> = ["one_transcription.txt", "two_transcription.txt"]
+            .iter()
+            .map(|name| {
+                let path = temp_dir.path().join(name);
+                std::fs::write(&path, "partial").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let unrelated = temp_dir.path().join("other_transcription.txt");
+        std::fs::write(&unrelated, "keep").unwrap();
+
+        let manager = Mutex::new(BatchProcessingManager::new());
+        let token = tokio_util::sync::CancellationToken::new();
+        {
+            let mut manager = manager.lock().await;
+            manager.add_cancellation_token("failed-job".to_string(), token.clone());
+            for output in &outputs {
+                manager.record_output("failed-job", output);
+            }
+            manager.retain_results("failed-job", Vec::new());
+        }
+
+        let report = cleanup_job_artifacts(&manager, "failed-job").await.unwrap();
+        assert_eq!(report, models::CacheClearReport { files_removed: 2, bytes_freed: 14 });
+        assert!(outputs.iter().all(|output| !std::path::Path::new(output).exists()));
+        assert!(unrelated.exists());
+        assert!(token.is_cancelled());
+        assert!(manager.lock().await.get_results("failed-job").is_none());
+
+        // Cleaning up again or an unknown job is a no-op
+        let report = cleanup_job_artifacts(&manager, "failed-job").await.unwrap();
+        assert_eq!(report.files_removed, 0);
+        assert_eq!(cleanup_job_artifacts(&manager, "unknown").await.unwrap().files_removed, 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failed_file_output_is_tracked_for_cleanup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), "echo 'RuntimeError: out of memory' >&2\nexit 1");
+        let input = temp_dir.path().join("meeting.m4a");
+        std::fs::write(&input, b"audio").unwrap();
+        let output_dir = temp_dir.path().join("out");
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            ..AppSettings::default()
+        };
+
+        let mut manager = BatchProcessingManager::new();
+        let cli_manager = CliManager::new_dev()
+            .with_cli_binary_path(binary.to_string_lossy())
+            .with_output_path_callback(output_tracking_callback(manager.job_outputs(), "job".to_string()));
+        assert!(cli_manager.process_file(&input.to_string_lossy(), &settings, None).await.is_err());
+
+        let outputs = manager.cleanup_job("job");
+        assert!(outputs.contains(&output_dir.join("meeting_transcription.txt").to_string_lossy().to_string()), "{:?}", outputs);
+        assert!(manager.cleanup_job("job").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_two_file_batch_writes_combined_output() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let binary = crate::test_support::write_fake_cli(temp_dir.path(), r#"write_output "text of $stem""#);
+        let file_paths: Vec<String> = ["part1.m4a", "part2.m4a"]
+            .iter()
+            .map(|name| {
+                let path = temp_dir.path().join(name);
+                std::fs::write(&path, b"audio").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let output_dir = temp_dir.path().join("out");
+        let settings = AppSettings {
+            output_directory: output_dir.to_string_lossy().to_string(),
+            retry_count: 0,
+            produce_combined_output: true,
+            ..AppSettings::default()
+        };
+
+        let cli_manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        let results = cli_manager.process_batch(&file_paths, &settings, None).await.unwrap();
+        let path = write_combined_output("combined-test-job", &results, &settings).await.unwrap();
+
+        assert_eq!(path, output_dir.join("combined-test-job_combined.txt").to_string_lossy());
+        let combined = std::fs::read_to_string(&path).unwrap();
+        let first = combined.find("text of part1").expect(&combined);
+        let second = combined.find("text of part2").expect(&combined);
+        assert!(first < second, "{}", combined);
+    }
+
+    fn file_progress(file_index: Option<usize>, progress: f64) -> ProcessingProgress {
+        ProcessingProgress {
+            stage: ProcessingStage::Transcribing,
+            progress,
+            current_file: None,
+            timestamp: Utc::now(),
+            message: None,
+            job_id: Some("job".to_string()),
+            file_index,
+            total_files: Some(2),
+            can_cancel: true,
+            estimated_completion: None,
+        }
+    }
+
+    /// A job over `count` small files written to `dir`
+    fn test_job(dir: &std::path::Path, count: usize) -> ProcessingJob {
+        let files: Vec<AudioFile> = (0..count)
+            .map(|index| {
+                let path = dir.join(format!("file-{}.wav", index));
+                std::fs::write(&path, b"audio").unwrap();
+                utils::create_audio_file(&path.to_string_lossy()).unwrap()
+            })
+            .collect();
+
+        ProcessingJob {
+            id: "job".to_string(),
+            files,
+            current_file_index: 0,
+            progress: 0.0,
+            stage: ProcessingStage::Initializing,
+            start_time: Utc::now(),
+            estimated_completion: None,
+            is_cancelled: false,
+            can_cancel: true,
+            concurrency: 1,
+        }
+    }
+
+    #[test]
+    fn test_batch_progress_includes_current_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = BatchProcessingManager::new();
+        manager.add_job(test_job(temp_dir.path(), 2));
+
+        // A batch-level update at each file start followed by that file's own progress
+        let updates = [
+            file_progress(Some(0), 0.0),
+            file_progress(None, 25.0),
+            file_progress(None, 50.0),
+            file_progress(None, 100.0),
+            file_progress(Some(1), 50.0),
+            file_progress(None, 30.0),
+            file_progress(None, 80.0),
+            file_progress(None, 100.0),
+        ];
+        let overall: Vec<f64> = updates
+            .into_iter()
+            .map(|update| {
+                manager.update_job_progress("job", update);
+                manager.get_job("job").unwrap().progress
+            })
+            .collect();
+
+        assert_eq!(overall, vec![0.0, 12.5, 25.0, 50.0, 50.0, 65.0, 90.0, 100.0]);
+        assert!(overall.windows(2).all(|pair| pair[1] >= pair[0] && pair[1] - pair[0] <= 25.0), "{:?}", overall);
+    }
+
+    #[test]
+    fn test_batch_progress_weighted_by_duration() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = BatchProcessingManager::new();
+        let mut job = test_job(temp_dir.path(), 2);
+        job.files[0].duration = Some(100.0);
+        job.files[1].duration = Some(300.0);
+        manager.add_job(job);
+
+        // Finishing the short file covers a quarter of the batch
+        manager.next_file("job");
+        manager.update_job_progress("job", file_progress(Some(0), 0.0));
+        manager.update_job_progress("job", file_progress(None, 100.0));
+        assert_eq!(manager.get_job("job").unwrap().progress, 25.0);
+
+        // Half of the long file is worth more than all of the short one
+        manager.next_file("job");
+        manager.update_job_progress("job", file_progress(Some(1), 0.0));
+        assert_eq!(manager.get_job("job").unwrap().progress, 25.0);
+        manager.update_job_progress("job", file_progress(None, 50.0));
+        assert_eq!(manager.get_job("job").unwrap().progress, 62.5);
+    }
+
+    #[test]
+    fn test_reorder_pending_queue() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = BatchProcessingManager::new();
+        manager.add_job(test_job(temp_dir.path(), 5));
+
+        // File 0 is in flight and can't be moved
+        assert_eq!(manager.next_file("job"), Some(0));
+        assert_eq!(manager.pending_files("job"), vec![1, 2, 3, 4]);
+        assert!(manager.reorder_queue("job", vec![0, 4, 3, 2, 1]).is_err());
+        assert!(manager.reorder_queue("job", vec![4, 3, 2]).is_err());
+        assert!(manager.prioritize_file("job", 0).is_err());
+        assert!(manager.reorder_queue("unknown", vec![]).is_err());
+
+        manager.reorder_queue("job", vec![4, 3, 2, 1]).unwrap();
+        manager.prioritize_file("job", 1).unwrap();
+        assert_eq!(manager.pending_files("job"), vec![1, 4, 3, 2]);
+
+        let processed: Vec<usize> = std::iter::from_fn(|| manager.next_file("job")).collect();
+        assert_eq!(processed, vec![1, 4, 3, 2]);
+        assert!(manager.pending_files("job").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_free_memory_empties_caches() {
+        let manager = Mutex::new(BatchProcessingManager::new());
+        {
+            let mut manager = manager.lock().await;
+            manager.retain_results("finished-1", Vec::new());
+            manager.retain_results("finished-2", Vec::new());
+            manager.record_file_completion("finished-1", 0, 2.0, Some(10.0));
+        }
+        enable_log_stream("finished-1");
+
+        release_memory(&manager).await;
+
+        let manager = manager.lock().await;
+        assert!(manager.get_results("finished-1").is_none());
+        assert!(manager.get_results("finished-2").is_none());
+        assert!(manager.eta_estimators.is_empty());
+        assert!(!disable_log_stream("finished-1"));
+    }
+
+    #[test]
+    fn test_recent_cli_output_keeps_last_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = BatchProcessingManager::new();
+        assert!(manager.recent_cli_output(10).is_empty());
+
+        let job = test_job(temp_dir.path(), 1);
+        let job_id = job.id.clone();
+        manager.add_job(job);
+        let overflow = 25;
+        for i in 0..BatchProcessingManager::RECENT_OUTPUT_LINES + overflow {
+            manager.record_cli_line(&job_id, &format!("line {}", i));
+        }
+
+        let last = BatchProcessingManager::RECENT_OUTPUT_LINES + overflow - 1;
+        assert_eq!(manager.recent_cli_output(3), vec![
+            format!("line {}", last - 2),
+            format!("line {}", last - 1),
+            format!("line {}", last),
+        ]);
+        let everything = manager.recent_cli_output(usize::MAX);
+        assert_eq!(everything.len(), BatchProcessingManager::RECENT_OUTPUT_LINES);
+        assert_eq!(everything[0], format!("line {}", overflow));
+
+        // Output outlives the job, until a newer job starts
+        manager.remove_job(&job_id);
+        assert_eq!(manager.recent_cli_output(1), vec![format!("line {}", last)]);
+        manager.add_job(ProcessingJob { id: "newer".to_string(), ..test_job(temp_dir.path(), 1) });
+        assert!(manager.recent_cli_output(1).is_empty());
+    }
+
+    #[test]
+    fn test_cancel_stale_jobs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = BatchProcessingManager::new();
+        let now = Utc::now();
+
+        let stale = ProcessingJob { id: "stale".to_string(), start_time: now - chrono::Duration::hours(3), ..test_job(temp_dir.path(), 1) };
+        let fresh = ProcessingJob { id: "fresh".to_string(), start_time: now - chrono::Duration::minutes(5), ..test_job(temp_dir.path(), 1) };
+        let stale_token = tokio_util::sync::CancellationToken::new();
+        let fresh_token = tokio_util::sync::CancellationToken::new();
+        manager.add_job(stale);
+        manager.add_job(fresh);
+        manager.add_cancellation_token("stale".to_string(), stale_token.clone());
+        manager.add_cancellation_token("fresh".to_string(), fresh_token.clone());
+
+        let cancelled = manager.cancel_stale_jobs(std::time::Duration::from_secs(3600), now);
+        assert_eq!(cancelled, vec!["stale"]);
+        assert!(stale_token.is_cancelled());
+        assert!(!fresh_token.is_cancelled());
+
+        // Already cancelled jobs aren't reported again
+        assert!(manager.cancel_stale_jobs(std::time::Duration::from_secs(3600), now).is_empty());
+    }
+
+    #[test]
+    fn test_list_active_processes() {
+        let manager = BatchProcessingManager::new();
+        manager.register_process("job", 4242, "/audio/meeting.m4a");
+
+        let later = Utc::now() + chrono::Duration::seconds(5);
+        let processes = manager.list_active_processes(later);
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].job_id, "job");
+        assert_eq!(processes[0].pid, 4242);
+        assert_eq!(processes[0].file_path, "/audio/meeting.m4a");
+        assert!(processes[0].elapsed_secs >= 4.0);
+        assert!(manager.is_tracked_process(4242));
+        assert!(!manager.is_tracked_process(1));
+
+        // The callback drops a process once it exits
+        let callback = process_tracking_callback(manager.active_processes(), "job".to_string());
+        callback(4242, None);
+        assert!(manager.list_active_processes(later).is_empty());
+        assert!(!manager.is_tracked_process(4242));
+    }
+
+    #[test]
+    fn test_skip_file_in_batch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = BatchProcessingManager::new();
+        let job = test_job(temp_dir.path(), 3);
+        let paths: Vec<String> = job.files.iter().map(|file| file.path.clone()).collect();
+        manager.add_job(job);
+        let job_token = tokio_util::sync::CancellationToken::new();
+
+        assert_eq!(manager.next_file("job"), Some(0));
+        let file_token = manager.file_cancellation_token("job", &job_token);
+        manager.register_process("job", 4242, &paths[0]);
+
+        // A pending file is never handed out
+        assert_eq!(manager.skip_file("job", &paths[2]).unwrap(), SkippedFile::Pending);
+        assert_eq!(manager.pending_files("job"), vec![1]);
+
+        // The file in flight is cancelled on its own, leaving the job running
+        assert_eq!(manager.skip_file("job", &paths[0]).unwrap(), SkippedFile::Running(vec![4242]));
+        assert!(file_token.is_cancelled());
+        assert!(!job_token.is_cancelled());
+        assert!(manager.take_skipped("job", 0));
+        assert!(!manager.take_skipped("job", 0));
+
+        assert!(manager.skip_file("job", &paths[2]).is_err());
+        assert!(manager.skip_file("unknown", &paths[1]).is_err());
+
+        let processed: Vec<usize> = std::iter::from_fn(|| manager.next_file("job")).collect();
+        assert_eq!(processed, vec![1]);
+    }
+
+    #[test]
+    fn test_cancel_job_reports_progress() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = BatchProcessingManager::new();
+        manager.add_job(test_job(temp_dir.path(), 5));
+        let token = tokio_util::sync::CancellationToken::new();
+        manager.add_cancellation_token("job".to_string(), token.clone());
+
+        // Two files done, the third in progress
+        for _ in 0..2 {
+            let index = manager.next_file("job").unwrap();
+            manager.record_file_completion("job", index, 1.0, Some(10.0));
+        }
+        manager.next_file("job");
+
+        let result = manager.cancel_job_with_progress("job");
+        assert_eq!(result, models::CancellationResult { cancelled: true, completed: 2, remaining: 3 });
+        assert!(token.is_cancelled());
+
+        assert_eq!(
+            manager.cancel_job_with_progress("missing"),
+            models::CancellationResult { cancelled: false, completed: 0, remaining: 0 }
+        );
+    }
 }
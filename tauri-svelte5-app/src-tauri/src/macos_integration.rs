@@ -1,4 +1,5 @@
 use crate::error::{AppError, AppResult};
+use crate::models::SUPPORTED_FORMATS;
 use serde::{Deserialize, Serialize};
 use std::process::Command;
 
@@ -27,6 +28,28 @@ pub struct NotificationOptions {
     pub identifier: Option<String>,
 }
 
+/// Delivers notifications to the operating system
+pub trait NotificationSender {
+    fn send_notification(&self, options: &NotificationOptions) -> AppResult<()>;
+}
+
+impl<R: tauri::Runtime> NotificationSender for tauri::AppHandle<R> {
+    fn send_notification(&self, options: &NotificationOptions) -> AppResult<()> {
+        use tauri_plugin_notification::NotificationExt;
+
+        let mut builder = self.notification()
+            .builder()
+            .title(&options.title)
+            .body(&options.body);
+        if let Some(ref sound) = options.sound {
+            builder = builder.sound(sound);
+        }
+
+        builder.show()
+            .map_err(|e| AppError::SystemError(format!("Failed to show notification: {}", e)))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockBadgeInfo {
     pub count: Option<u32>,
@@ -39,16 +62,14 @@ impl MacOSIntegration {
         Self::default()
     }
 
-    /// Set dock badge with count or text
+    /// Set dock badge with count or text (must be called on the main thread)
     pub fn set_dock_badge(&self, badge_info: DockBadgeInfo) -> AppResult<()> {
         #[cfg(target_os = "macos")]
         {
-            // For now, we'll use a simple approach without direct Cocoa calls
-            // This can be enhanced later with proper macOS integration
-            if let Some(count) = badge_info.count {
-                println!("Setting dock badge count: {}", count);
-            } else if let Some(text) = badge_info.text {
-                println!("Setting dock badge text: {}", text);
+            if self.dock_badge_enabled {
+                let label = Self::badge_label(&badge_info);
+                println!("Setting dock badge: '{}'", label);
+                Self::set_dock_badge_label(&label);
             }
         }
         
@@ -61,6 +82,41 @@ impl MacOSIntegration {
         Ok(())
     }
 
+    /// Badge label for the given info; an empty label clears the badge
+    pub fn badge_label(badge_info: &DockBadgeInfo) -> String {
+        match (badge_info.count, &badge_info.text) {
+            (Some(count), _) if count > 0 => count.to_string(),
+            (Some(_), _) => String::new(),
+            (None, Some(text)) => text.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Set `NSApp.dockTile.badgeLabel`
+    #[cfg(target_os = "macos")]
+    fn set_dock_badge_label(label: &str) {
+        use cocoa::appkit::NSApp;
+        use cocoa::base::{id, nil};
+        use cocoa::foundation::NSString;
+        use objc::{msg_send, sel, sel_impl};
+
+        unsafe {
+            let app: id = NSApp();
+            if app == nil {
+                return;
+            }
+
+            let dock_tile: id = msg_send![app, dockTile];
+            if dock_tile == nil {
+                return;
+            }
+
+            let ns_label: id = NSString::alloc(nil).init_str(label);
+            let _: () = msg_send![dock_tile, setBadgeLabel: ns_label];
+            let _: () = msg_send![ns_label, release];
+        }
+    }
+
     /// Clear dock badge
     pub fn clear_dock_badge(&self) -> AppResult<()> {
         self.set_dock_badge(DockBadgeInfo {
@@ -70,13 +126,21 @@ impl MacOSIntegration {
         })
     }
 
-    /// Show system notification
-    pub fn show_notification(&self, options: NotificationOptions) -> AppResult<()> {
-        // Use tauri-plugin-notification for cross-platform notifications
+    /// Show system notification, returning whether it was actually sent
+    pub fn show_notification<S: NotificationSender>(
+        &self,
+        sender: &S,
+        options: NotificationOptions,
+        settings_enabled: bool,
+    ) -> AppResult<bool> {
+        if !self.notifications_enabled || !settings_enabled {
+            println!("Notification skipped (disabled): {}", options.title);
+            return Ok(false);
+        }
+
         println!("Notification: {} - {}", options.title, options.body);
-        
-        // This will be handled by the frontend using the tauri notification plugin
-        Ok(())
+        sender.send_notification(&options)?;
+        Ok(true)
     }
 
     /// Register file associations (requires app restart to take effect)
@@ -98,10 +162,10 @@ impl MacOSIntegration {
     pub fn verify_file_associations(&self) -> AppResult<()> {
         #[cfg(target_os = "macos")]
         {
-            let supported_extensions = vec!["m4a", "wav", "mp3", "aac", "flac"];
+            let supported_extensions = SUPPORTED_FORMATS;
             let bundle_id = "com.yang-gyunam.speech-to-text";
             
-            for ext in supported_extensions {
+            for &ext in supported_extensions {
                 let output = Command::new("duti")
                     .args(&["-x", ext])
                     .output();
@@ -129,9 +193,9 @@ impl MacOSIntegration {
         #[cfg(target_os = "macos")]
         {
             let bundle_id = "com.yang-gyunam.speech-to-text";
-            let supported_extensions = vec!["m4a", "wav", "mp3", "aac", "flac"];
+            let supported_extensions = SUPPORTED_FORMATS;
             
-            for ext in supported_extensions {
+            for &ext in supported_extensions {
                 let _output = Command::new("duti")
                     .args(&["-s", bundle_id, ext, "editor"])
                     .output();
@@ -145,13 +209,13 @@ impl MacOSIntegration {
     /// Get current file association status
     pub fn get_file_association_status(&self) -> AppResult<Vec<FileAssociationStatus>> {
         let mut status_list = Vec::new();
-        let supported_extensions = vec!["m4a", "wav", "mp3", "aac", "flac"];
+        let supported_extensions = SUPPORTED_FORMATS;
         
         #[cfg(target_os = "macos")]
         {
             let bundle_id = "com.yang-gyunam.speech-to-text";
             
-            for ext in supported_extensions {
+            for &ext in supported_extensions {
                 let output = Command::new("duti")
                     .args(&["-x", ext])
                     .output();
@@ -174,7 +238,7 @@ impl MacOSIntegration {
         
         #[cfg(not(target_os = "macos"))]
         {
-            for ext in supported_extensions {
+            for &ext in supported_extensions {
                 status_list.push(FileAssociationStatus {
                     extension: ext.to_string(),
                     is_associated: false,
@@ -251,3 +315,73 @@ pub struct FileAssociationStatus {
     pub current_handler: Option<String>,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingSender {
+        sent: AtomicUsize,
+    }
+
+    impl NotificationSender for CountingSender {
+        fn send_notification(&self, _options: &NotificationOptions) -> AppResult<()> {
+            self.sent.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn test_options() -> NotificationOptions {
+        NotificationOptions {
+            title: "Batch complete".to_string(),
+            body: "3 files transcribed".to_string(),
+            sound: None,
+            identifier: None,
+        }
+    }
+
+    #[test]
+    fn test_notification_skipped_when_disabled() {
+        let sender = CountingSender::default();
+
+        let mut integration = MacOSIntegration::new();
+        assert!(!integration.show_notification(&sender, test_options(), false).unwrap());
+
+        integration.notifications_enabled = false;
+        assert!(!integration.show_notification(&sender, test_options(), true).unwrap());
+
+        assert_eq!(sender.sent.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_badge_label() {
+        let badge = |count: Option<u32>, text: Option<&str>| DockBadgeInfo {
+            count,
+            text: text.map(|t| t.to_string()),
+            progress: None,
+        };
+
+        assert_eq!(MacOSIntegration::badge_label(&badge(Some(3), None)), "3");
+        assert_eq!(MacOSIntegration::badge_label(&badge(Some(0), None)), "");
+        assert_eq!(MacOSIntegration::badge_label(&badge(None, Some("!"))), "!");
+        assert_eq!(MacOSIntegration::badge_label(&badge(None, None)), "");
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_set_dock_badge_smoke() {
+        let integration = MacOSIntegration::new();
+        assert!(integration.set_dock_badge(DockBadgeInfo { count: Some(2), text: None, progress: None }).is_ok());
+        assert!(integration.clear_dock_badge().is_ok());
+    }
+
+    #[test]
+    fn test_notification_sent_when_enabled() {
+        let sender = CountingSender::default();
+        let integration = MacOSIntegration::new();
+
+        assert!(integration.show_notification(&sender, test_options(), true).unwrap());
+        assert_eq!(sender.sent.load(Ordering::SeqCst), 1);
+    }
+}
@@ -7,17 +7,90 @@ pub struct AppSettings {
     pub language: String,
     pub model_size: ModelSize,
     pub output_directory: String,
+    /// How `output_directory` is applied to each input file
+    #[serde(default)]
+    pub output_directory_mode: OutputDirectoryMode,
     pub include_metadata: bool,
     pub auto_save: bool,
     pub theme: Theme,
     // Advanced performance options
+    /// CLI processes allowed at once (0 = auto, based on the CPU count)
     pub max_concurrent_jobs: u32,
+    /// Seconds a single CLI run may take (0 = no limit)
+    #[serde(default = "default_processing_timeout_secs")]
+    pub processing_timeout_secs: u32,
+    /// Seconds one CLI run of a file may take, not counting the wait for a free slot (0 = no limit)
+    #[serde(default)]
+    pub per_file_timeout_secs: u32,
+    /// Largest input file accepted, in megabytes (None = no limit)
+    #[serde(default)]
+    pub max_file_size_mb: Option<u32>,
     pub enable_gpu_acceleration: bool,
     pub memory_optimization: bool,
     // Advanced processing options
     pub enable_voice_activity_detection: bool,
     pub noise_reduction: bool,
     pub output_format: OutputFormat,
+    /// Extra attempts after a failed CLI run (0 disables retrying)
+    #[serde(default = "default_retry_count")]
+    pub retry_count: u32,
+    /// Command run after each successful transcription with the output path as its argument
+    #[serde(default)]
+    pub post_process_command: Option<String>,
+    /// Speech-to-text CLI to run instead of the bundled sidecar or development lookup
+    #[serde(default)]
+    pub cli_binary_path: Option<String>,
+    /// Convert formats only FFmpeg can decode to WAV before transcribing. Formats the CLI
+    /// can't read at all are converted either way
+    #[serde(default)]
+    pub auto_transcode: bool,
+    /// Release cached results before each batch. Every file already runs in a fresh CLI
+    /// process, so no model stays loaded between batches either way
+    #[serde(default)]
+    pub unload_model_when_idle: bool,
+    /// Skip files whose transcription output already exists and is newer than the file
+    #[serde(default)]
+    pub skip_existing_output: bool,
+    /// Text encoding for saved transcriptions
+    #[serde(default)]
+    pub output_encoding: OutputEncoding,
+    /// Output file name without extension, using `{name}`, `{date}`, `{time}`, `{lang}` and
+    /// `{model}` (empty = `{name}_transcription`)
+    #[serde(default)]
+    pub output_filename_template: String,
+    /// Results with an average confidence (0.0-1.0) below this get a warning (0 disables)
+    #[serde(default = "default_low_confidence_threshold")]
+    pub low_confidence_threshold: f64,
+    /// Remove a file's outputs from earlier runs in the CLI working directory before transcribing it
+    #[serde(default = "default_true")]
+    pub clean_workdir_before_run: bool,
+    /// Ask the CLI to label segments by speaker, when it supports `--diarize`
+    #[serde(default)]
+    pub enable_diarization: bool,
+    /// Collapse stray whitespace and blank lines in the transcribed text
+    #[serde(default)]
+    pub clean_output_text: bool,
+    /// After a batch, also write every transcript, in order, into `{job_id}_combined.txt`
+    #[serde(default)]
+    pub produce_combined_output: bool,
+    /// Text the model is primed with, e.g. the topic or how names are spelled
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+    /// Names and terms added to the initial prompt
+    #[serde(default)]
+    pub custom_vocabulary: Vec<String>,
+    /// Sampling temperature for decoding (0.0-1.0, 0 = always pick the most likely text)
+    #[serde(default)]
+    pub temperature: f32,
+    /// Candidates kept during beam search decoding (1-10)
+    #[serde(default = "default_beam_size")]
+    pub beam_size: u32,
+    /// History entries kept before the oldest are dropped (0 = unlimited)
+    #[serde(default = "default_max_history_entries")]
+    pub max_history_entries: u32,
+    /// Days a job's CLI log is kept before it is deleted (0 = keep forever)
+    #[serde(default = "default_job_log_retention_days")]
+    pub job_log_retention_days: u32,
     // UI preferences
     pub compact_mode: bool,
     pub show_advanced_options: bool,
@@ -35,17 +108,40 @@ impl Default for AppSettings {
                 .join("Documents")
                 .to_string_lossy()
                 .to_string(),
+            output_directory_mode: OutputDirectoryMode::Absolute,
             include_metadata: true,
             auto_save: true,
             theme: Theme::System,
             // Advanced performance options
             max_concurrent_jobs: 2,
+            processing_timeout_secs: default_processing_timeout_secs(),
+            per_file_timeout_secs: 0,
+            max_file_size_mb: None,
             enable_gpu_acceleration: false,
             memory_optimization: true,
             // Advanced processing options
             enable_voice_activity_detection: true,
             noise_reduction: false,
             output_format: OutputFormat::Txt,
+            retry_count: default_retry_count(),
+            post_process_command: None,
+            cli_binary_path: None,
+            auto_transcode: false,
+            unload_model_when_idle: false,
+            skip_existing_output: false,
+            output_encoding: OutputEncoding::Utf8,
+            output_filename_template: String::new(),
+            low_confidence_threshold: default_low_confidence_threshold(),
+            clean_workdir_before_run: true,
+            enable_diarization: false,
+            clean_output_text: false,
+            produce_combined_output: false,
+            initial_prompt: None,
+            custom_vocabulary: Vec::new(),
+            temperature: 0.0,
+            beam_size: default_beam_size(),
+            max_history_entries: default_max_history_entries(),
+            job_log_retention_days: default_job_log_retention_days(),
             // UI preferences
             compact_mode: false,
             show_advanced_options: false,
@@ -55,6 +151,107 @@ impl Default for AppSettings {
     }
 }
 
+/// Upper bound for the automatic concurrency level
+pub const MAX_AUTO_CONCURRENT_JOBS: usize = 4;
+
+impl AppSettings {
+    /// Concurrency level to use for this machine, resolving auto mode
+    pub fn resolved_concurrent_jobs(&self) -> usize {
+        resolve_concurrent_jobs(self.max_concurrent_jobs, self.memory_optimization, num_cpus::get())
+    }
+
+    /// Directory the output for `input_path` goes to ("" keeps the CLI's default)
+    pub fn output_directory_for(&self, input_path: &str) -> String {
+        let output_directory = self.output_directory.trim();
+        match self.output_directory_mode {
+            OutputDirectoryMode::Absolute => output_directory.to_string(),
+            OutputDirectoryMode::SameAsInput => std::path::Path::new(input_path.trim())
+                .parent()
+                .map(|parent| parent.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            OutputDirectoryMode::RelativeTo(ref base) => std::path::Path::new(base.trim())
+                .join(output_directory)
+                .to_string_lossy()
+                .to_string(),
+        }
+    }
+
+    /// These settings with the output directory resolved for `input_path`
+    pub fn resolved_for_file(&self, input_path: &str) -> AppSettings {
+        AppSettings {
+            output_directory: self.output_directory_for(input_path),
+            output_directory_mode: OutputDirectoryMode::Absolute,
+            ..self.clone()
+        }
+    }
+}
+
+/// Resolve `max_concurrent_jobs` for `cpu_count` cores. Auto mode uses one job per core
+/// up to `MAX_AUTO_CONCURRENT_JOBS`, halved when memory optimization is on
+pub fn resolve_concurrent_jobs(max_concurrent_jobs: u32, memory_optimization: bool, cpu_count: usize) -> usize {
+    if max_concurrent_jobs > 0 {
+        return max_concurrent_jobs as usize;
+    }
+
+    let jobs = cpu_count.min(MAX_AUTO_CONCURRENT_JOBS);
+    let jobs = if memory_optimization { jobs / 2 } else { jobs };
+    jobs.max(1)
+}
+
+fn default_processing_timeout_secs() -> u32 {
+    3600
+}
+
+fn default_retry_count() -> u32 {
+    1
+}
+
+/// Beam size Whisper itself uses by default
+pub const DEFAULT_BEAM_SIZE: u32 = 5;
+
+fn default_beam_size() -> u32 {
+    DEFAULT_BEAM_SIZE
+}
+
+fn default_max_history_entries() -> u32 {
+    crate::history::MAX_HISTORY_ENTRIES as u32
+}
+
+fn default_job_log_retention_days() -> u32 {
+    14
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_low_confidence_threshold() -> f64 {
+    0.6
+}
+
+/// Where transcriptions are written
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputDirectoryMode {
+    /// `output_directory` as an absolute path
+    #[default]
+    Absolute,
+    /// Next to each input file
+    SameAsInput,
+    /// `output_directory` relative to the given base directory
+    RelativeTo(String),
+}
+
+/// Encoding for transcription text files
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputEncoding {
+    #[default]
+    Utf8,
+    /// UTF-8 with a byte order mark, which some Windows tools need to detect Korean text
+    Utf8Bom,
+}
+
 /// Whisper model sizes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -78,6 +275,34 @@ impl std::fmt::Display for ModelSize {
     }
 }
 
+impl ModelSize {
+    /// Rough processing seconds per second of audio on a typical machine
+    pub fn realtime_factor(&self) -> f64 {
+        match self {
+            ModelSize::Tiny => 0.1,
+            ModelSize::Base => 0.2,
+            ModelSize::Small => 0.4,
+            ModelSize::Medium => 0.8,
+            ModelSize::Large => 1.5,
+        }
+    }
+}
+
+impl std::str::FromStr for ModelSize {
+    type Err = crate::error::AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "tiny" => Ok(ModelSize::Tiny),
+            "base" => Ok(ModelSize::Base),
+            "small" => Ok(ModelSize::Small),
+            "medium" => Ok(ModelSize::Medium),
+            "large" => Ok(ModelSize::Large),
+            other => Err(crate::error::AppError::ConfigError(format!("Unknown model size: {}", other))),
+        }
+    }
+}
+
 /// UI theme options
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -87,6 +312,40 @@ pub enum Theme {
     System,
 }
 
+/// Files removed by an output cache cleanup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CacheClearReport {
+    pub files_removed: usize,
+    pub bytes_freed: u64,
+}
+
+/// A batch restarted for the files an interrupted batch didn't finish
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResumedBatch {
+    /// None when every file was already finished and no batch was started
+    pub job_id: Option<String>,
+    pub skipped_files: usize,
+}
+
+/// Outcome of cancelling a batch, with how far it had got
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CancellationResult {
+    pub cancelled: bool,
+    /// Files transcribed before the cancellation
+    pub completed: usize,
+    /// Files still waiting or in progress when cancelled
+    pub remaining: usize,
+}
+
+/// A setting that differs between two settings objects
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsDiff {
+    /// Field name as it appears in the settings file
+    pub field: String,
+    pub old_value: serde_json::Value,
+    pub new_value: serde_json::Value,
+}
+
 /// Output format options
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -97,6 +356,31 @@ pub enum OutputFormat {
     Json,
 }
 
+impl OutputFormat {
+    /// Every output format
+    pub const ALL: [OutputFormat; 4] = [OutputFormat::Txt, OutputFormat::Srt, OutputFormat::Vtt, OutputFormat::Json];
+
+    /// File extension written for this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Txt => "txt",
+            OutputFormat::Srt => "srt",
+            OutputFormat::Vtt => "vtt",
+            OutputFormat::Json => "json",
+        }
+    }
+
+    /// Rough output bytes per minute of speech; subtitles repeat timecodes and JSON adds segment metadata
+    pub fn estimated_bytes_per_minute(&self) -> u64 {
+        match self {
+            OutputFormat::Txt => 150,
+            OutputFormat::Srt => 400,
+            OutputFormat::Vtt => 380,
+            OutputFormat::Json => 1000,
+        }
+    }
+}
+
 /// Audio file information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioFile {
@@ -107,6 +391,10 @@ pub struct AudioFile {
     pub format: String,
     pub duration: Option<f64>,
     pub status: FileStatus,
+    /// SHA-256 of the file contents, used to find cached results. Only computed when the
+    /// transcription cache is consulted
+    #[serde(default)]
+    pub hash: Option<String>,
 }
 
 /// File processing status
@@ -129,6 +417,86 @@ pub struct TranscriptionResult {
     pub output_path: String,
     pub processing_time: f64,
     pub confidence: Option<f64>,
+    #[serde(default)]
+    pub segments: Option<Vec<Segment>>,
+    /// Non-fatal problems, such as a failed post-processing command
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// The transcript is empty because Whisper heard no speech, not because the run failed
+    #[serde(default)]
+    pub no_speech_detected: bool,
+    /// Seconds of audio transcribed per second of processing, when both are known
+    #[serde(default)]
+    pub realtime_factor: Option<f64>,
+}
+
+impl TranscriptionResult {
+    /// Whether the confidence is known and below `threshold`
+    pub fn is_low_confidence(&self, threshold: f64) -> bool {
+        self.confidence.is_some_and(|confidence| confidence < threshold)
+    }
+
+    /// Warn that the transcription may be unreliable if its confidence is below `threshold`
+    pub fn flag_low_confidence(&mut self, threshold: f64) -> bool {
+        let Some(confidence) = self.confidence.filter(|_| self.is_low_confidence(threshold)) else {
+            return false;
+        };
+        self.warnings.push(format!(
+            "Low confidence ({:.0}%): the transcription may be unreliable",
+            confidence * 100.0
+        ));
+        true
+    }
+}
+
+/// Timestamped transcription segment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    /// Speaker label from diarization, when the CLI provides one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
+/// Segment text streamed while a file is still being transcribed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialTranscription {
+    pub file_path: String,
+    pub job_id: Option<String>,
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// Output stream a CLI log line was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CliLogStream {
+    Stdout,
+    Stderr,
+}
+
+/// A CLI process started by a job, for diagnostics and force-kill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub job_id: String,
+    pub pid: u32,
+    pub file_path: String,
+    pub started_at: DateTime<Utc>,
+    /// Seconds since the process started, as of the listing
+    pub elapsed_secs: f64,
+}
+
+/// A line of CLI output forwarded to the diagnostics panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliLogLine {
+    pub job_id: Option<String>,
+    pub file_path: String,
+    pub stream: CliLogStream,
+    pub line: String,
+    pub timestamp: DateTime<Utc>,
 }
 
 /// Transcription metadata
@@ -160,6 +528,9 @@ pub struct ProcessingJob {
     pub estimated_completion: Option<DateTime<Utc>>,
     pub is_cancelled: bool,
     pub can_cancel: bool,
+    /// Files transcribed at once, after resolving auto mode
+    #[serde(default)]
+    pub concurrency: usize,
 }
 
 /// Processing stages
@@ -167,6 +538,7 @@ pub struct ProcessingJob {
 #[serde(rename_all = "snake_case")]
 pub enum ProcessingStage {
     Initializing,
+    Downloading,
     LoadingModel,
     Preprocessing,
     Transcribing,
@@ -179,6 +551,7 @@ impl std::fmt::Display for ProcessingStage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ProcessingStage::Initializing => write!(f, "Initializing"),
+            ProcessingStage::Downloading => write!(f, "Downloading Model"),
             ProcessingStage::LoadingModel => write!(f, "Loading Model"),
             ProcessingStage::Preprocessing => write!(f, "Preprocessing"),
             ProcessingStage::Transcribing => write!(f, "Transcribing"),
@@ -201,6 +574,8 @@ pub struct ProcessingProgress {
     pub file_index: Option<usize>,
     pub total_files: Option<usize>,
     pub can_cancel: bool,
+    #[serde(default)]
+    pub estimated_completion: Option<DateTime<Utc>>,
 }
 
 /// Batch processing statistics
@@ -228,6 +603,9 @@ pub struct ProcessingError {
     pub file_path: String,
     pub error_message: String,
     pub timestamp: DateTime<Utc>,
+    /// The job's CLI log, for attaching to bug reports
+    #[serde(default)]
+    pub log_path: Option<String>,
 }
 
 /// Batch validation result
@@ -241,6 +619,29 @@ pub struct BatchValidationResult {
     pub warnings: Vec<String>,
 }
 
+/// Dry-run readiness of a batch, checked without transcribing anything
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineReadinessReport {
+    pub files: Vec<FileReadiness>,
+    pub cli_available: bool,
+    pub model_available: bool,
+    pub ffmpeg_available: bool,
+    pub output_directory_writable: bool,
+    pub can_proceed: bool,
+    /// Problems that would stop every file from being processed
+    pub issues: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Readiness of a single file; environment problems are reported on the batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileReadiness {
+    pub file_path: String,
+    pub ready: bool,
+    pub output_path: Option<String>,
+    pub issues: Vec<String>,
+}
+
 /// File validation error
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileValidationError {
@@ -249,13 +650,65 @@ pub struct FileValidationError {
 }
 
 /// Supported audio formats
-pub const SUPPORTED_FORMATS: &[&str] = &["m4a", "wav", "mp3", "aac", "flac"];
+pub const SUPPORTED_FORMATS: &[&str] = &["m4a", "wav", "mp3", "aac", "flac", "ogg", "opus", "wma"];
+
+/// Supported formats that can only be decoded through FFmpeg
+pub const FFMPEG_ONLY_FORMATS: &[&str] = &["mp3", "aac", "ogg", "opus", "wma"];
+
+/// Formats the speech-to-text CLI reads itself; other supported formats are converted to WAV first
+pub const CLI_INPUT_FORMATS: &[&str] = &["m4a", "wav", "mp3", "aac", "flac"];
 
 /// Check if a file extension is supported
 pub fn is_supported_format(extension: &str) -> bool {
     SUPPORTED_FORMATS.contains(&extension.to_lowercase().as_str())
 }
 
+/// Check if a file extension can only be decoded with FFmpeg installed
+pub fn requires_ffmpeg(extension: &str) -> bool {
+    let extension = extension.to_lowercase();
+    FFMPEG_ONLY_FORMATS.contains(&extension.trim_start_matches('.'))
+}
+
+/// Language codes accepted by Whisper with the English names it prints for them, plus `auto`
+/// for automatic detection
+pub const SUPPORTED_LANGUAGES: &[(&str, &str)] = &[
+    ("auto", "auto"), ("en", "english"), ("zh", "chinese"), ("de", "german"), ("es", "spanish"),
+    ("ru", "russian"), ("ko", "korean"), ("fr", "french"), ("ja", "japanese"),
+    ("pt", "portuguese"), ("tr", "turkish"), ("pl", "polish"), ("ca", "catalan"), ("nl", "dutch"),
+    ("ar", "arabic"), ("sv", "swedish"), ("it", "italian"), ("id", "indonesian"), ("hi", "hindi"),
+    ("fi", "finnish"), ("vi", "vietnamese"), ("he", "hebrew"), ("uk", "ukrainian"),
+    ("el", "greek"), ("ms", "malay"), ("cs", "czech"), ("ro", "romanian"), ("da", "danish"),
+    ("hu", "hungarian"), ("ta", "tamil"), ("no", "norwegian"), ("th", "thai"), ("ur", "urdu"),
+    ("hr", "croatian"), ("bg", "bulgarian"), ("lt", "lithuanian"), ("la", "latin"),
+    ("mi", "maori"), ("ml", "malayalam"), ("cy", "welsh"), ("sk", "slovak"), ("te", "telugu"),
+    ("fa", "persian"), ("lv", "latvian"), ("bn", "bengali"), ("sr", "serbian"),
+    ("az", "azerbaijani"), ("sl", "slovenian"), ("kn", "kannada"), ("et", "estonian"),
+    ("mk", "macedonian"), ("br", "breton"), ("eu", "basque"), ("is", "icelandic"),
+    ("hy", "armenian"), ("ne", "nepali"), ("mn", "mongolian"), ("bs", "bosnian"), ("kk", "kazakh"),
+    ("sq", "albanian"), ("sw", "swahili"), ("gl", "galician"), ("mr", "marathi"),
+    ("pa", "punjabi"), ("si", "sinhala"), ("km", "khmer"), ("sn", "shona"), ("yo", "yoruba"),
+    ("so", "somali"), ("af", "afrikaans"), ("oc", "occitan"), ("ka", "georgian"),
+    ("be", "belarusian"), ("tg", "tajik"), ("sd", "sindhi"), ("gu", "gujarati"), ("am", "amharic"),
+    ("yi", "yiddish"), ("lo", "lao"), ("uz", "uzbek"), ("fo", "faroese"), ("ht", "haitian creole"),
+    ("ps", "pashto"), ("tk", "turkmen"), ("nn", "nynorsk"), ("mt", "maltese"), ("sa", "sanskrit"),
+    ("lb", "luxembourgish"), ("my", "myanmar"), ("bo", "tibetan"), ("tl", "tagalog"),
+    ("mg", "malagasy"), ("as", "assamese"), ("tt", "tatar"), ("haw", "hawaiian"),
+    ("ln", "lingala"), ("ha", "hausa"), ("ba", "bashkir"), ("jw", "javanese"), ("su", "sundanese"),
+    ("yue", "cantonese"),
+];
+
+/// Check if a language code is supported
+pub fn is_supported_language(language: &str) -> bool {
+    let language = language.trim().to_lowercase();
+    SUPPORTED_LANGUAGES.iter().any(|(code, _)| *code == language)
+}
+
+/// The code of a supported language from its English name (e.g. "Korean" -> "ko")
+pub fn language_code_for_name(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_lowercase();
+    SUPPORTED_LANGUAGES.iter().find(|(_, language_name)| *language_name == name).map(|(code, _)| *code)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,6 +722,52 @@ mod tests {
         assert!(settings.auto_save);
     }
 
+    #[test]
+    fn test_resolve_concurrent_jobs() {
+        // Explicit values are used as-is
+        assert_eq!(resolve_concurrent_jobs(3, true, 16), 3);
+
+        // Auto follows the core count up to the cap
+        assert_eq!(resolve_concurrent_jobs(0, false, 2), 2);
+        assert_eq!(resolve_concurrent_jobs(0, false, 16), MAX_AUTO_CONCURRENT_JOBS);
+
+        // Memory optimization halves auto, never below one job
+        assert_eq!(resolve_concurrent_jobs(0, true, 16), 2);
+        assert_eq!(resolve_concurrent_jobs(0, true, 6), 2);
+        assert_eq!(resolve_concurrent_jobs(0, true, 1), 1);
+
+        let settings = AppSettings { max_concurrent_jobs: 0, ..AppSettings::default() };
+        assert!((1..=MAX_AUTO_CONCURRENT_JOBS).contains(&settings.resolved_concurrent_jobs()));
+    }
+
+    #[test]
+    fn test_output_directory_for_each_mode() {
+        let input = "/recordings/2024/interview.m4a";
+        let absolute = AppSettings { output_directory: "/transcripts".to_string(), ..AppSettings::default() };
+        assert_eq!(absolute.output_directory_for(input), "/transcripts");
+
+        let beside_input = AppSettings { output_directory_mode: OutputDirectoryMode::SameAsInput, ..absolute.clone() };
+        assert_eq!(beside_input.output_directory_for(input), "/recordings/2024");
+
+        let relative = AppSettings {
+            output_directory: "transcripts/raw".to_string(),
+            output_directory_mode: OutputDirectoryMode::RelativeTo("/projects/podcast".to_string()),
+            ..AppSettings::default()
+        };
+        assert_eq!(relative.output_directory_for(input), "/projects/podcast/transcripts/raw");
+
+        let resolved = relative.resolved_for_file(input);
+        assert_eq!(resolved.output_directory, "/projects/podcast/transcripts/raw");
+        assert_eq!(resolved.output_directory_mode, OutputDirectoryMode::Absolute);
+
+        // Serialized as snake_case, with the base for relative mode
+        assert_eq!(serde_json::to_value(&beside_input.output_directory_mode).unwrap(), "same_as_input");
+        assert_eq!(
+            serde_json::to_value(&relative.output_directory_mode).unwrap(),
+            serde_json::json!({ "relative_to": "/projects/podcast" })
+        );
+    }
+
     #[test]
     fn test_model_size_display() {
         assert_eq!(ModelSize::Base.to_string(), "base");
@@ -282,6 +781,15 @@ mod tests {
         assert!(!is_supported_format("txt"));
     }
 
+    #[test]
+    fn test_supported_languages() {
+        assert!(is_supported_language("ko"));
+        assert!(is_supported_language("EN"));
+        assert!(is_supported_language("auto"));
+        assert!(!is_supported_language("english"));
+        assert!(!is_supported_language("xx"));
+    }
+
     #[test]
     fn test_processing_stage_display() {
         assert_eq!(ProcessingStage::LoadingModel.to_string(), "Loading Model");
@@ -295,4 +803,26 @@ mod tests {
         let deserialized: AppSettings = serde_json::from_str(&serialized).unwrap();
         assert_eq!(settings.language, deserialized.language);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_flag_low_confidence() {
+        let result = |confidence: Option<f64>| TranscriptionResult {
+            confidence,
+            ..crate::test_support::sample_result("/audio/meeting.m4a")
+        };
+        let threshold = AppSettings::default().low_confidence_threshold;
+
+        let mut low = result(Some(0.42));
+        assert!(low.flag_low_confidence(threshold));
+        assert_eq!(low.warnings, vec!["Low confidence (42%): the transcription may be unreliable"]);
+
+        let mut high = result(Some(0.93));
+        assert!(!high.flag_low_confidence(threshold));
+        assert!(high.warnings.is_empty());
+
+        // Unknown confidence is never flagged
+        let mut unknown = result(None);
+        assert!(!unknown.flag_low_confidence(threshold));
+        assert!(unknown.warnings.is_empty());
+    }
+}
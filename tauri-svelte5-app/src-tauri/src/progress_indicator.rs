@@ -0,0 +1,193 @@
+use crate::error::AppResult;
+#[cfg(target_os = "windows")]
+use crate::error::AppError;
+
+/// Platform progress indicator (macOS dock, Windows taskbar)
+pub trait ProgressIndicator {
+    /// Show progress between 0.0 and 1.0; values outside the range are clamped
+    fn set_progress(&self, progress: f64) -> AppResult<()>;
+
+    /// Remove the progress indicator
+    fn clear_progress(&self) -> AppResult<()>;
+}
+
+/// Clamp a progress value to the 0.0-1.0 range
+pub fn clamp_progress(progress: f64) -> f64 {
+    if progress.is_nan() {
+        0.0
+    } else {
+        progress.clamp(0.0, 1.0)
+    }
+}
+
+/// Dock progress on macOS
+#[cfg(target_os = "macos")]
+#[derive(Default)]
+pub struct DockProgressIndicator {
+    integration: crate::macos_integration::MacOSIntegration,
+}
+
+#[cfg(target_os = "macos")]
+impl DockProgressIndicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl ProgressIndicator for DockProgressIndicator {
+    fn set_progress(&self, progress: f64) -> AppResult<()> {
+        self.integration.set_dock_progress(clamp_progress(progress))
+    }
+
+    fn clear_progress(&self) -> AppResult<()> {
+        self.integration.clear_dock_progress()
+    }
+}
+
+/// Taskbar progress on Windows via ITaskbarList3
+#[cfg(target_os = "windows")]
+pub struct TaskbarProgressIndicator {
+    // Raw window handle; kept as an integer so the indicator can cross threads
+    hwnd: isize,
+}
+
+#[cfg(target_os = "windows")]
+impl TaskbarProgressIndicator {
+    /// Scale used for SetProgressValue
+    const PROGRESS_TOTAL: u64 = 1000;
+
+    pub fn new(hwnd: isize) -> Self {
+        Self { hwnd }
+    }
+
+    fn hwnd(&self) -> windows::Win32::Foundation::HWND {
+        windows::Win32::Foundation::HWND(self.hwnd as *mut std::ffi::c_void)
+    }
+
+    fn taskbar() -> AppResult<windows::Win32::UI::Shell::ITaskbarList3> {
+        use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED};
+        use windows::Win32::UI::Shell::{ITaskbarList3, TaskbarList};
+
+        unsafe {
+            // Already-initialized COM on this thread is fine, so the result is ignored
+            let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+            let taskbar: ITaskbarList3 = CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER)
+                .map_err(|e| AppError::SystemError(format!("Failed to create taskbar list: {}", e)))?;
+            taskbar.HrInit()
+                .map_err(|e| AppError::SystemError(format!("Failed to initialize taskbar list: {}", e)))?;
+
+            Ok(taskbar)
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl ProgressIndicator for TaskbarProgressIndicator {
+    fn set_progress(&self, progress: f64) -> AppResult<()> {
+        use windows::Win32::UI::Shell::TBPF_NORMAL;
+
+        let completed = (clamp_progress(progress) * Self::PROGRESS_TOTAL as f64).round() as u64;
+        let taskbar = Self::taskbar()?;
+
+        unsafe {
+            taskbar.SetProgressState(self.hwnd(), TBPF_NORMAL)
+                .map_err(|e| AppError::SystemError(format!("Failed to set taskbar progress state: {}", e)))?;
+            taskbar.SetProgressValue(self.hwnd(), completed, Self::PROGRESS_TOTAL)
+                .map_err(|e| AppError::SystemError(format!("Failed to set taskbar progress: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    fn clear_progress(&self) -> AppResult<()> {
+        use windows::Win32::UI::Shell::TBPF_NOPROGRESS;
+
+        let taskbar = Self::taskbar()?;
+
+        unsafe {
+            taskbar.SetProgressState(self.hwnd(), TBPF_NOPROGRESS)
+                .map_err(|e| AppError::SystemError(format!("Failed to clear taskbar progress: {}", e)))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// No-op indicator for platforms without a native progress display
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub struct NoopProgressIndicator;
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+impl ProgressIndicator for NoopProgressIndicator {
+    fn set_progress(&self, progress: f64) -> AppResult<()> {
+        let _ = progress;
+        Ok(())
+    }
+
+    fn clear_progress(&self) -> AppResult<()> {
+        Ok(())
+    }
+}
+
+/// Get the progress indicator for the current platform
+pub fn for_window(window: &tauri::WebviewWindow) -> AppResult<Box<dyn ProgressIndicator>> {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = window;
+        Ok(Box::new(DockProgressIndicator::new()))
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let hwnd = window.hwnd()
+            .map_err(|e| AppError::SystemError(format!("Failed to get window handle: {}", e)))?;
+        Ok(Box::new(TaskbarProgressIndicator::new(hwnd.0 as isize)))
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = window;
+        Ok(Box::new(NoopProgressIndicator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_progress() {
+        assert_eq!(clamp_progress(-0.5), 0.0);
+        assert_eq!(clamp_progress(0.25), 0.25);
+        assert_eq!(clamp_progress(1.5), 1.0);
+        assert_eq!(clamp_progress(f64::NAN), 0.0);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_platform_dispatch() {
+        let indicator: Box<dyn ProgressIndicator> = Box::new(DockProgressIndicator::new());
+        assert!(indicator.set_progress(1.5).is_ok());
+        assert!(indicator.clear_progress().is_ok());
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_platform_dispatch() {
+        // A null window handle can't show progress, but dispatch must still compile
+        let indicator: Box<dyn ProgressIndicator> = Box::new(TaskbarProgressIndicator::new(0));
+        let _ = indicator.set_progress(0.5);
+        let _ = indicator.clear_progress();
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[test]
+    fn test_platform_dispatch() {
+        let indicator: Box<dyn ProgressIndicator> = Box::new(NoopProgressIndicator);
+        assert!(indicator.set_progress(-1.0).is_ok());
+        assert!(indicator.set_progress(2.0).is_ok());
+        assert!(indicator.clear_progress().is_ok());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::{AppError, AppResult};
-use crate::models::{AppSettings, ModelSize, Theme};
+use crate::models::{is_supported_language, AppSettings, ModelSize, OutputDirectoryMode, OutputFormat, SettingsDiff, Theme, SUPPORTED_LANGUAGES};
+use crate::system::{ComputeDevice, SystemIntegration};
 use serde_json;
 use std::path::PathBuf;
 use tokio::fs;
@@ -7,6 +8,7 @@ use tokio::fs;
 /// Settings manager for handling configuration persistence and validation
 pub struct SettingsManager {
     config_path: PathBuf,
+    auto_repair: bool,
 }
 
 impl SettingsManager {
@@ -15,12 +17,18 @@ impl SettingsManager {
         let config_dir = Self::get_config_directory()?;
         let config_path = config_dir.join("settings.json");
         
-        Ok(Self { config_path })
+        Ok(Self { config_path, auto_repair: false })
     }
 
     /// Create a settings manager with a custom config path (useful for testing)
     pub fn with_config_path(config_path: PathBuf) -> Self {
-        Self { config_path }
+        Self { config_path, auto_repair: false }
+    }
+
+    /// Repair a corrupt settings file on load instead of failing
+    pub fn with_auto_repair(mut self, auto_repair: bool) -> Self {
+        self.auto_repair = auto_repair;
+        self
     }
 
     /// Get the application configuration directory
@@ -45,15 +53,94 @@ impl SettingsManager {
         let content = fs::read_to_string(&self.config_path).await
             .map_err(|e| AppError::ConfigError(format!("Failed to read config file: {}", e)))?;
 
-        let settings: AppSettings = serde_json::from_str(&content)
+        match self.parse_settings(&content) {
+            Err(e) if self.auto_repair => {
+                println!("⚠️ {}; repairing settings file", e);
+                self.repair_settings().await
+            }
+            result => result,
+        }
+    }
+
+    /// Parse and validate the contents of a settings file
+    fn parse_settings(&self, content: &str) -> AppResult<AppSettings> {
+        let settings: AppSettings = serde_json::from_str(content)
             .map_err(|e| AppError::ConfigError(format!("Failed to parse config file: {}", e)))?;
 
-        // Validate the loaded settings
         self.validate_settings(&settings)?;
+        Ok(settings)
+    }
+
+    /// Recover from a corrupt settings file: back it up to `settings.json.bak`, then save
+    /// defaults with every field that still parses and validates carried over
+    pub async fn repair_settings(&self) -> AppResult<AppSettings> {
+        if !self.config_path.exists() {
+            return self.reset_to_defaults().await;
+        }
+
+        let content = fs::read_to_string(&self.config_path).await
+            .map_err(|e| AppError::ConfigError(format!("Failed to read config file: {}", e)))?;
+        if let Ok(settings) = self.parse_settings(&content) {
+            return Ok(settings);
+        }
+
+        fs::copy(&self.config_path, self.get_backup_path()).await
+            .map_err(|e| AppError::ConfigError(format!("Failed to back up config file: {}", e)))?;
 
+        let settings = self.salvage_settings(&content);
+        self.save_settings(&settings).await?;
+        println!("🔧 Repaired settings file; the original was saved to {}", self.get_backup_path().display());
         Ok(settings)
     }
 
+    /// Where `repair_settings` keeps the file it replaced
+    pub fn get_backup_path(&self) -> PathBuf {
+        let mut path = self.config_path.clone().into_os_string();
+        path.push(".bak");
+        PathBuf::from(path)
+    }
+
+    /// Defaults with each field of `content` applied, skipping fields that break parsing or validation
+    fn salvage_settings(&self, content: &str) -> AppSettings {
+        let Ok(mut merged) = serde_json::to_value(AppSettings::default()) else {
+            return AppSettings::default();
+        };
+
+        for (key, value) in Self::salvageable_fields(content) {
+            if merged.get(&key).is_none() {
+                continue;
+            }
+
+            let mut candidate = merged.clone();
+            candidate[&key] = value;
+            let valid = serde_json::from_value::<AppSettings>(candidate.clone())
+                .is_ok_and(|settings| self.validate_settings(&settings).is_ok());
+            if valid {
+                merged = candidate;
+            }
+        }
+
+        serde_json::from_value(merged).unwrap_or_default()
+    }
+
+    /// Top-level fields of a settings file, read line by line when the file no longer parses
+    fn salvageable_fields(content: &str) -> Vec<(String, serde_json::Value)> {
+        if let Ok(serde_json::Value::Object(fields)) = serde_json::from_str(content) {
+            return fields.into_iter().collect();
+        }
+
+        // Settings are saved pretty-printed, so each field sits on its own line
+        let field_regex = regex::Regex::new(r#"^\s*"(\w+)"\s*:\s*(.+?),?\s*$"#).unwrap();
+        content
+            .lines()
+            .filter_map(|line| {
+                let captures = field_regex.captures(line)?;
+                let value = serde_json::from_str(&captures[2]).ok()?;
+                Some((captures[1].to_string(), value))
+            })
+            .collect()
+    }
+
     /// Save settings to the configuration file
     pub async fn save_settings(&self, settings: &AppSettings) -> AppResult<()> {
         // Validate settings before saving
@@ -71,18 +158,35 @@ impl SettingsManager {
         fs::write(&self.config_path, content).await
             .map_err(|e| AppError::ConfigError(format!("Failed to write config file: {}", e)))?;
 
+        for warning in settings_warnings(settings) {
+            println!("⚠️ {}", warning);
+        }
+
         Ok(())
     }
 
     /// Validate settings for correctness and consistency
     pub fn validate_settings(&self, settings: &AppSettings) -> AppResult<()> {
-        // Validate language code (basic check for non-empty string)
-        if settings.language.trim().is_empty() {
-            return Err(AppError::ConfigError("Language cannot be empty".to_string()));
+        // Validate language code against the languages Whisper understands
+        SettingsValidator::validate_language_code(&settings.language)?;
+
+        // Only an absolute output directory is known up front; the other modes resolve per file
+        match settings.output_directory_mode {
+            OutputDirectoryMode::Absolute => self.validate_output_directory(&settings.output_directory)?,
+            OutputDirectoryMode::SameAsInput => {}
+            OutputDirectoryMode::RelativeTo(ref base) => SettingsValidator::validate_directory_path(base)?,
         }
 
-        // Validate output directory exists or can be created
-        self.validate_output_directory(&settings.output_directory)?;
+        SettingsValidator::validate_low_confidence_threshold(settings.low_confidence_threshold)?;
+        SettingsValidator::validate_temperature(settings.temperature)?;
+        SettingsValidator::validate_beam_size(settings.beam_size)?;
+
+        // A custom CLI binary must point at an executable file
+        if let Some(ref cli_binary_path) = settings.cli_binary_path {
+            if !cli_binary_path.trim().is_empty() {
+                SettingsValidator::validate_cli_binary_path(cli_binary_path)?;
+            }
+        }
 
         Ok(())
     }
@@ -173,6 +277,141 @@ impl Default for SettingsManager {
     }
 }
 
+/// Fields that differ from `old` to `new`, in alphabetical order
+pub fn diff_settings(old: &AppSettings, new: &AppSettings) -> Vec<SettingsDiff> {
+    let to_fields = |settings: &AppSettings| match serde_json::to_value(settings) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    };
+    let old_fields = to_fields(old);
+    let new_fields = to_fields(new);
+
+    old_fields
+        .into_iter()
+        .filter_map(|(field, old_value)| {
+            let new_value = new_fields.get(&field).cloned().unwrap_or(serde_json::Value::Null);
+            (old_value != new_value).then_some(SettingsDiff { field, old_value, new_value })
+        })
+        .collect()
+}
+
+/// Settings a running job reads when it starts, so changing them mid-run mixes results
+const PROCESSING_FIELDS: &[&str] = &[
+    "language",
+    "model_size",
+    "output_format",
+    "temperature",
+    "beam_size",
+    "initial_prompt",
+    "custom_vocabulary",
+];
+
+/// Problems with `settings` that don't stop them from working. Unlike validation this probes the
+/// system for free memory and compute devices, so call it once per save rather than per check.
+pub fn settings_warnings(settings: &AppSettings) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    // The CLI working directory is searched for outputs and cleared, which would pick up or
+    // delete transcriptions saved there
+    warnings.extend(SettingsValidator::output_in_work_directory_warning(settings, &crate::utils::work_directory()));
+
+    // A model larger than free memory still loads, but swaps heavily or gets killed
+    warnings.extend(SettingsValidator::model_memory_warning(settings, SystemIntegration::get_available_memory()));
+
+    // GPU acceleration without a GPU still works, just on the CPU
+    if settings.enable_gpu_acceleration {
+        let devices = SystemIntegration::detect_compute_devices();
+        warnings.extend(SettingsValidator::gpu_acceleration_warning(settings, &devices));
+    }
+
+    warnings
+}
+
+/// Whether going from `old` to `new` changes a setting that running jobs would need
+/// restarting to pick up
+pub fn settings_changed_requires_restart(old: &AppSettings, new: &AppSettings) -> bool {
+    diff_settings(old, new)
+        .iter()
+        .any(|diff| PROCESSING_FIELDS.contains(&diff.field.as_str()))
+}
+
+/// Validate a single settings field before it is saved, e.g. as the user edits it.
+/// Unlike `validate_settings` this never creates the output directory.
+pub fn validate_settings_field(field: &str, value: &serde_json::Value) -> AppResult<()> {
+    let invalid = |expected: &str| AppError::ConfigError(format!("Invalid value for '{}': expected {}, got {}", field, expected, value));
+
+    match field {
+        "language" => SettingsValidator::validate_language_code(value.as_str().ok_or_else(|| invalid("a string"))?),
+        "model_size" => {
+            let model_size: ModelSize = serde_json::from_value(value.clone()).map_err(|_| invalid("a model size"))?;
+            SettingsValidator::validate_model_size(&model_size)
+        }
+        "theme" => {
+            let theme: Theme = serde_json::from_value(value.clone()).map_err(|_| invalid("a theme"))?;
+            SettingsValidator::validate_theme(&theme)
+        }
+        "output_directory" => {
+            let dir = value.as_str().ok_or_else(|| invalid("a string"))?;
+            // Empty means the default location
+            if dir.trim().is_empty() {
+                return Ok(());
+            }
+            SettingsValidator::validate_directory_path(dir)?;
+            let path = PathBuf::from(dir);
+            if path.exists() && !path.is_dir() {
+                return Err(AppError::ConfigError(format!("Output path '{}' exists but is not a directory", dir)));
+            }
+            Ok(())
+        }
+        "low_confidence_threshold" => {
+            SettingsValidator::validate_low_confidence_threshold(value.as_f64().ok_or_else(|| invalid("a number"))?)
+        }
+        "temperature" => SettingsValidator::validate_temperature(value.as_f64().ok_or_else(|| invalid("a number"))? as f32),
+        "beam_size" => {
+            let beam_size = value.as_u64().ok_or_else(|| invalid("a whole number"))?;
+            SettingsValidator::validate_beam_size(u32::try_from(beam_size).unwrap_or(u32::MAX))
+        }
+        "output_format" => serde_json::from_value::<OutputFormat>(value.clone()).map(|_| ()).map_err(|_| invalid("an output format")),
+        "initial_prompt" => match value {
+            serde_json::Value::Null | serde_json::Value::String(_) => Ok(()),
+            _ => Err(invalid("a string or null")),
+        },
+        "include_metadata" | "auto_save" => value.as_bool().map(|_| ()).ok_or_else(|| invalid("true or false")),
+        _ => Err(AppError::ConfigError(format!("Unknown settings field '{}'", field))),
+    }
+}
+
+/// Set one field of `settings` from a JSON value, as sent by the settings UI. Ranges are left to
+/// `validate_settings` when the settings are saved.
+pub fn apply_settings_field(settings: &mut AppSettings, field: &str, value: &serde_json::Value) -> AppResult<()> {
+    let invalid = |expected: &str| AppError::ConfigError(format!("Invalid value for '{}': expected {}, got {}", field, expected, value));
+
+    match field {
+        "language" => settings.language = value.as_str().ok_or_else(|| invalid("a string"))?.to_string(),
+        "model_size" => settings.model_size = serde_json::from_value(value.clone()).map_err(|_| invalid("a model size"))?,
+        "output_directory" => settings.output_directory = value.as_str().ok_or_else(|| invalid("a string"))?.to_string(),
+        "include_metadata" => settings.include_metadata = value.as_bool().ok_or_else(|| invalid("true or false"))?,
+        "auto_save" => settings.auto_save = value.as_bool().ok_or_else(|| invalid("true or false"))?,
+        "theme" => settings.theme = serde_json::from_value(value.clone()).map_err(|_| invalid("a theme"))?,
+        "output_format" => settings.output_format = serde_json::from_value(value.clone()).map_err(|_| invalid("an output format"))?,
+        "temperature" => settings.temperature = value.as_f64().ok_or_else(|| invalid("a number"))? as f32,
+        "beam_size" => {
+            let beam_size = value.as_u64().ok_or_else(|| invalid("a whole number"))?;
+            settings.beam_size = u32::try_from(beam_size).map_err(|_| invalid("a whole number"))?;
+        }
+        // Null or blank clears the prompt
+        "initial_prompt" => {
+            settings.initial_prompt = match value {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(prompt) => Some(prompt.clone()).filter(|prompt| !prompt.trim().is_empty()),
+                _ => return Err(invalid("a string or null")),
+            }
+        }
+        _ => return Err(AppError::ConfigError(format!("Unknown settings field '{}'", field))),
+    }
+    Ok(())
+}
+
 /// Settings validation utilities
 pub struct SettingsValidator;
 
@@ -183,20 +422,132 @@ impl SettingsValidator {
             return Err(AppError::ConfigError("Language code cannot be empty".to_string()));
         }
 
-        // Basic validation for common language codes (2-5 characters)
-        if language.len() < 2 || language.len() > 5 {
-            return Err(AppError::ConfigError(
-                "Language code should be 2-5 characters long".to_string()
-            ));
+        if !is_supported_language(language) {
+            return Err(AppError::ConfigError(format!(
+                "Unsupported language code '{}'. Supported codes: {}",
+                language,
+                SUPPORTED_LANGUAGES.iter().map(|(code, _)| *code).collect::<Vec<_>>().join(", ")
+            )));
         }
 
         Ok(())
     }
 
+    /// Validate that a custom CLI binary exists and is executable
+    pub fn validate_cli_binary_path(path: &str) -> AppResult<()> {
+        let binary = PathBuf::from(path.trim());
+
+        if !binary.exists() {
+            return Err(AppError::ConfigError(format!("CLI binary not found at '{}'", path)));
+        }
+
+        if !binary.is_file() {
+            return Err(AppError::ConfigError(format!("CLI binary path '{}' is not a file", path)));
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = std::fs::metadata(&binary)
+                .map_err(|e| AppError::ConfigError(format!("Cannot read CLI binary '{}': {}", path, e)))?
+                .permissions()
+                .mode();
+            if mode & 0o111 == 0 {
+                return Err(AppError::ConfigError(format!("CLI binary '{}' is not executable", path)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Warning for GPU acceleration enabled when `devices` has no usable GPU
+    pub fn gpu_acceleration_warning(settings: &AppSettings, devices: &[ComputeDevice]) -> Option<String> {
+        (settings.enable_gpu_acceleration && !SystemIntegration::has_gpu(devices)).then(|| {
+            "GPU acceleration is enabled but no GPU was detected; transcription will run on the CPU".to_string()
+        })
+    }
+
+    /// Validate the confidence below which a transcription is flagged
+    pub fn validate_low_confidence_threshold(threshold: f64) -> AppResult<()> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(AppError::ConfigError(format!(
+                "Low confidence threshold must be between 0 and 1, got {}",
+                threshold
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate the decoding temperature
+    pub fn validate_temperature(temperature: f32) -> AppResult<()> {
+        if !(0.0..=1.0).contains(&temperature) {
+            return Err(AppError::ConfigError(format!(
+                "Temperature must be between 0.0 and 1.0, got {}",
+                temperature
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate the decoding beam size
+    pub fn validate_beam_size(beam_size: u32) -> AppResult<()> {
+        if !(1..=10).contains(&beam_size) {
+            return Err(AppError::ConfigError(format!(
+                "Beam size must be between 1 and 10, got {}",
+                beam_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Warning for an output directory that resolves to `work_dir` or somewhere under it
+    pub fn output_in_work_directory_warning(settings: &AppSettings, work_dir: &std::path::Path) -> Option<String> {
+        let output_dir = match settings.output_directory_mode {
+            OutputDirectoryMode::Absolute => PathBuf::from(settings.output_directory.trim()),
+            OutputDirectoryMode::RelativeTo(ref base) => PathBuf::from(base.trim()).join(settings.output_directory.trim()),
+            OutputDirectoryMode::SameAsInput => return None,
+        };
+        if output_dir.as_os_str().is_empty() {
+            return None;
+        }
+
+        // Resolve symlinks through the deepest existing ancestor, since the directory may not exist yet
+        let resolve = |path: &std::path::Path| {
+            path.ancestors()
+                .find_map(|ancestor| {
+                    let canonical = ancestor.canonicalize().ok()?;
+                    Some(canonical.join(path.strip_prefix(ancestor).ok()?))
+                })
+                .unwrap_or_else(|| path.to_path_buf())
+        };
+        resolve(&output_dir).starts_with(resolve(work_dir)).then(|| {
+            format!(
+                "Output directory '{}' is inside the CLI working directory '{}'; transcriptions saved there may be picked up by other runs or removed when the cache is cleared",
+                output_dir.display(),
+                work_dir.display()
+            )
+        })
+    }
+
+    /// Warning for a model whose estimated footprint exceeds `available_memory` bytes
+    pub fn model_memory_warning(settings: &AppSettings, available_memory: Option<u64>) -> Option<String> {
+        const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+        let required = SystemIntegration::estimate_model_memory(&settings.model_size);
+        available_memory.filter(|available| required > *available).map(|available| {
+            format!(
+                "The {} model needs about {:.1} GB of memory but only {:.1} GB is available; consider a smaller model",
+                settings.model_size,
+                required as f64 / GB,
+                available as f64 / GB
+            )
+        })
+    }
+
     /// Validate model size
     pub fn validate_model_size(_model_size: &ModelSize) -> AppResult<()> {
-        // All enum variants are valid, but we could add additional checks here
-        // For example, checking if the model is available on the system
+        // All enum variants are valid. A model that isn't downloaded yet is still valid,
+        // since the CLI fetches it on first use; see SystemIntegration::check_model_available
         Ok(())
     }
 
@@ -229,6 +580,7 @@ impl SettingsValidator {
 mod tests {
     use super::*;
     use tempfile::TempDir;
+    use serde_json::json;
 
     fn create_test_settings_manager() -> (SettingsManager, TempDir) {
         let temp_dir = TempDir::new().unwrap();
@@ -292,11 +644,157 @@ mod tests {
     fn test_validate_language_code() {
         assert!(SettingsValidator::validate_language_code("en").is_ok());
         assert!(SettingsValidator::validate_language_code("ko").is_ok());
-        assert!(SettingsValidator::validate_language_code("zh-CN").is_ok());
+        assert!(SettingsValidator::validate_language_code("auto").is_ok());
         
         assert!(SettingsValidator::validate_language_code("").is_err());
         assert!(SettingsValidator::validate_language_code("x").is_err());
         assert!(SettingsValidator::validate_language_code("toolong").is_err());
+        assert!(SettingsValidator::validate_language_code("english").is_err());
+        assert!(SettingsValidator::validate_language_code("xx").is_err());
+        assert!(SettingsValidator::validate_language_code("zh-CN").is_err());
+    }
+
+    #[test]
+    fn test_validate_settings_rejects_unknown_language() {
+        let (manager, _temp_dir) = create_test_settings_manager();
+        
+        let mut settings = AppSettings::default();
+        settings.language = "kr".to_string();
+        
+        let error = manager.validate_settings(&settings).unwrap_err();
+        assert!(error.to_string().contains("Supported codes"));
+    }
+
+    #[test]
+    fn test_validate_settings_rejects_missing_cli_binary() {
+        let (manager, temp_dir) = create_test_settings_manager();
+
+        let mut settings = AppSettings::default();
+        settings.cli_binary_path = Some(temp_dir.path().join("missing-cli").to_string_lossy().to_string());
+
+        let error = manager.validate_settings(&settings).unwrap_err();
+        assert!(error.to_string().contains("CLI binary not found"));
+
+        settings.cli_binary_path = Some(String::new());
+        assert!(manager.validate_settings(&settings).is_ok());
+    }
+
+    #[test]
+    fn test_validate_settings_decoding_ranges() {
+        let (manager, _temp_dir) = create_test_settings_manager();
+        let settings = AppSettings::default();
+
+        for temperature in [0.0, 0.4, 1.0] {
+            assert!(manager.validate_settings(&AppSettings { temperature, ..settings.clone() }).is_ok());
+        }
+        for temperature in [-0.1, 1.5, f32::NAN] {
+            let error = manager.validate_settings(&AppSettings { temperature, ..settings.clone() }).unwrap_err();
+            assert!(error.to_string().contains("Temperature"));
+        }
+
+        for beam_size in [1, 10] {
+            assert!(manager.validate_settings(&AppSettings { beam_size, ..settings.clone() }).is_ok());
+        }
+        for beam_size in [0, 11] {
+            let error = manager.validate_settings(&AppSettings { beam_size, ..settings.clone() }).unwrap_err();
+            assert!(error.to_string().contains("Beam size"));
+        }
+    }
+
+    #[test]
+    fn test_gpu_acceleration_warning() {
+        use crate::system::ComputeDeviceKind;
+
+        let cpu_only = SystemIntegration::detect_compute_devices_with("linux", &|_: &str, _: &[&str]| None);
+        let with_cuda = SystemIntegration::detect_compute_devices_with("linux", &|_: &str, _: &[&str]| {
+            Some("NVIDIA GeForce RTX 3060".to_string())
+        });
+        assert_eq!(with_cuda[1].kind, ComputeDeviceKind::Cuda);
+
+        let mut settings = AppSettings::default();
+        assert!(SettingsValidator::gpu_acceleration_warning(&settings, &cpu_only).is_none());
+
+        settings.enable_gpu_acceleration = true;
+        let warning = SettingsValidator::gpu_acceleration_warning(&settings, &cpu_only).unwrap();
+        assert!(warning.contains("no GPU was detected"));
+        assert!(SettingsValidator::gpu_acceleration_warning(&settings, &with_cuda).is_none());
+    }
+
+    #[test]
+    fn test_output_in_work_directory_warning() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let work_dir = temp_dir.path().join("SpeechToText");
+        std::fs::create_dir(&work_dir).unwrap();
+        let settings_for = |dir: PathBuf| AppSettings { output_directory: dir.to_string_lossy().to_string(), ..AppSettings::default() };
+
+        let inside = settings_for(work_dir.join("output"));
+        let warning = SettingsValidator::output_in_work_directory_warning(&inside, &work_dir).unwrap();
+        assert!(warning.contains("inside the CLI working directory"), "{}", warning);
+        assert!(SettingsValidator::output_in_work_directory_warning(&settings_for(work_dir.clone()), &work_dir).is_some());
+
+        // Relative mode resolves against its base
+        let relative = AppSettings {
+            output_directory: "transcripts".to_string(),
+            output_directory_mode: OutputDirectoryMode::RelativeTo(work_dir.to_string_lossy().to_string()),
+            ..AppSettings::default()
+        };
+        assert!(SettingsValidator::output_in_work_directory_warning(&relative, &work_dir).is_some());
+
+        // A sibling sharing the name prefix is outside
+        let sibling = settings_for(temp_dir.path().join("SpeechToTextOutput"));
+        assert!(SettingsValidator::output_in_work_directory_warning(&sibling, &work_dir).is_none());
+        let beside_input = AppSettings { output_directory_mode: OutputDirectoryMode::SameAsInput, ..inside };
+        assert!(SettingsValidator::output_in_work_directory_warning(&beside_input, &work_dir).is_none());
+    }
+
+    #[test]
+    fn test_model_memory_warning() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        let settings = AppSettings { model_size: ModelSize::Large, ..AppSettings::default() };
+
+        let warning = SettingsValidator::model_memory_warning(&settings, Some(4 * GB)).unwrap();
+        assert!(warning.contains("large model needs about 10.0 GB"));
+        assert!(warning.contains("4.0 GB is available"));
+        assert!(SettingsValidator::model_memory_warning(&settings, Some(16 * GB)).is_none());
+        assert!(SettingsValidator::model_memory_warning(&settings, None).is_none());
+
+        let tiny = AppSettings { model_size: ModelSize::Tiny, ..settings };
+        assert!(SettingsValidator::model_memory_warning(&tiny, Some(4 * GB)).is_none());
+    }
+
+    #[test]
+    fn test_settings_warnings_collects_each_check() {
+        let inside = AppSettings {
+            output_directory: crate::utils::work_directory().join("output").to_string_lossy().to_string(),
+            model_size: ModelSize::Tiny,
+            enable_gpu_acceleration: false,
+            ..AppSettings::default()
+        };
+        let warnings = settings_warnings(&inside);
+        assert!(warnings.iter().any(|warning| warning.contains("inside the CLI working directory")), "{:?}", warnings);
+
+        let beside_input = AppSettings { output_directory_mode: OutputDirectoryMode::SameAsInput, ..inside };
+        assert!(settings_warnings(&beside_input).is_empty());
+    }
+
+    #[test]
+    fn test_validate_output_directory_modes() {
+        let (manager, temp_dir) = create_test_settings_manager();
+        let relative = AppSettings { output_directory: "transcripts".to_string(), ..AppSettings::default() };
+
+        // A relative directory needs a base
+        let beside_input = AppSettings { output_directory_mode: OutputDirectoryMode::SameAsInput, ..relative.clone() };
+        assert!(manager.validate_settings(&beside_input).is_ok());
+        let with_base = AppSettings {
+            output_directory_mode: OutputDirectoryMode::RelativeTo(temp_dir.path().to_string_lossy().to_string()),
+            ..relative.clone()
+        };
+        assert!(manager.validate_settings(&with_base).is_ok());
+        let relative_base = AppSettings {
+            output_directory_mode: OutputDirectoryMode::RelativeTo("projects".to_string()),
+            ..relative
+        };
+        assert!(manager.validate_settings(&relative_base).is_err());
     }
 
     #[test]
@@ -338,9 +836,133 @@ mod tests {
         assert_eq!(imported_settings.language, "es");
     }
 
+    #[tokio::test]
+    async fn test_repair_truncated_settings() {
+        let (manager, temp_dir) = create_test_settings_manager();
+        let output_dir = temp_dir.path().join("transcripts");
+
+        let settings = AppSettings {
+            language: "en".to_string(),
+            model_size: ModelSize::Small,
+            output_directory: output_dir.to_string_lossy().to_string(),
+            ..AppSettings::default()
+        };
+        manager.save_settings(&settings).await.unwrap();
+
+        // Cut the file off mid-way and break a field that comes before the cut
+        let content = std::fs::read_to_string(manager.get_config_path()).unwrap();
+        let truncated = &content[..content.find("\"theme\"").unwrap() + 10];
+        let corrupt = truncated.replace("\"auto_save\": true", "\"auto_save\": \"sometimes\"");
+        std::fs::write(manager.get_config_path(), &corrupt).unwrap();
+        assert!(manager.load_settings().await.is_err());
+
+        let repaired = manager.repair_settings().await.unwrap();
+        assert_eq!(repaired.language, "en");
+        assert!(matches!(repaired.model_size, ModelSize::Small));
+        assert_eq!(repaired.output_directory, output_dir.to_string_lossy());
+        assert!(repaired.auto_save);
+
+        // The corrupt original is kept and the repaired file loads again
+        assert_eq!(std::fs::read_to_string(manager.get_backup_path()).unwrap(), corrupt);
+        assert_eq!(manager.load_settings().await.unwrap().language, "en");
+    }
+
+    #[tokio::test]
+    async fn test_load_settings_auto_repair() {
+        let (manager, _temp_dir) = create_test_settings_manager();
+        std::fs::write(manager.get_config_path(), "{ not json at all").unwrap();
+        assert!(manager.load_settings().await.is_err());
+
+        // An unsupported language parses but fails validation, so it's dropped
+        let manager = manager.with_auto_repair(true);
+        std::fs::write(manager.get_config_path(), r#"{"language": "klingon", "compact_mode": true}"#).unwrap();
+        let repaired = manager.load_settings().await.unwrap();
+        assert_eq!(repaired.language, AppSettings::default().language);
+        assert!(repaired.compact_mode);
+        assert!(manager.get_backup_path().exists());
+    }
+
     #[test]
     fn test_config_exists() {
         let (manager, _temp_dir) = create_test_settings_manager();
         assert!(!manager.config_exists());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_settings_changed_requires_restart() {
+        let old = AppSettings::default();
+        assert!(!settings_changed_requires_restart(&old, &old));
+
+        let new_model = AppSettings { model_size: ModelSize::Large, ..old.clone() };
+        assert!(settings_changed_requires_restart(&old, &new_model));
+        let new_beam_size = AppSettings { beam_size: 8, ..old.clone() };
+        assert!(settings_changed_requires_restart(&old, &new_beam_size));
+
+        let new_theme = AppSettings { theme: Theme::Dark, ..old.clone() };
+        assert!(!settings_changed_requires_restart(&old, &new_theme));
+    }
+
+    #[test]
+    fn test_validate_settings_field() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("file.txt");
+        std::fs::write(&file, "").unwrap();
+
+        let cases = [
+            ("language", json!("en"), json!("xx")),
+            ("model_size", json!("large"), json!("huge")),
+            ("theme", json!("dark"), json!(3)),
+            ("output_directory", json!(temp_dir.path().join("new")), json!(file)),
+            ("output_directory", json!(""), json!("relative/dir")),
+            ("low_confidence_threshold", json!(0.5), json!(1.5)),
+            ("temperature", json!(0.2), json!(-0.1)),
+            ("beam_size", json!(5), json!(0)),
+            ("beam_size", json!(10), json!(2.5)),
+            ("auto_save", json!(false), json!("yes")),
+            ("output_format", json!("vtt"), json!("docx")),
+            ("initial_prompt", json!(null), json!(5)),
+        ];
+        for (field, valid, invalid) in cases {
+            assert!(validate_settings_field(field, &valid).is_ok(), "{} should accept {}", field, valid);
+            assert!(validate_settings_field(field, &invalid).is_err(), "{} should reject {}", field, invalid);
+        }
+
+        // Validating never creates the directory
+        assert!(!temp_dir.path().join("new").exists());
+        assert!(validate_settings_field("no_such_field", &json!(true)).is_err());
+    }
+
+    #[test]
+    fn test_apply_settings_field() {
+        let mut settings = AppSettings::default();
+        apply_settings_field(&mut settings, "output_format", &json!("srt")).unwrap();
+        apply_settings_field(&mut settings, "temperature", &json!(0.4)).unwrap();
+        apply_settings_field(&mut settings, "beam_size", &json!(3)).unwrap();
+        apply_settings_field(&mut settings, "initial_prompt", &json!("Quarterly review")).unwrap();
+        assert_eq!(serde_json::to_value(&settings.output_format).unwrap(), json!("srt"));
+        assert_eq!(settings.temperature, 0.4);
+        assert_eq!(settings.beam_size, 3);
+        assert_eq!(settings.initial_prompt.as_deref(), Some("Quarterly review"));
+        assert!(settings_changed_requires_restart(&AppSettings::default(), &settings));
+
+        apply_settings_field(&mut settings, "initial_prompt", &json!(null)).unwrap();
+        assert_eq!(settings.initial_prompt, None);
+
+        let before = settings.clone();
+        assert!(apply_settings_field(&mut settings, "beam_size", &json!("five")).is_err());
+        assert!(apply_settings_field(&mut settings, "no_such_field", &json!(true)).is_err());
+        assert_eq!(serde_json::to_value(&settings).unwrap(), serde_json::to_value(&before).unwrap());
+    }
+
+    #[test]
+    fn test_diff_settings() {
+        let old = AppSettings::default();
+        assert!(diff_settings(&old, &old).is_empty());
+
+        let new = AppSettings { language: "en".to_string(), model_size: ModelSize::Large, ..old.clone() };
+        assert_eq!(diff_settings(&old, &new), vec![
+            SettingsDiff { field: "language".to_string(), old_value: json!("ko"), new_value: json!("en") },
+            SettingsDiff { field: "model_size".to_string(), old_value: json!("base"), new_value: json!("large") },
+        ]);
+    }
+}
@@ -1,11 +1,97 @@
+use crate::cli::CliManager;
 use crate::error::{AppError, AppResult};
-use crate::models::SUPPORTED_FORMATS;
-use std::path::Path;
+use crate::models::{requires_ffmpeg, AppSettings, FileReadiness, ModelSize, OutputDirectoryMode, PipelineReadinessReport, SUPPORTED_FORMATS};
+use std::path::{Path, PathBuf};
+
+/// Devices found at first use; the hardware doesn't change while the app runs
+static COMPUTE_DEVICES: once_cell::sync::Lazy<Vec<ComputeDevice>> = once_cell::sync::Lazy::new(|| {
+    SystemIntegration::detect_compute_devices_with(std::env::consts::OS, &run_device_query)
+});
+
+/// Run a probe command, returning its trimmed stdout when it succeeds
+fn run_device_query(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Show a directory in the platform's file manager
+fn run_directory_opener(path: &Path) -> std::io::Result<()> {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(program).arg(path).spawn().map(|_| ())
+}
+
+/// A path picked in the native dialog, if it is a local file
+fn dialog_path(path: tauri_plugin_dialog::FilePath) -> Option<String> {
+    path.into_path().ok().map(|path| path.to_string_lossy().to_string())
+}
 
 /// System integration utilities for native OS features
 pub struct SystemIntegration;
 
 impl SystemIntegration {
+    /// Whether tauri-plugin-dialog is registered; headless and test builds run without it,
+    /// and using it there would panic
+    pub fn dialog_available<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> bool {
+        use tauri::Manager;
+        app.try_state::<tauri_plugin_dialog::Dialog<R>>().is_some()
+    }
+
+    /// Pick a directory in the native dialog, falling back to `select_directory` without it
+    pub async fn select_directory_with<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> AppResult<Option<String>> {
+        if !Self::dialog_available(app) {
+            return Self::select_directory().await;
+        }
+
+        use tauri_plugin_dialog::DialogExt;
+        let app = app.clone();
+        let picked = tokio::task::spawn_blocking(move || app.dialog().file().blocking_pick_folder())
+            .await
+            .map_err(|e| AppError::SystemError(format!("Directory dialog failed: {}", e)))?;
+        Ok(picked.and_then(dialog_path))
+    }
+
+    /// Pick audio files in the native dialog, falling back to `select_files` without it
+    pub async fn select_files_with<R: tauri::Runtime>(app: &tauri::AppHandle<R>, multiple: bool) -> AppResult<Vec<String>> {
+        if !Self::dialog_available(app) {
+            return Self::select_files(multiple).await;
+        }
+
+        use tauri_plugin_dialog::DialogExt;
+        let app = app.clone();
+        let picked = tokio::task::spawn_blocking(move || {
+            let dialog = app.dialog().file().add_filter("Audio", SUPPORTED_FORMATS);
+            if multiple {
+                dialog.blocking_pick_files()
+            } else {
+                dialog.blocking_pick_file().map(|path| vec![path])
+            }
+        })
+        .await
+        .map_err(|e| AppError::SystemError(format!("File dialog failed: {}", e)))?;
+        Ok(picked.unwrap_or_default().into_iter().filter_map(dialog_path).collect())
+    }
+
+    /// Choose where to save in the native dialog, falling back to `save_file_dialog` without it
+    pub async fn save_file_dialog_with<R: tauri::Runtime>(app: &tauri::AppHandle<R>, default_filename: &str) -> AppResult<Option<String>> {
+        if !Self::dialog_available(app) {
+            return Self::save_file_dialog(default_filename).await;
+        }
+
+        use tauri_plugin_dialog::DialogExt;
+        let app = app.clone();
+        let default_filename = default_filename.to_string();
+        let picked = tokio::task::spawn_blocking(move || app.dialog().file().set_file_name(default_filename).blocking_save_file())
+            .await
+            .map_err(|e| AppError::SystemError(format!("Save dialog failed: {}", e)))?;
+        Ok(picked.and_then(dialog_path))
+    }
+
     /// Open a native directory picker dialog
     pub async fn select_directory() -> AppResult<Option<String>> {
         // For now, we'll use a simple approach. In a full implementation,
@@ -34,7 +120,7 @@ impl SystemIntegration {
             let mut cmd = Command::new("osascript");
             if multiple {
                 cmd.arg("-e")
-                   .arg("set fileList to choose file with multiple selections allowed with prompt \"Select audio files\" of type {\"m4a\", \"wav\", \"mp3\", \"aac\", \"flac\"}")
+                   .arg("set fileList to choose file with multiple selections allowed with prompt \"Select audio files\" of type {\"m4a\", \"wav\", \"mp3\", \"aac\", \"flac\", \"ogg\", \"opus\", \"wma\"}")
                    .arg("-e")
                    .arg("set posixPaths to {}")
                    .arg("-e")
@@ -49,7 +135,7 @@ impl SystemIntegration {
                    .arg("return posixPaths as string");
             } else {
                 cmd.arg("-e")
-                   .arg("set selectedFile to choose file with prompt \"Select an audio file\" of type {\"m4a\", \"wav\", \"mp3\", \"aac\", \"flac\"}")
+                   .arg("set selectedFile to choose file with prompt \"Select an audio file\" of type {\"m4a\", \"wav\", \"mp3\", \"aac\", \"flac\", \"ogg\", \"opus\", \"wma\"}")
                    .arg("-e")
                    .arg("return POSIX path of selectedFile");
             }
@@ -160,6 +246,49 @@ impl SystemIntegration {
         Ok(())
     }
 
+    /// Forcefully terminate the process with `pid`
+    pub fn kill_process(pid: u32) -> AppResult<()> {
+        let pid = pid.to_string();
+        let output = if cfg!(target_os = "windows") {
+            std::process::Command::new("taskkill").args(["/PID", &pid, "/F"]).output()
+        } else {
+            std::process::Command::new("kill").args(["-9", &pid]).output()
+        }
+        .map_err(|e| AppError::SystemError(format!("Failed to kill process {}: {}", pid, e)))?;
+
+        if !output.status.success() {
+            return Err(AppError::SystemError(format!(
+                "Failed to kill process {}: {}",
+                pid,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Open a directory in the system file manager
+    pub fn open_directory(directory: &str) -> AppResult<()> {
+        Self::open_directory_with(directory, &run_directory_opener)
+    }
+
+    /// Open `directory` with `open`, after checking it is an existing directory
+    pub fn open_directory_with(directory: &str, open: &dyn Fn(&Path) -> std::io::Result<()>) -> AppResult<()> {
+        let directory = directory.trim();
+        if directory.is_empty() {
+            return Err(AppError::ConfigError("No output directory is configured".to_string()));
+        }
+
+        let path = Path::new(directory);
+        if !path.exists() {
+            return Err(AppError::FileNotFound(directory.to_string()));
+        }
+        if !path.is_dir() {
+            return Err(AppError::ConfigError(format!("'{}' is not a directory", directory)));
+        }
+
+        open(path).map_err(|e| AppError::SystemError(format!("Failed to open file manager: {}", e)))
+    }
+
     /// Open a file with the default system application
     pub async fn open_file_with_default_app(file_path: &str) -> AppResult<()> {
         let path = Path::new(file_path);
@@ -202,26 +331,57 @@ impl SystemIntegration {
                 extension: "m4a".to_string(),
                 description: "MPEG-4 Audio (AAC)".to_string(),
                 mime_type: "audio/mp4".to_string(),
+                requires_ffmpeg: false,
+                is_lossless: false,
             },
             FormatInfo {
                 extension: "wav".to_string(),
                 description: "Waveform Audio File".to_string(),
                 mime_type: "audio/wav".to_string(),
+                requires_ffmpeg: false,
+                is_lossless: true,
             },
             FormatInfo {
                 extension: "mp3".to_string(),
                 description: "MPEG Audio Layer III".to_string(),
                 mime_type: "audio/mpeg".to_string(),
+                requires_ffmpeg: true,
+                is_lossless: false,
             },
             FormatInfo {
                 extension: "aac".to_string(),
                 description: "Advanced Audio Coding".to_string(),
                 mime_type: "audio/aac".to_string(),
+                requires_ffmpeg: true,
+                is_lossless: false,
             },
             FormatInfo {
                 extension: "flac".to_string(),
                 description: "Free Lossless Audio Codec".to_string(),
                 mime_type: "audio/flac".to_string(),
+                requires_ffmpeg: false,
+                is_lossless: true,
+            },
+            FormatInfo {
+                extension: "ogg".to_string(),
+                description: "Ogg Vorbis Audio".to_string(),
+                mime_type: "audio/ogg".to_string(),
+                requires_ffmpeg: true,
+                is_lossless: false,
+            },
+            FormatInfo {
+                extension: "opus".to_string(),
+                description: "Opus Audio".to_string(),
+                mime_type: "audio/opus".to_string(),
+                requires_ffmpeg: true,
+                is_lossless: false,
+            },
+            FormatInfo {
+                extension: "wma".to_string(),
+                description: "Windows Media Audio".to_string(),
+                mime_type: "audio/x-ms-wma".to_string(),
+                requires_ffmpeg: true,
+                is_lossless: false,
             },
         ]
     }
@@ -247,7 +407,47 @@ impl SystemIntegration {
             home_dir: dirs::home_dir().map(|p| p.to_string_lossy().to_string()),
             documents_dir: dirs::document_dir().map(|p| p.to_string_lossy().to_string()),
             config_dir: dirs::config_dir().map(|p| p.to_string_lossy().to_string()),
+            compute_devices: Self::detect_compute_devices(),
+        }
+    }
+
+    /// Devices Whisper can run on; the CPU is always listed first
+    pub fn detect_compute_devices() -> Vec<ComputeDevice> {
+        COMPUTE_DEVICES.clone()
+    }
+
+    /// Detect devices for `os`, where `query` runs a command and returns its stdout on success
+    pub fn detect_compute_devices_with(os: &str, query: &dyn Fn(&str, &[&str]) -> Option<String>) -> Vec<ComputeDevice> {
+        let mut devices = vec![ComputeDevice {
+            name: "CPU".to_string(),
+            kind: ComputeDeviceKind::Cpu,
+            available: true,
+        }];
+
+        if os == "macos" {
+            // Metal acceleration (MPS) is only usable on Apple Silicon
+            if let Some(brand) = query("sysctl", &["-n", "machdep.cpu.brand_string"]).filter(|b| !b.is_empty()) {
+                devices[0].name = brand.clone();
+                devices.push(ComputeDevice {
+                    available: brand.starts_with("Apple"),
+                    name: format!("{} GPU", brand),
+                    kind: ComputeDeviceKind::Metal,
+                });
+            }
+        } else if let Some(gpus) = query("nvidia-smi", &["--query-gpu=name", "--format=csv,noheader"]) {
+            devices.extend(gpus.lines().map(str::trim).filter(|name| !name.is_empty()).map(|name| ComputeDevice {
+                name: name.to_string(),
+                kind: ComputeDeviceKind::Cuda,
+                available: true,
+            }));
         }
+
+        devices
+    }
+
+    /// Whether any usable GPU is in `devices`
+    pub fn has_gpu(devices: &[ComputeDevice]) -> bool {
+        devices.iter().any(|device| device.available && device.kind != ComputeDeviceKind::Cpu)
     }
 
     /// Check if the system has required dependencies
@@ -296,20 +496,260 @@ impl SystemIntegration {
         }
 
         // Check FFmpeg (optional but recommended)
-        if let Ok(output) = std::process::Command::new("ffmpeg")
+        check.ffmpeg_available = Self::is_ffmpeg_available();
+
+        if !check.ffmpeg_available {
+            check.issues.push("FFmpeg is not available (optional but recommended for better audio support)".to_string());
+        }
+
+        check
+    }
+
+    /// Whether `ffmpeg` runs from PATH
+    pub fn is_ffmpeg_available() -> bool {
+        std::process::Command::new("ffmpeg")
             .arg("-version")
             .output()
-        {
-            if output.status.success() {
-                check.ffmpeg_available = true;
+            .is_ok_and(|output| output.status.success())
+    }
+
+    /// Check every file and the CLI, model, FFmpeg and output directory a batch needs,
+    /// without transcribing anything
+    pub async fn check_batch_pipeline(cli_manager: &CliManager, file_paths: &[String], settings: &AppSettings) -> PipelineReadinessReport {
+        let mut issues = Vec::new();
+        let mut warnings = Vec::new();
+
+        let cli_available = match cli_manager.check_cli_availability().await {
+            Ok(available) => available,
+            Err(e) => {
+                issues.push(format!("CLI check failed: {}", e));
+                false
             }
+        };
+        if !cli_available && issues.is_empty() {
+            issues.push("speech-to-text CLI did not respond to --version".to_string());
         }
 
-        if !check.ffmpeg_available {
-            check.issues.push("FFmpeg is not available (optional but recommended for better audio support)".to_string());
+        // A missing model only delays the first file while it downloads
+        let model_available = Self::check_model_available(&settings.model_size);
+        if !model_available {
+            warnings.push(format!("Whisper model '{}' is not downloaded yet", settings.model_size));
         }
 
-        check
+        let ffmpeg_available = Self::is_ffmpeg_available();
+        if !ffmpeg_available {
+            warnings.push("FFmpeg is not available; some audio formats may fail to decode".to_string());
+        }
+
+        // Other modes resolve a directory per file
+        let output_directory_writable = match settings.output_directory_mode {
+            OutputDirectoryMode::Absolute => match crate::utils::validate_output_directory(&settings.output_directory) {
+                Ok(()) => true,
+                Err(e) => {
+                    issues.push(format!("Output directory issue: {}", e));
+                    false
+                }
+            },
+            _ => true,
+        };
+
+        let files: Vec<FileReadiness> = file_paths
+            .iter()
+            .map(|path| {
+                let mut file_issues = Vec::new();
+                if let Err(e) = crate::utils::validate_file_path(path) {
+                    file_issues.push(e.to_string());
+                }
+                match crate::utils::validate_audio_format(path) {
+                    Ok(format) if !ffmpeg_available && requires_ffmpeg(&format) => {
+                        file_issues.push(format!(".{} files need FFmpeg, which is not installed", format));
+                    }
+                    Ok(_) => {}
+                    Err(e) => file_issues.push(e.to_string()),
+                }
+                let output_path = crate::utils::get_output_path(path, &settings.output_directory_for(path), settings, &settings.output_format).ok();
+
+                FileReadiness {
+                    file_path: path.clone(),
+                    ready: file_issues.is_empty(),
+                    output_path,
+                    issues: file_issues,
+                }
+            })
+            .collect();
+
+        if files.is_empty() {
+            issues.push("No files to process".to_string());
+        }
+        let can_proceed = issues.is_empty() && files.iter().all(|file| file.ready);
+
+        PipelineReadinessReport {
+            files,
+            cli_available,
+            model_available,
+            ffmpeg_available,
+            output_directory_writable,
+            can_proceed,
+            issues,
+            warnings,
+        }
+    }
+
+    /// Gather every readiness signal into one report for a diagnostics panel
+    pub async fn collect_app_health(cli_manager: &CliManager, output_directory: &str) -> AppHealth {
+        let mut issues = Vec::new();
+
+        let cli_path = match cli_manager.resolved_cli_path() {
+            Ok(path) => Some(path),
+            Err(e) => {
+                issues.push(format!("CLI path could not be resolved: {}", e));
+                None
+            }
+        };
+
+        let cli_available = match cli_manager.check_cli_availability().await {
+            Ok(available) => available,
+            Err(e) => {
+                issues.push(format!("CLI check failed: {}", e));
+                false
+            }
+        };
+        if !cli_available && cli_path.is_some() {
+            issues.push("speech-to-text CLI did not respond to --version".to_string());
+        }
+
+        let cli_version = if cli_available {
+            match cli_manager.get_cli_version().await {
+                Ok(version) => Some(version),
+                Err(e) => {
+                    issues.push(format!("CLI version could not be read: {}", e));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let output_directory = output_directory.trim().to_string();
+        let output_directory_writable = if output_directory.is_empty() {
+            issues.push("No output directory is configured".to_string());
+            false
+        } else {
+            match crate::utils::validate_output_directory(&output_directory) {
+                Ok(()) => true,
+                Err(e) => {
+                    issues.push(e.to_string());
+                    false
+                }
+            }
+        };
+
+        let dependencies = Self::check_system_dependencies().await;
+
+        AppHealth {
+            healthy: cli_available && output_directory_writable,
+            cli_available,
+            cli_version,
+            cli_path,
+            output_directory,
+            output_directory_writable,
+            dependencies,
+            issues,
+        }
+    }
+
+    /// Directory where Whisper caches downloaded models (`$XDG_CACHE_HOME/whisper` or `~/.cache/whisper`)
+    pub fn whisper_model_cache_dir() -> Option<PathBuf> {
+        std::env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cache")))
+            .map(|cache_dir| cache_dir.join("whisper"))
+    }
+
+    /// Check whether a cache directory holds the checkpoint for `model_size`.
+    /// `large` is stored under its versioned name (e.g., `large-v3.pt`).
+    pub fn is_model_cached(cache_dir: &Path, model_size: &ModelSize) -> bool {
+        let model_name = model_size.to_string();
+        let versioned_prefix = format!("{}-v", model_name);
+
+        let Ok(entries) = std::fs::read_dir(cache_dir) else {
+            return false;
+        };
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
+            .filter_map(|name| name.strip_suffix(".pt").map(|stem| stem.to_string()))
+            .any(|stem| stem == model_name || stem.starts_with(&versioned_prefix))
+    }
+
+    /// Check whether the Whisper model has already been downloaded
+    pub fn check_model_available(model_size: &ModelSize) -> bool {
+        Self::whisper_model_cache_dir()
+            .map(|cache_dir| Self::is_model_cached(&cache_dir, model_size))
+            .unwrap_or(false)
+    }
+
+    /// Approximate RAM in bytes needed to run `model_size`, per the Whisper model card
+    pub fn estimate_model_memory(model_size: &ModelSize) -> u64 {
+        const MB: u64 = 1024 * 1024;
+        match model_size {
+            ModelSize::Tiny => 1024 * MB,
+            ModelSize::Base => 1536 * MB,
+            ModelSize::Small => 2 * 1024 * MB,
+            ModelSize::Medium => 5 * 1024 * MB,
+            ModelSize::Large => 10 * 1024 * MB,
+        }
+    }
+
+    /// Memory in bytes available to new processes, if the platform reports it
+    pub fn get_available_memory() -> Option<u64> {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        Some(system.available_memory()).filter(|bytes| *bytes > 0)
+    }
+
+    /// Suggest the largest model this machine can run comfortably, from its memory, CPU cores and GPUs
+    pub fn recommend_model() -> ModelRecommendation {
+        let devices = Self::detect_compute_devices();
+        Self::recommend_model_for(Self::get_available_memory(), num_cpus::get(), Self::has_gpu(&devices))
+    }
+
+    /// Recommend a model for the given available memory in bytes, CPU core count and GPU availability
+    pub fn recommend_model_for(available_memory: Option<u64>, cpu_cores: usize, has_gpu: bool) -> ModelRecommendation {
+        let sizes = [ModelSize::Tiny, ModelSize::Base, ModelSize::Small, ModelSize::Medium, ModelSize::Large];
+
+        // Without a GPU larger models run far slower than real time, so cap them by core count
+        let max_by_speed = match (has_gpu, cpu_cores) {
+            (true, _) => 4,
+            (false, cores) if cores >= 8 => 3,
+            (false, cores) if cores >= 4 => 2,
+            _ => 1,
+        };
+
+        let (model_size, memory_note) = match available_memory {
+            Some(memory) => {
+                let fits = sizes[..=max_by_speed].iter().rev()
+                    .find(|size| Self::estimate_model_memory(size) <= memory)
+                    .cloned()
+                    .unwrap_or(ModelSize::Tiny);
+                (fits, format!("{} of memory available", crate::utils::format_file_size(memory)))
+            }
+            // Unknown memory: stay with the default rather than risk running out
+            None => (sizes[max_by_speed.min(1)].clone(), "available memory unknown".to_string()),
+        };
+
+        let rationale = format!(
+            "{}, {} CPU core{}, {}: the {} model needs about {} and should run at a usable speed",
+            memory_note,
+            cpu_cores,
+            if cpu_cores == 1 { "" } else { "s" },
+            if has_gpu { "GPU acceleration available" } else { "no GPU detected" },
+            model_size,
+            crate::utils::format_file_size(Self::estimate_model_memory(&model_size)),
+        );
+        ModelRecommendation { model_size, rationale }
     }
 
     /// Get available disk space for a given directory
@@ -332,6 +772,12 @@ pub struct FormatInfo {
     pub extension: String,
     pub description: String,
     pub mime_type: String,
+    /// Only decodable when FFmpeg is installed
+    #[serde(default)]
+    pub requires_ffmpeg: bool,
+    /// Stores audio without lossy compression
+    #[serde(default)]
+    pub is_lossless: bool,
 }
 
 /// System information structure
@@ -343,6 +789,33 @@ pub struct SystemInfo {
     pub home_dir: Option<String>,
     pub documents_dir: Option<String>,
     pub config_dir: Option<String>,
+    #[serde(default)]
+    pub compute_devices: Vec<ComputeDevice>,
+}
+
+/// Model size suggested for this machine and why
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ModelRecommendation {
+    pub model_size: ModelSize,
+    pub rationale: String,
+}
+
+/// Kind of hardware a transcription can run on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComputeDeviceKind {
+    Cpu,
+    Cuda,
+    Metal,
+}
+
+/// A detected compute device
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ComputeDevice {
+    pub name: String,
+    pub kind: ComputeDeviceKind,
+    /// False when the device exists but Whisper can't accelerate on it
+    pub available: bool,
 }
 
 /// System dependency check result
@@ -355,10 +828,38 @@ pub struct SystemDependencyCheck {
     pub issues: Vec<String>,
 }
 
+/// Aggregated readiness report; `issues` explains every failed check
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AppHealth {
+    pub healthy: bool,
+    pub cli_available: bool,
+    pub cli_version: Option<String>,
+    pub cli_path: Option<String>,
+    pub output_directory: String,
+    pub output_directory_writable: bool,
+    pub dependencies: SystemDependencyCheck,
+    pub issues: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_dialogs_fall_back_without_plugin() {
+        let app = tauri::test::mock_app();
+        assert!(!SystemIntegration::dialog_available(app.handle()));
+
+        let directory = SystemIntegration::select_directory_with(app.handle()).await.unwrap();
+        assert_eq!(directory, SystemIntegration::select_directory().await.unwrap());
+
+        let save_path = SystemIntegration::save_file_dialog_with(app.handle(), "notes.txt").await.unwrap().unwrap();
+        assert!(save_path.ends_with("notes.txt"), "{}", save_path);
+
+        #[cfg(not(target_os = "macos"))]
+        assert!(SystemIntegration::select_files_with(app.handle(), true).await.unwrap().is_empty());
+    }
+
     #[test]
     fn test_get_supported_formats() {
         let formats = SystemIntegration::get_supported_formats();
@@ -372,9 +873,29 @@ mod tests {
         let formats = SystemIntegration::get_supported_formats_detailed();
         assert!(!formats.is_empty());
         
-        let m4a_format = formats.iter().find(|f| f.extension == "m4a");
-        assert!(m4a_format.is_some());
-        assert_eq!(m4a_format.unwrap().mime_type, "audio/mp4");
+        let format = |extension: &str| formats.iter().find(|f| f.extension == extension).unwrap();
+        assert_eq!(format("m4a").mime_type, "audio/mp4");
+        assert!(!format("m4a").requires_ffmpeg);
+
+        for (extension, mime_type) in [
+            ("mp3", "audio/mpeg"),
+            ("aac", "audio/aac"),
+            ("ogg", "audio/ogg"),
+            ("opus", "audio/opus"),
+            ("wma", "audio/x-ms-wma"),
+        ] {
+            assert_eq!(format(extension).mime_type, mime_type);
+            assert!(format(extension).requires_ffmpeg);
+            assert!(!format(extension).is_lossless);
+        }
+        assert!(!format("wav").requires_ffmpeg);
+        assert!(format("wav").is_lossless);
+        assert!(format("flac").is_lossless);
+
+        // Every supported extension has a detailed entry that agrees with the pipeline
+        for extension in SUPPORTED_FORMATS {
+            assert_eq!(format(extension).requires_ffmpeg, requires_ffmpeg(extension), "{}", extension);
+        }
     }
 
     #[test]
@@ -382,6 +903,9 @@ mod tests {
         assert!(SystemIntegration::is_supported_format("m4a"));
         assert!(SystemIntegration::is_supported_format(".wav"));
         assert!(SystemIntegration::is_supported_format("MP3"));
+        assert!(SystemIntegration::is_supported_format("ogg"));
+        assert!(SystemIntegration::is_supported_format(".opus"));
+        assert!(SystemIntegration::is_supported_format("WMA"));
         assert!(!SystemIntegration::is_supported_format("txt"));
         assert!(!SystemIntegration::is_supported_format("doc"));
     }
@@ -392,6 +916,36 @@ mod tests {
         assert!(!info.os.is_empty());
         assert!(!info.arch.is_empty());
         assert!(!info.family.is_empty());
+        assert_eq!(info.compute_devices[0].kind, ComputeDeviceKind::Cpu);
+    }
+
+    #[test]
+    fn test_detect_compute_devices_with_mocked_query() {
+        let nvidia = |program: &str, _: &[&str]| {
+            (program == "nvidia-smi").then(|| "NVIDIA GeForce RTX 4090\nNVIDIA A100\n".to_string())
+        };
+        let devices = SystemIntegration::detect_compute_devices_with("linux", &nvidia);
+        assert_eq!(devices.len(), 3);
+        assert_eq!(devices[1].name, "NVIDIA GeForce RTX 4090");
+        assert_eq!(devices[2].kind, ComputeDeviceKind::Cuda);
+        assert!(SystemIntegration::has_gpu(&devices));
+
+        // No driver means CPU only
+        let devices = SystemIntegration::detect_compute_devices_with("linux", &|_: &str, _: &[&str]| None);
+        assert_eq!(devices.len(), 1);
+        assert!(!SystemIntegration::has_gpu(&devices));
+
+        let apple = |_: &str, _: &[&str]| Some("Apple M2 Pro".to_string());
+        let devices = SystemIntegration::detect_compute_devices_with("macos", &apple);
+        assert_eq!(devices[0].name, "Apple M2 Pro");
+        assert_eq!(devices[1].kind, ComputeDeviceKind::Metal);
+        assert!(SystemIntegration::has_gpu(&devices));
+
+        // Intel Macs report a Metal device that Whisper can't use
+        let intel = |_: &str, _: &[&str]| Some("Intel(R) Core(TM) i9-9880H CPU @ 2.30GHz".to_string());
+        let devices = SystemIntegration::detect_compute_devices_with("macos", &intel);
+        assert!(!devices[1].available);
+        assert!(!SystemIntegration::has_gpu(&devices));
     }
 
     #[tokio::test]
@@ -403,6 +957,84 @@ mod tests {
         assert!(check.issues.is_empty() || !check.issues.is_empty()); // Always true, but uses the variable
     }
 
+    #[tokio::test]
+    async fn test_collect_app_health() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let missing_cli = temp_dir.path().join("missing-cli");
+        let cli_manager = CliManager::new_dev().with_cli_binary_path(missing_cli.to_string_lossy());
+
+        let health = SystemIntegration::collect_app_health(&cli_manager, &temp_dir.path().to_string_lossy()).await;
+        assert!(!health.healthy);
+        assert!(!health.cli_available);
+        assert!(health.cli_version.is_none());
+        assert!(health.output_directory_writable);
+        assert!(health.issues.iter().any(|issue| issue.contains("CLI")));
+
+        let json = serde_json::to_value(&health).unwrap();
+        assert_eq!(json["output_directory_writable"], true);
+        assert!(json["dependencies"]["issues"].is_array());
+        let round_trip: AppHealth = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.issues, health.issues);
+
+        let health = SystemIntegration::collect_app_health(&cli_manager, "").await;
+        assert!(!health.output_directory_writable);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_collect_app_health_with_installed_cli() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let binary = temp_dir.path().join("speech-to-text");
+        let cli_manager = CliManager::new_dev().with_cli_binary_path(binary.to_string_lossy());
+        assert!(!SystemIntegration::collect_app_health(&cli_manager, &temp_dir.path().to_string_lossy()).await.cli_available);
+
+        // Installed after the first check; the next probe picks it up
+        std::fs::write(&binary, "#!/bin/sh\necho 'speech-to-text 1.2.3'\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let health = SystemIntegration::collect_app_health(&cli_manager, &temp_dir.path().to_string_lossy()).await;
+        assert!(health.cli_available);
+        assert!(health.healthy);
+        assert_eq!(health.cli_version.as_deref(), Some("speech-to-text 1.2.3"));
+        assert_eq!(health.cli_path, Some(binary.to_string_lossy().to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_check_batch_pipeline_flags_invalid_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let valid = temp_dir.path().join("meeting.m4a");
+        let unsupported = temp_dir.path().join("notes.txt");
+        std::fs::write(&valid, b"audio").unwrap();
+        std::fs::write(&unsupported, b"text").unwrap();
+        let missing = temp_dir.path().join("missing.wav");
+
+        let file_paths: Vec<String> = [&valid, &unsupported, &missing]
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let settings = AppSettings {
+            output_directory: temp_dir.path().to_string_lossy().to_string(),
+            ..AppSettings::default()
+        };
+        let cli_manager = CliManager::new_dev().with_cli_binary_path(temp_dir.path().join("missing-cli").to_string_lossy());
+
+        let report = SystemIntegration::check_batch_pipeline(&cli_manager, &file_paths, &settings).await;
+        let ready: Vec<bool> = report.files.iter().map(|file| file.ready).collect();
+        assert_eq!(ready, vec![true, false, false]);
+        assert!(report.files[1].issues[0].contains("Unsupported"));
+        assert!(report.files[2].issues[0].contains("not found"));
+        assert!(report.files[0].output_path.as_deref().is_some_and(|path| path.ends_with("meeting_transcription.txt")));
+
+        // Nothing was transcribed, and the missing CLI blocks the batch
+        assert!(!temp_dir.path().join("meeting_transcription.txt").exists());
+        assert!(report.output_directory_writable);
+        assert!(!report.cli_available);
+        assert!(!report.can_proceed);
+        assert!(!report.issues.is_empty());
+    }
+
     #[tokio::test]
     async fn test_select_directory() {
         let result = SystemIntegration::select_directory().await;
@@ -412,6 +1044,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_model_cached() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["base.pt", "large-v3.pt", "tiny.en.pt", "small.pt.partial", "notes.txt"] {
+            std::fs::write(temp_dir.path().join(name), b"").unwrap();
+        }
+
+        assert!(SystemIntegration::is_model_cached(temp_dir.path(), &ModelSize::Base));
+        assert!(SystemIntegration::is_model_cached(temp_dir.path(), &ModelSize::Large));
+        assert!(!SystemIntegration::is_model_cached(temp_dir.path(), &ModelSize::Tiny));
+        assert!(!SystemIntegration::is_model_cached(temp_dir.path(), &ModelSize::Small));
+        assert!(!SystemIntegration::is_model_cached(temp_dir.path(), &ModelSize::Medium));
+        assert!(!SystemIntegration::is_model_cached(&temp_dir.path().join("missing"), &ModelSize::Base));
+    }
+
+    #[test]
+    fn test_estimate_model_memory_grows_with_model_size() {
+        let sizes = [ModelSize::Tiny, ModelSize::Base, ModelSize::Small, ModelSize::Medium, ModelSize::Large];
+        let estimates: Vec<u64> = sizes.iter().map(SystemIntegration::estimate_model_memory).collect();
+
+        assert!(estimates.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(estimates[0], 1024 * 1024 * 1024);
+        assert_eq!(estimates[4], 10 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_recommend_model_for_hardware() {
+        const GB: u64 = 1024 * 1024 * 1024;
+
+        let low_memory = SystemIntegration::recommend_model_for(Some(3 * GB), 8, false);
+        assert!(matches!(low_memory.model_size, ModelSize::Small), "{:?}", low_memory);
+        assert!(low_memory.rationale.contains("no GPU detected"));
+        let tiny_memory = SystemIntegration::recommend_model_for(Some(GB + GB / 4), 8, false);
+        assert!(matches!(tiny_memory.model_size, ModelSize::Tiny));
+
+        let workstation = SystemIntegration::recommend_model_for(Some(32 * GB), 16, true);
+        assert!(matches!(workstation.model_size, ModelSize::Large), "{:?}", workstation);
+        assert!(workstation.rationale.contains("GPU acceleration available"));
+
+        // Plenty of memory but no GPU is limited by the CPU
+        let cpu_only = SystemIntegration::recommend_model_for(Some(32 * GB), 2, false);
+        assert!(matches!(cpu_only.model_size, ModelSize::Base));
+        let unknown = SystemIntegration::recommend_model_for(None, 16, true);
+        assert!(matches!(unknown.model_size, ModelSize::Base));
+    }
+
+    #[test]
+    fn test_open_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let opened = std::cell::RefCell::new(Vec::new());
+        let open = |path: &Path| {
+            opened.borrow_mut().push(path.to_path_buf());
+            Ok(())
+        };
+
+        let missing = temp_dir.path().join("missing");
+        let result = SystemIntegration::open_directory_with(&missing.to_string_lossy(), &open);
+        assert!(matches!(result, Err(AppError::FileNotFound(_))));
+        assert!(SystemIntegration::open_directory_with("", &open).is_err());
+
+        SystemIntegration::open_directory_with(&temp_dir.path().to_string_lossy(), &open).unwrap();
+        assert_eq!(opened.into_inner(), vec![temp_dir.path().to_path_buf()]);
+    }
+
     #[test]
     fn test_get_available_disk_space_invalid_path() {
         let result = SystemIntegration::get_available_disk_space("/nonexistent/path");
@@ -0,0 +1,66 @@
+//! Fixtures shared by the unit tests
+
+use crate::models::{AudioFile, AudioInfo, FileStatus, TranscriptionMetadata, TranscriptionResult};
+use chrono::Utc;
+use std::path::Path;
+
+/// A finished result for `path` with placeholder values
+pub fn sample_result(path: &str) -> TranscriptionResult {
+    let file_path = Path::new(path);
+    let stem = file_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("output");
+    TranscriptionResult {
+        id: crate::utils::generate_id(),
+        original_file: AudioFile {
+            id: crate::utils::generate_id(),
+            name: file_path.file_name().and_then(|name| name.to_str()).unwrap_or(path).to_string(),
+            path: path.to_string(),
+            size: 1024,
+            format: file_path.extension().and_then(|ext| ext.to_str()).unwrap_or("m4a").to_string(),
+            duration: None,
+            status: FileStatus::Completed,
+            hash: None,
+        },
+        transcribed_text: "text".to_string(),
+        metadata: TranscriptionMetadata {
+            language: "ko".to_string(),
+            model_size: "base".to_string(),
+            timestamp: Utc::now(),
+            audio_info: AudioInfo { duration: 30.0, sample_rate: None, channels: None },
+        },
+        output_path: format!("/out/{}_transcription.txt", stem),
+        processing_time: 3.0,
+        confidence: None,
+        segments: None,
+        warnings: Vec::new(),
+        no_speech_detected: false,
+        realtime_factor: None,
+    }
+}
+
+/// Write an executable stand-in for the speech-to-text CLI to `dir`. Its arguments are parsed
+/// the way the real CLI takes them into `$input`, `$stem`, `$language` and `$output_dir`, with
+/// all of them kept in `$args`. `body` then runs, and can call `write_output TEXT` to save a
+/// transcript where the CLI would.
+#[cfg(unix)]
+pub fn write_fake_cli(dir: &Path, body: &str) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let binary = dir.join("fake-speech-to-text");
+    let script = format!(r#"#!/bin/sh
+args="$*"
+input="$1"; shift
+while [ $# -gt 0 ]; do
+  case "$1" in
+    --language) language="$2"; shift 2 ;;
+    --output-dir) output_dir="$2"; shift 2 ;;
+    *) shift ;;
+  esac
+done
+stem=$(basename "$input"); stem="${{stem%.*}}"
+write_output() {{ printf '%s' "$1" > "$output_dir/${{stem}}_transcription_202601011200.txt"; }}
+{}
+"#, body);
+    std::fs::write(&binary, script).unwrap();
+    std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+    binary
+}
@@ -0,0 +1,157 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{AppSettings, AudioFile, OutputEncoding, TranscriptionResult};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+/// Stores finished transcriptions keyed by input hash and the settings that affect output
+#[derive(Debug, Clone)]
+pub struct TranscriptionCache {
+    directory: PathBuf,
+}
+
+impl TranscriptionCache {
+    /// Create a cache stored in `directory`
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into() }
+    }
+
+    /// Default cache location under the user's cache directory
+    pub fn default_directory() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("SpeechToText")
+            .join("transcriptions")
+    }
+
+    /// Cache key for a file hash under the given settings
+    pub fn cache_key(hash: &str, settings: &AppSettings) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(hash.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(settings.language.trim().to_lowercase().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(settings.model_size.to_string().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(settings.output_format.extension().as_bytes());
+        // Only hashed when changed, so entries cached with the defaults keep their keys
+        if settings.temperature != 0.0 || settings.beam_size != crate::models::DEFAULT_BEAM_SIZE {
+            hasher.update(format!("\0decoding\0{}\0{}", settings.temperature, settings.beam_size).as_bytes());
+        }
+        // Only hashed when set, so entries cached without a prompt keep their keys
+        if let Some(prompt) = crate::cli::CliManager::initial_prompt(settings) {
+            hasher.update(b"\0prompt\0");
+            hasher.update(prompt.as_bytes());
+        }
+        if settings.enable_diarization {
+            hasher.update(b"\0diarize");
+        }
+        if settings.clean_output_text {
+            hasher.update(b"\0clean");
+        }
+        if settings.output_encoding != OutputEncoding::Utf8 {
+            hasher.update(b"\0bom");
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn entry_path(&self, hash: &str, settings: &AppSettings) -> PathBuf {
+        self.directory.join(format!("{}.json", Self::cache_key(hash, settings)))
+    }
+
+    /// Look up a cached result for `audio_file`; unreadable entries count as misses
+    pub fn get(&self, audio_file: &AudioFile, settings: &AppSettings) -> Option<TranscriptionResult> {
+        let hash = audio_file.hash.as_deref()?;
+        let content = std::fs::read_to_string(self.entry_path(hash, settings)).ok()?;
+        let mut result: TranscriptionResult = serde_json::from_str(&content).ok()?;
+
+        // Duplicates may live at another path, so report the file that was asked for
+        result.original_file = audio_file.clone();
+        result.processing_time = 0.0;
+        result.realtime_factor = None;
+        result.warnings.clear();
+        Some(result)
+    }
+
+    /// Drop the entry for `audio_file` so the next run transcribes it again
+    pub fn remove(&self, audio_file: &AudioFile, settings: &AppSettings) -> AppResult<()> {
+        let Some(hash) = audio_file.hash.as_deref() else {
+            return Ok(());
+        };
+        match std::fs::remove_file(self.entry_path(hash, settings)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Store a result under its input file's hash
+    pub fn put(&self, result: &TranscriptionResult, settings: &AppSettings) -> AppResult<()> {
+        let hash = result.original_file.hash.as_deref()
+            .ok_or_else(|| AppError::ProcessingError("Cannot cache a result without a file hash".to_string()))?;
+
+        std::fs::create_dir_all(&self.directory)?;
+        let content = serde_json::to_string(result)
+            .map_err(|e| AppError::ProcessingError(format!("Failed to serialize cached result: {}", e)))?;
+        std::fs::write(self.entry_path(hash, settings), content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ModelSize;
+    use tempfile::tempdir;
+
+    fn sample_result(path: &str, hash: Option<&str>) -> TranscriptionResult {
+        let mut result = crate::test_support::sample_result(path);
+        result.original_file.hash = hash.map(str::to_string);
+        result.transcribed_text = "cached text".to_string();
+        result.processing_time = 42.0;
+        result.warnings = vec!["slow".to_string()];
+        result
+    }
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let temp_dir = tempdir().unwrap();
+        let cache = TranscriptionCache::new(temp_dir.path());
+        let settings = AppSettings::default();
+        let stored = sample_result("/audio/meeting.m4a", Some("abc123"));
+
+        assert!(cache.get(&stored.original_file, &settings).is_none());
+        cache.put(&stored, &settings).unwrap();
+
+        // A duplicate at another path hits the same entry
+        let duplicate = sample_result("/backup/meeting copy.m4a", Some("abc123")).original_file;
+        let cached = cache.get(&duplicate, &settings).unwrap();
+        assert_eq!(cached.transcribed_text, "cached text");
+        assert_eq!(cached.original_file.path, "/backup/meeting copy.m4a");
+        assert_eq!(cached.processing_time, 0.0);
+        assert!(cached.warnings.is_empty());
+
+        // Any setting in the key misses
+        let other_model = AppSettings { model_size: ModelSize::Tiny, ..settings.clone() };
+        assert!(cache.get(&duplicate, &other_model).is_none());
+        let other_language = AppSettings { language: "en".to_string(), ..settings.clone() };
+        assert!(cache.get(&duplicate, &other_language).is_none());
+        let with_prompt = AppSettings { initial_prompt: Some("Quarterly review".to_string()), ..settings.clone() };
+        assert!(cache.get(&duplicate, &with_prompt).is_none());
+        let diarized = AppSettings { enable_diarization: true, ..settings.clone() };
+        assert!(cache.get(&duplicate, &diarized).is_none());
+        let cleaned = AppSettings { clean_output_text: true, ..settings.clone() };
+        assert!(cache.get(&duplicate, &cleaned).is_none());
+        let with_bom = AppSettings { output_encoding: OutputEncoding::Utf8Bom, ..settings.clone() };
+        assert!(cache.get(&duplicate, &with_bom).is_none());
+
+        // Different content or no hash misses
+        let other_file = sample_result("/audio/meeting.m4a", Some("def456")).original_file;
+        assert!(cache.get(&other_file, &settings).is_none());
+        let unhashed = sample_result("/audio/meeting.m4a", None);
+        assert!(cache.get(&unhashed.original_file, &settings).is_none());
+        assert!(cache.put(&unhashed, &settings).is_err());
+
+        cache.remove(&duplicate, &settings).unwrap();
+        assert!(cache.get(&duplicate, &settings).is_none());
+        cache.remove(&duplicate, &settings).unwrap();
+    }
+}
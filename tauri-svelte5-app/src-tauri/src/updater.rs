@@ -111,7 +111,7 @@ pub fn notify_update_available(app_handle: &AppHandle, update_info: &UpdateInfo)
     log::info!("Update available: version {}", update_info.version);
     
     // Emit event to frontend
-    let _ = app_handle.emit("update-available", update_info);
+    let _ = app_handle.emit(crate::events::UPDATE_AVAILABLE, update_info);
 }
 
 /// Initialize updater system
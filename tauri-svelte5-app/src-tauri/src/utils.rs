@@ -1,6 +1,7 @@
 use crate::error::{AppError, AppResult};
-use crate::models::{AudioFile, FileStatus, SUPPORTED_FORMATS};
-use std::path::Path;
+use crate::models::{is_supported_format, AppSettings, AudioFile, AudioInfo, CacheClearReport, FileStatus, ModelSize, OutputEncoding, OutputFormat, SUPPORTED_FORMATS};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// Generate a unique ID for files and jobs
@@ -8,10 +9,25 @@ pub fn generate_id() -> String {
     Uuid::new_v4().to_string()
 }
 
+/// The form of `path` that exists on disk. macOS stores file names decomposed (NFD)
+/// while pasted paths are usually composed (NFC), so both forms are tried.
+pub fn resolve_file_path(path: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+
+    let trimmed = path.trim();
+    let composed: String = trimmed.nfc().collect();
+    let decomposed: String = trimmed.nfd().collect();
+    [trimmed.to_string(), composed, decomposed]
+        .into_iter()
+        .find(|candidate| Path::new(candidate).exists())
+        .unwrap_or_else(|| trimmed.to_string())
+}
+
 /// Validate if a file path exists and is accessible
 pub fn validate_file_path(path: &str) -> AppResult<()> {
     // Handle potential Unicode normalization issues
-    let normalized_path = path.trim();
+    let resolved_path = resolve_file_path(path);
+    let normalized_path = resolved_path.as_str();
     let file_path = Path::new(normalized_path);
     
     // Debug logging
@@ -53,7 +69,16 @@ pub fn get_file_extension(path: &str) -> Option<String> {
 pub fn validate_audio_format(path: &str) -> AppResult<String> {
     println!("🎵 Validating audio format for: {}", path);
     
-    let extension = get_file_extension(path)
+    // Without a usable extension, recognize the format from the file contents
+    let extension = get_file_extension(path);
+    if !extension.as_ref().is_some_and(|ext| SUPPORTED_FORMATS.contains(&ext.as_str())) {
+        if let Some(detected) = sniff_audio_format(path) {
+            println!("🔎 Detected '{}' audio from file contents", detected);
+            return Ok(detected);
+        }
+    }
+
+    let extension = extension
         .ok_or_else(|| {
             let error_msg = "No file extension found".to_string();
             println!("❌ {}", error_msg);
@@ -78,18 +103,116 @@ pub fn validate_audio_format(path: &str) -> AppResult<String> {
     Ok(extension)
 }
 
+/// Supported audio format recognized from the magic bytes at the start of the file
+pub fn sniff_audio_format(path: &str) -> Option<String> {
+    use std::io::Read;
+
+    let mut header = Vec::with_capacity(64);
+    std::fs::File::open(path.trim()).ok()?.take(64).read_to_end(&mut header).ok()?;
+
+    infer::get(&header)
+        .filter(|kind| kind.matcher_type() == infer::MatcherType::Audio)
+        .map(|kind| kind.extension())
+        .filter(|extension| SUPPORTED_FORMATS.contains(extension))
+        .map(str::to_string)
+}
+
+/// A uniquely named folder for one run's intermediate files, removed with its contents when dropped
+#[derive(Debug)]
+pub struct ScratchDirectory {
+    path: PathBuf,
+}
+
+impl ScratchDirectory {
+    /// Create a new folder under `parent`
+    pub fn create(parent: &Path) -> AppResult<Self> {
+        let path = parent.join(generate_id());
+        std::fs::create_dir_all(&path)?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ScratchDirectory {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Move `from` to `to`, copying when they are on different file systems
+pub fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+/// Hard link (or copy) of an input whose extension doesn't name its format, carrying the
+/// extension detected from its contents so tools that go by the suffix accept it. The link is
+/// removed when this is dropped.
+pub struct DetectedFormatInput {
+    _directory: ScratchDirectory,
+    path: String,
+}
+
+impl DetectedFormatInput {
+    /// Link `path` into a fresh folder under `parent`; `None` when its extension is already
+    /// supported or its format can't be recognized
+    pub fn create(path: &str, parent: &Path) -> AppResult<Option<Self>> {
+        if get_file_extension(path).is_some_and(|ext| SUPPORTED_FORMATS.contains(&ext.as_str())) {
+            return Ok(None);
+        }
+        let Some(format) = sniff_audio_format(path) else {
+            return Ok(None);
+        };
+
+        let source = Path::new(path.trim());
+        let stem = source.file_stem().and_then(|stem| stem.to_str()).unwrap_or("audio");
+        let directory = ScratchDirectory::create(parent)?;
+        let link = directory.path().join(format!("{}.{}", stem, format));
+        // Hard links can't cross file systems; a copy works anywhere
+        if std::fs::hard_link(source, &link).is_err() {
+            std::fs::copy(source, &link)
+                .map_err(|e| AppError::IoError(format!("Failed to stage {} as .{}: {}", path, format, e)))?;
+        }
+
+        println!("🔗 Staged {} as {}", path, link.display());
+        Ok(Some(Self { _directory: directory, path: link.to_string_lossy().to_string() }))
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
 /// Create AudioFile struct from file path
 pub fn create_audio_file(path: &str) -> AppResult<AudioFile> {
-    let normalized_path = path.trim();
+    create_audio_file_with_limit(path, None)
+}
+
+/// Like `create_audio_file`, rejecting files over `max_file_size_mb`
+pub fn create_audio_file_with_limit(path: &str, max_file_size_mb: Option<u32>) -> AppResult<AudioFile> {
+    let resolved_path = resolve_file_path(path);
+    let normalized_path = resolved_path.as_str();
     
     validate_file_path(normalized_path)?;
     let format = validate_audio_format(normalized_path)?;
+    validate_file_size(normalized_path, max_file_size_mb)?;
     
-    let file_path = Path::new(normalized_path);
     let metadata = std::fs::metadata(normalized_path)?;
+    if metadata.len() == 0 {
+        return Err(AppError::AudioDecodeError(format!(
+            "'{}' is empty (0 bytes) and contains no audio",
+            path.trim()
+        )));
+    }
     
-    // Handle Unicode file names safely
-    let file_name = file_path
+    // Handle Unicode file names safely, showing the name as the caller wrote it
+    let file_name = Path::new(path.trim())
         .file_name()
         .and_then(|name| name.to_str())
         .map(|name| name.to_string())
@@ -108,9 +231,48 @@ pub fn create_audio_file(path: &str) -> AppResult<AudioFile> {
         format,
         duration: None, // Will be populated during processing
         status: FileStatus::Pending,
+        // Hashing reads the whole file, so it is left to callers that need it
+        hash: None,
     })
 }
 
+/// SHA-256 of a file's contents as lowercase hex
+pub fn hash_file(path: &str) -> AppResult<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// `hash_file` on the blocking thread pool, so hashing a long recording doesn't stall async tasks
+pub async fn hash_file_blocking(path: &str) -> AppResult<String> {
+    let path = path.to_string();
+    tokio::task::spawn_blocking(move || hash_file(&path))
+        .await
+        .map_err(|e| AppError::IoError(format!("Failed to hash file: {}", e)))?
+}
+
+/// Reject a file larger than `max_file_size_mb` megabytes; `None` means no limit
+pub fn validate_file_size(path: &str, max_file_size_mb: Option<u32>) -> AppResult<()> {
+    let Some(max_file_size_mb) = max_file_size_mb else {
+        return Ok(());
+    };
+
+    let size = std::fs::metadata(path.trim())?.len();
+    if size > max_file_size_mb as u64 * 1024 * 1024 {
+        return Err(AppError::FileTooLarge(format!(
+            "'{}' is {}, over the {} MB limit",
+            path.trim(),
+            format_file_size(size),
+            max_file_size_mb
+        )));
+    }
+
+    Ok(())
+}
+
 /// Format file size in human-readable format
 pub fn format_file_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
@@ -129,6 +291,13 @@ pub fn format_file_size(bytes: u64) -> String {
     }
 }
 
+/// How many times faster than realtime `audio_duration` seconds were processed in
+/// `processing_time` seconds; None unless both are positive
+pub fn realtime_factor(audio_duration: f64, processing_time: f64) -> Option<f64> {
+    (audio_duration > 0.0 && processing_time > 0.0 && audio_duration.is_finite() && processing_time.is_finite())
+        .then(|| audio_duration / processing_time)
+}
+
 /// Format duration in human-readable format (MM:SS)
 pub fn format_duration(seconds: f64) -> String {
     let total_seconds = seconds as u64;
@@ -148,33 +317,237 @@ pub fn sanitize_filename(filename: &str) -> String {
         .collect()
 }
 
-/// Get output filename for transcription result
-pub fn get_output_filename(input_path: &str, output_dir: &str) -> AppResult<String> {
-    let input_path = Path::new(input_path);
-    let stem = input_path
+/// Highest ` (n)` suffix tried before giving up on finding a free output filename
+const MAX_OUTPUT_SUFFIX: u32 = 9999;
+
+/// Output name used when `output_filename_template` is empty
+pub const DEFAULT_OUTPUT_FILENAME_TEMPLATE: &str = "{name}_transcription";
+
+/// Render `output_filename_template` for `input_path` into a file name without extension.
+/// Characters that aren't allowed in file names are replaced, so `{name}` can't escape the output directory.
+pub fn render_output_filename(input_path: &str, settings: &AppSettings, now: chrono::DateTime<chrono::Local>) -> AppResult<String> {
+    let stem = Path::new(input_path)
         .file_stem()
         .and_then(|s| s.to_str())
         .ok_or_else(|| AppError::IoError("Invalid input filename".to_string()))?;
 
-    let sanitized_stem = sanitize_filename(stem);
-    // Use transcription suffix (actual file will have timestamp added by Python CLI)
-    let output_filename = format!("{}_transcription.txt", sanitized_stem);
+    Ok(render_filename_template(&settings.output_filename_template, stem, &settings.language, &settings.model_size, now))
+}
 
+/// Fill in a filename template, falling back to `DEFAULT_OUTPUT_FILENAME_TEMPLATE` when it renders to nothing
+pub fn render_filename_template(
+    template: &str,
+    name: &str,
+    language: &str,
+    model_size: &ModelSize,
+    now: chrono::DateTime<chrono::Local>,
+) -> String {
+    let render = |template: &str| {
+        let rendered = template
+            .replace("{name}", name)
+            .replace("{date}", &now.format("%Y-%m-%d").to_string())
+            .replace("{time}", &now.format("%H%M%S").to_string())
+            .replace("{lang}", language.trim())
+            .replace("{model}", &model_size.to_string());
+        sanitize_filename(rendered.trim())
+    };
+
+    let rendered = render(template.trim());
+    if rendered.trim_matches('.').is_empty() {
+        return render(DEFAULT_OUTPUT_FILENAME_TEMPLATE);
+    }
+    rendered
+}
+
+/// Rename existing transcription outputs to match `template`, returning (old, new) path pairs.
+///
+/// The input name is recovered from the output name (`{name}_transcription...`), the language
+/// from the CLI's JSON output next to the file when there is one, and the date and time from
+/// the file's modification time. `settings` fills in whatever can't be recovered. Files whose
+/// new name is already taken are skipped.
+pub fn rename_outputs(paths: &[String], template: &str, settings: &AppSettings) -> Vec<(String, String)> {
+    let output_suffix = regex::Regex::new(r"_transcription(_\d{12})?( \(\d+\))?$").unwrap();
+    let mut renamed = Vec::new();
+
+    for path in paths {
+        let old_path = Path::new(path.trim());
+        let (Some(stem), Some(parent)) = (old_path.file_stem().and_then(|s| s.to_str()), old_path.parent()) else {
+            continue;
+        };
+        let Ok(modified) = std::fs::metadata(old_path).and_then(|metadata| metadata.modified()) else {
+            println!("⚠️ Skipping rename of missing output: {}", old_path.display());
+            continue;
+        };
+
+        let json_path = old_path.with_extension("json");
+        let language = std::fs::read_to_string(&json_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| json.get("language").and_then(|language| language.as_str()).map(str::to_string))
+            .unwrap_or_else(|| settings.language.clone());
+
+        let name = output_suffix.replace(stem, "");
+        let new_name = render_filename_template(template, &name, &language, &settings.model_size, modified.into());
+        let new_path = match old_path.extension().and_then(|ext| ext.to_str()) {
+            Some(extension) => parent.join(format!("{}.{}", new_name, extension)),
+            None => parent.join(&new_name),
+        };
+
+        if new_path == old_path {
+            continue;
+        }
+        if new_path.exists() {
+            println!("⚠️ Not renaming {}: {} already exists", old_path.display(), new_path.display());
+            continue;
+        }
+        if let Err(e) = std::fs::rename(old_path, &new_path) {
+            println!("⚠️ Failed to rename {}: {}", old_path.display(), e);
+            continue;
+        }
+
+        // Keep the JSON output paired with the text output
+        let new_json_path = new_path.with_extension("json");
+        let json_listed = paths.iter().any(|other| Path::new(other.trim()) == json_path);
+        if json_path != old_path && json_path.exists() && !json_listed && !new_json_path.exists() {
+            let _ = std::fs::rename(&json_path, &new_json_path);
+        }
+
+        renamed.push((old_path.to_string_lossy().to_string(), new_path.to_string_lossy().to_string()));
+    }
+
+    println!("✏️ Renamed {} of {} outputs", renamed.len(), paths.len());
+    renamed
+}
+
+/// Get a free path for the text output of `input_path`, named by the settings' filename template
+pub fn get_output_filename(input_path: &str, output_dir: &str, settings: &AppSettings) -> AppResult<String> {
+    get_output_path(input_path, output_dir, settings, &OutputFormat::Txt)
+}
+
+/// Output paths handed to running jobs but not written yet, shared by every job in the process
+static RESERVED_OUTPUT_PATHS: once_cell::sync::Lazy<std::sync::Mutex<HashSet<PathBuf>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(HashSet::new()));
+
+fn reserved_output_paths() -> std::sync::MutexGuard<'static, HashSet<PathBuf>> {
+    RESERVED_OUTPUT_PATHS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// An output path held by one job until its file is written; released when dropped
+#[derive(Debug)]
+pub struct OutputPathReservation {
+    path: PathBuf,
+}
+
+impl OutputPathReservation {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for OutputPathReservation {
+    fn drop(&mut self) {
+        reserved_output_paths().remove(&self.path);
+    }
+}
+
+/// Like `get_output_filename`, but holds the path so concurrent jobs writing to the same
+/// directory get a disambiguated name instead of overwriting each other
+pub fn reserve_output_filename(input_path: &str, output_dir: &str, settings: &AppSettings) -> AppResult<OutputPathReservation> {
+    let name = render_output_filename(input_path, settings, chrono::Local::now())?;
+    let mut reserved = reserved_output_paths();
+    let path = PathBuf::from(free_output_path(Path::new(input_path), output_dir, &name, OutputFormat::Txt.extension(), &reserved)?);
+    reserved.insert(path.clone());
+    Ok(OutputPathReservation { path })
+}
+
+/// Get a free output path for the given format. The name rendered from
+/// `output_filename_template` is used when available, otherwise ` (1)`, ` (2)`, ... is
+/// appended until the name is unused.
+pub fn get_output_path(input_path: &str, output_dir: &str, settings: &AppSettings, format: &OutputFormat) -> AppResult<String> {
+    let name = render_output_filename(input_path, settings, chrono::Local::now())?;
+    free_output_path(Path::new(input_path), output_dir, &name, format.extension(), &reserved_output_paths())
+}
+
+fn free_output_path(
+    input_path: &Path,
+    output_dir: &str,
+    name: &str,
+    extension: &str,
+    reserved: &HashSet<PathBuf>,
+) -> AppResult<String> {
+    let actual_output_dir = output_directory_for_input(input_path, output_dir);
+
+    for suffix in 0..=MAX_OUTPUT_SUFFIX {
+        let output_filename = if suffix == 0 {
+            format!("{}.{}", name, extension)
+        } else {
+            format!("{} ({}).{}", name, suffix, extension)
+        };
+
+        let output_path = actual_output_dir.join(output_filename);
+        if !output_path.exists() && !reserved.contains(&output_path) {
+            return Ok(output_path.to_string_lossy().to_string());
+        }
+    }
+
+    Err(AppError::IoError(format!(
+        "No free output filename for '{}' in {}",
+        name,
+        actual_output_dir.display()
+    )))
+}
+
+fn output_directory_for_input(input_path: &Path, output_dir: &str) -> std::path::PathBuf {
     // Use same directory as input file if output_dir is empty (default behavior)
-    let actual_output_dir = if output_dir.is_empty() {
+    if output_dir.is_empty() {
         // Use the same directory as the input file
         input_path
             .parent()
             .unwrap_or_else(|| Path::new("."))
-            .to_string_lossy()
-            .to_string()
+            .to_path_buf()
     } else {
         // Use user-specified output directory
-        output_dir.to_string()
-    };
+        Path::new(output_dir).to_path_buf()
+    }
+}
 
-    let output_path = Path::new(&actual_output_dir).join(output_filename);
-    Ok(output_path.to_string_lossy().to_string())
+/// The newest output a previous run wrote for `input_path` in `output_format`, if it is at
+/// least as new as the input. Names rendered from `output_filename_template` on any date,
+/// with or without a ` (n)` suffix, count, as do the CLI's own timestamped names.
+pub fn existing_output_path(input_path: &str, output_dir: &str, settings: &AppSettings) -> Option<String> {
+    let input_path = Path::new(input_path.trim());
+    let stem = input_path.file_stem()?.to_str()?;
+    let pattern = output_name_pattern(stem, settings);
+    let input_modified = std::fs::metadata(input_path).and_then(|metadata| metadata.modified()).ok()?;
+
+    std::fs::read_dir(output_directory_for_input(input_path, output_dir.trim())).ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_str().is_some_and(|name| pattern.is_match(name)))
+        .filter_map(|entry| Some((entry.metadata().and_then(|metadata| metadata.modified()).ok()?, entry.path())))
+        .filter(|(output_modified, _)| *output_modified >= input_modified)
+        .max_by_key(|(output_modified, _)| *output_modified)
+        .map(|(_, output_path)| output_path.to_string_lossy().to_string())
+}
+
+/// Output file names that may belong to an input named `stem` under `settings`
+fn output_name_pattern(stem: &str, settings: &AppSettings) -> regex::Regex {
+    // Stand-ins for `{date}` and `{time}` that survive rendering, so any run's date matches
+    const DATE_MARKER: &str = "\u{E000}";
+    const TIME_MARKER: &str = "\u{E001}";
+
+    let template = settings.output_filename_template.replace("{date}", DATE_MARKER).replace("{time}", TIME_MARKER);
+    let rendered = render_filename_template(&template, stem, &settings.language, &settings.model_size, chrono::Local::now());
+    let rendered = regex::escape(&rendered)
+        .replace(DATE_MARKER, r"\d{4}-\d{2}-\d{2}")
+        .replace(TIME_MARKER, r"\d{6}");
+
+    regex::Regex::new(&format!(
+        r"^(?:{}(?: \(\d+\))?|{}_transcription_\d{{12}})\.{}$",
+        rendered,
+        regex::escape(&sanitize_filename(stem)),
+        settings.output_format.extension()
+    ))
+    .unwrap()
 }
 
 /// Check if a directory exists and is writable
@@ -200,6 +573,26 @@ pub fn validate_output_directory(dir_path: &str) -> AppResult<()> {
     }
 }
 
+/// Check a directory picked for output, with an error message meant for the user
+pub fn check_output_directory_writable(dir_path: &str) -> AppResult<()> {
+    let dir_path = dir_path.trim();
+    if dir_path.is_empty() {
+        return Err(AppError::ConfigError("Choose a folder to save transcriptions in".to_string()));
+    }
+
+    let path = Path::new(dir_path);
+    validate_output_directory(dir_path).map_err(|_| {
+        let reason = if !path.exists() {
+            "the folder doesn't exist"
+        } else if !path.is_dir() {
+            "it isn't a folder"
+        } else {
+            "the folder is read-only"
+        };
+        AppError::IoError(format!("Transcriptions can't be saved to {} because {}. Please choose another folder.", dir_path, reason))
+    })
+}
+
 /// Get audio file duration using basic file metadata (placeholder)
 pub fn get_audio_duration(file_path: &str) -> AppResult<Option<f64>> {
     // This is a placeholder implementation
@@ -212,6 +605,51 @@ pub fn get_audio_duration(file_path: &str) -> AppResult<Option<f64>> {
     Ok(None)
 }
 
+/// Read duration, sample rate, and channel count from the audio container without decoding
+pub fn probe_audio_metadata(file_path: &str) -> AppResult<AudioInfo> {
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let resolved_path = resolve_file_path(file_path);
+    let file_path = resolved_path.as_str();
+    validate_file_path(file_path)?;
+    validate_audio_format(file_path)?;
+
+    let file = std::fs::File::open(file_path)?;
+    let source = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = get_file_extension(file_path.trim()) {
+        hint.with_extension(&extension);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, source, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| AppError::UnsupportedFormat(format!("Could not read audio data from '{}': {}", file_path, e)))?;
+
+    let track = probed.format.default_track()
+        .ok_or_else(|| AppError::UnsupportedFormat(format!("No audio track found in '{}'", file_path)))?;
+    let params = &track.codec_params;
+
+    // Duration is unknown for streams without a frame count (e.g., raw AAC)
+    let duration = match (params.time_base, params.n_frames, params.sample_rate) {
+        (Some(time_base), Some(frames), _) => {
+            let time = time_base.calc_time(frames);
+            time.seconds as f64 + time.frac
+        }
+        (None, Some(frames), Some(sample_rate)) if sample_rate > 0 => frames as f64 / sample_rate as f64,
+        _ => 0.0,
+    };
+
+    Ok(AudioInfo {
+        duration,
+        sample_rate: params.sample_rate,
+        channels: params.channels.map(|channels| channels.count() as u32),
+    })
+}
+
 /// Batch validate multiple file paths
 pub fn validate_multiple_file_paths(paths: &[String]) -> Vec<AppResult<AudioFile>> {
     paths.iter()
@@ -219,6 +657,325 @@ pub fn validate_multiple_file_paths(paths: &[String]) -> Vec<AppResult<AudioFile
         .collect()
 }
 
+/// Deepest folder level visited by a recursive directory scan
+pub const MAX_SCAN_DEPTH: usize = 16;
+
+/// Collect supported audio files from a directory, descending into subfolders when `recursive`.
+/// Unreadable entries and files that fail validation are skipped.
+pub fn scan_directory_for_audio(dir_path: &str, recursive: bool) -> AppResult<Vec<AudioFile>> {
+    let dir = Path::new(dir_path.trim());
+    if !dir.exists() {
+        return Err(AppError::FileNotFound(format!("Directory not found: {}", dir_path)));
+    }
+    if !dir.is_dir() {
+        return Err(AppError::IoError(format!("{} is not a directory", dir_path)));
+    }
+
+    let max_depth = if recursive { MAX_SCAN_DEPTH } else { 1 };
+    let audio_files: Vec<AudioFile> = walkdir::WalkDir::new(dir)
+        .max_depth(max_depth)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                println!("⚠️ Skipping unreadable entry: {}", e);
+                None
+            }
+        })
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry.path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(is_supported_format)
+        })
+        .filter_map(|entry| create_audio_file(&entry.path().to_string_lossy()).ok())
+        .collect();
+
+    println!("📂 Found {} audio files in {}", audio_files.len(), dir_path);
+
+    Ok(audio_files)
+}
+
+/// Remove transcription outputs (`*_transcription*` files with one of `extensions`) from `dir`.
+/// Extensions default to every output format; other files are left alone.
+pub fn clear_transcription_outputs(dir: &Path, extensions: Option<&[String]>) -> AppResult<CacheClearReport> {
+    let mut report = CacheClearReport::default();
+    if !dir.exists() {
+        return Ok(report); // Directory doesn't exist, nothing to clear
+    }
+
+    let extensions: Vec<String> = match extensions {
+        Some(extensions) => extensions
+            .iter()
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .collect(),
+        None => OutputFormat::ALL.iter().map(|format| format.extension().to_string()).collect(),
+    };
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let is_transcription = path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.contains("_transcription"));
+        let has_extension = get_file_extension(&path.to_string_lossy())
+            .is_some_and(|ext| extensions.contains(&ext));
+
+        if is_transcription && has_extension {
+            let size = std::fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+            std::fs::remove_file(&path)?;
+            report.files_removed += 1;
+            report.bytes_freed += size;
+        }
+    }
+
+    println!("🧹 Removed {} files ({} bytes) from {}", report.files_removed, report.bytes_freed, dir.display());
+    Ok(report)
+}
+
+/// Directory the CLI runs in and leaves intermediate files behind
+pub fn work_directory() -> std::path::PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("SpeechToText")
+}
+
+/// Remove outputs an earlier run left for `base_name` in the CLI working directory and its
+/// `output` folder, so the output search can't pick them up. Other files' outputs are kept,
+/// since runs for them may still be reading them.
+pub fn clear_stale_outputs(work_dir: &Path, base_name: &str) -> AppResult<CacheClearReport> {
+    let mut report = CacheClearReport::default();
+    let prefix = format!("{}_transcription", base_name);
+    let plain_name = format!("{}.txt", base_name);
+
+    for dir in [work_dir.to_path_buf(), work_dir.join("output")] {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry?.path();
+            let is_stale = path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) || name == plain_name);
+            if is_stale && path.is_file() {
+                let size = std::fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+                std::fs::remove_file(&path)?;
+                report.files_removed += 1;
+                report.bytes_freed += size;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Files under `root` with their sizes, skipping the `excluded` subdirectory
+pub fn list_files_under(root: &Path, excluded: Option<&Path>) -> Vec<(String, u64)> {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| excluded != Some(entry.path()))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+            (entry.path().to_string_lossy().to_string(), size)
+        })
+        .collect()
+}
+
+/// Delete `paths`, all of which must be files inside `root`. Nothing is deleted if any
+/// path escapes `root` (e.g. through `..` or a symlink).
+pub fn delete_files_under(root: &Path, paths: &[String]) -> AppResult<CacheClearReport> {
+    let root = root.canonicalize()
+        .map_err(|e| AppError::FileNotFound(format!("{}: {}", root.display(), e)))?;
+
+    let mut targets = Vec::new();
+    for path in paths {
+        let Ok(resolved) = Path::new(path.trim()).canonicalize() else {
+            continue; // Already gone
+        };
+        if !resolved.starts_with(&root) || !resolved.is_file() {
+            return Err(AppError::ConfigError(format!(
+                "'{}' is not a file inside {}",
+                path,
+                root.display()
+            )));
+        }
+        targets.push(resolved);
+    }
+
+    let mut report = CacheClearReport::default();
+    for target in targets {
+        let size = std::fs::metadata(&target).map(|metadata| metadata.len()).unwrap_or(0);
+        std::fs::remove_file(&target)?;
+        report.files_removed += 1;
+        report.bytes_freed += size;
+    }
+
+    println!("🧹 Removed {} files ({} bytes) from {}", report.files_removed, report.bytes_freed, root.display());
+    Ok(report)
+}
+
+/// Sample rate Whisper resamples all input to
+pub const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+/// Write a short 16 kHz mono WAV tone, small enough to transcribe in a few seconds
+pub fn write_sample_wav(path: &Path, seconds: u32) -> AppResult<()> {
+    let frames = WHISPER_SAMPLE_RATE * seconds;
+    let data_len = frames * 2;
+
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    bytes.extend_from_slice(&WHISPER_SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(WHISPER_SAMPLE_RATE * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for frame in 0..frames {
+        let phase = 2.0 * std::f64::consts::PI * 440.0 * frame as f64 / WHISPER_SAMPLE_RATE as f64;
+        bytes.extend_from_slice(&((phase.sin() * 8000.0) as i16).to_le_bytes());
+    }
+
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Convert `input_path` to a 16 kHz mono WAV in `output_dir` with FFmpeg and return the new path
+pub async fn transcode_to_wav(input_path: &str, output_dir: &str) -> AppResult<String> {
+    let resolved_path = resolve_file_path(input_path);
+    let input_path = resolved_path.as_str();
+    validate_file_path(input_path)?;
+    if !crate::system::SystemIntegration::is_ffmpeg_available() {
+        return Err(AppError::FfmpegMissing("FFmpeg is required to convert audio files".to_string()));
+    }
+
+    let input = Path::new(input_path.trim());
+    let stem = input.file_stem().and_then(|stem| stem.to_str()).unwrap_or("audio");
+    std::fs::create_dir_all(output_dir)?;
+    let mut output = Path::new(output_dir).join(format!("{}.wav", stem));
+    // Never overwrite the input itself
+    if output == input {
+        output = Path::new(output_dir).join(format!("{}_16k.wav", stem));
+    }
+
+    println!("🎛️ Converting {} to {}", input.display(), output.display());
+    let result = tokio::process::Command::new("ffmpeg")
+        .args(["-hide_banner", "-loglevel", "error", "-y", "-i"])
+        .arg(input)
+        .args(["-ar", &WHISPER_SAMPLE_RATE.to_string(), "-ac", "1", "-c:a", "pcm_s16le"])
+        .arg(&output)
+        .output()
+        .await
+        .map_err(|e| AppError::FfmpegMissing(format!("Failed to run FFmpeg: {}", e)))?;
+
+    if !result.status.success() {
+        let _ = std::fs::remove_file(&output);
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(match crate::cli::classify_cli_failure(&stderr, result.status.code()) {
+            AppError::CliError(_) => AppError::AudioDecodeError(
+                stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("FFmpeg conversion failed").trim().to_string()
+            ),
+            error => error,
+        });
+    }
+
+    Ok(output.to_string_lossy().to_string())
+}
+
+/// Byte order mark written before UTF-8 text when `OutputEncoding::Utf8Bom` is selected
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Drop a leading byte order mark so text read back matches what was transcribed
+pub fn strip_bom(text: String) -> String {
+    match text.strip_prefix('\u{FEFF}') {
+        Some(stripped) => stripped.to_string(),
+        None => text,
+    }
+}
+
+/// Write `text` to `path` in the given encoding
+pub fn write_output_text(path: &str, text: &str, encoding: &OutputEncoding) -> AppResult<()> {
+    let mut bytes = Vec::with_capacity(UTF8_BOM.len() + text.len());
+    if *encoding == OutputEncoding::Utf8Bom && !text.starts_with('\u{FEFF}') {
+        bytes.extend_from_slice(UTF8_BOM);
+    }
+    bytes.extend_from_slice(text.as_bytes());
+
+    std::fs::write(path, bytes)
+        .map_err(|e| AppError::IoError(format!("Failed to write {}: {}", path, e)))
+}
+
+/// Re-encode a text file written by the CLI, which always writes plain UTF-8
+pub fn apply_output_encoding(path: &str, encoding: &OutputEncoding) -> AppResult<()> {
+    if *encoding == OutputEncoding::Utf8 {
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::IoError(format!("Failed to read {}: {}", path, e)))?;
+    write_output_text(path, &content, encoding)
+}
+
+/// Collapse runs of whitespace within lines, trim every line and drop leading and trailing
+/// blank lines. A blank line between paragraphs is kept, but only one.
+pub fn normalize_transcription(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        let previous_blank = lines.last().is_none_or(|previous| previous.is_empty());
+        if !line.is_empty() || !previous_blank {
+            lines.push(line);
+        }
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Separator used between merged transcriptions when none is given
+pub const DEFAULT_MERGE_SEPARATOR: &str = "\n\n";
+
+/// Join text files, in order, into `output_path`. Every input is read before anything is written.
+pub fn merge_text_files(paths: &[String], output_path: &str, separator: Option<&str>) -> AppResult<()> {
+    if paths.is_empty() {
+        return Err(AppError::ProcessingError("No transcriptions to merge".to_string()));
+    }
+
+    let mut parts = Vec::with_capacity(paths.len());
+    for path in paths {
+        let path = path.trim();
+        if !Path::new(path).is_file() {
+            return Err(AppError::FileNotFound(path.to_string()));
+        }
+        let content = std::fs::read_to_string(path)
+            .map(strip_bom)
+            .map_err(|e| AppError::IoError(format!("Failed to read {}: {}", path, e)))?;
+        // Trailing newlines would otherwise pile up around the separator
+        parts.push(content.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    if let Some(parent) = Path::new(output_path).parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(output_path, parts.join(separator.unwrap_or(DEFAULT_MERGE_SEPARATOR)))
+        .map_err(|e| AppError::IoError(format!("Failed to write {}: {}", output_path, e)))?;
+
+    println!("📎 Merged {} transcriptions into {}", paths.len(), output_path);
+    Ok(())
+}
+
 /// Check available disk space in output directory
 pub fn check_available_space(dir_path: &str) -> AppResult<u64> {
     let path = Path::new(dir_path);
@@ -232,6 +989,161 @@ pub fn check_available_space(dir_path: &str) -> AppResult<u64> {
     Ok(u64::MAX)
 }
 
+/// Processing seconds assumed for a file whose duration can't be read
+pub const FALLBACK_SECONDS_PER_FILE: f64 = 30.0;
+
+/// Audio minutes assumed for a file whose duration can't be read
+pub const FALLBACK_AUDIO_MINUTES: f64 = 5.0;
+
+/// Estimate the bytes a batch writes in `format` from the audio durations in seconds
+pub fn estimate_output_size(durations: &[Option<f64>], format: &OutputFormat) -> u64 {
+    let minutes: f64 = durations
+        .iter()
+        .map(|duration| match duration.filter(|d| *d > 0.0) {
+            Some(duration) => duration / 60.0,
+            None => FALLBACK_AUDIO_MINUTES,
+        })
+        .sum();
+    (minutes * format.estimated_bytes_per_minute() as f64).ceil() as u64
+}
+
+/// Estimate the seconds a batch takes from audio durations, the model's realtime factor
+/// and the number of files processed at once. A batch never finishes before its slowest file.
+pub fn estimate_batch_duration(durations: &[Option<f64>], model_size: &ModelSize, concurrency: usize) -> f64 {
+    let per_file: Vec<f64> = durations
+        .iter()
+        .map(|duration| match duration.filter(|d| *d > 0.0) {
+            Some(duration) => duration * model_size.realtime_factor(),
+            None => FALLBACK_SECONDS_PER_FILE,
+        })
+        .collect();
+
+    let parallel = concurrency.clamp(1, per_file.len().max(1)) as f64;
+    let longest = per_file.iter().cloned().fold(0.0, f64::max);
+    (per_file.iter().sum::<f64>() / parallel).max(longest)
+}
+
+/// Overall batch percentage with each file weighted by its audio duration, counting the file
+/// in progress (`current`: index and fraction done) by how far along it is. Files of unknown
+/// duration weigh the average known duration, so with no durations every file weighs the same.
+pub fn overall_batch_progress(durations: &[Option<f64>], completed: &[usize], current: Option<(usize, f64)>) -> f64 {
+    let known: Vec<f64> = durations.iter().flatten().copied().filter(|duration| *duration > 0.0).collect();
+    let default_weight = if known.is_empty() { 1.0 } else { known.iter().sum::<f64>() / known.len() as f64 };
+    let weight = |index: usize| durations[index].filter(|duration| *duration > 0.0).unwrap_or(default_weight);
+
+    let total: f64 = (0..durations.len()).map(weight).sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let done: f64 = completed.iter().filter(|&&index| index < durations.len()).map(|&index| weight(index)).sum::<f64>()
+        + current
+            .filter(|(index, _)| *index < durations.len())
+            .map(|(index, fraction)| weight(index) * fraction.clamp(0.0, 1.0))
+            .unwrap_or(0.0);
+    (done / total * 100.0).min(100.0)
+}
+
+/// Keeps the last `capacity` lines pushed, dropping the oldest
+#[derive(Debug, Clone)]
+pub struct RecentLines {
+    capacity: usize,
+    lines: std::collections::VecDeque<String>,
+}
+
+impl RecentLines {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, lines: std::collections::VecDeque::with_capacity(capacity) }
+    }
+
+    pub fn push(&mut self, line: impl Into<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line.into());
+    }
+
+    /// The last `count` lines, oldest first
+    pub fn tail(&self, count: usize) -> Vec<String> {
+        let skip = self.lines.len().saturating_sub(count);
+        self.lines.iter().skip(skip).cloned().collect()
+    }
+}
+
+/// Projects remaining processing time from files that already finished
+#[derive(Debug, Clone, Default)]
+pub struct EtaEstimator {
+    completed_files: usize,
+    total_processing_time: f64,
+    // Only files with a known audio duration contribute to the speed ratio
+    timed_audio_seconds: f64,
+    timed_processing_time: f64,
+}
+
+impl EtaEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a finished file with its wall-clock processing time and audio duration
+    pub fn record_file(&mut self, processing_time: f64, audio_duration: Option<f64>) {
+        self.completed_files += 1;
+        self.total_processing_time += processing_time;
+
+        if let Some(duration) = audio_duration.filter(|d| *d > 0.0) {
+            self.timed_audio_seconds += duration;
+            self.timed_processing_time += processing_time;
+        }
+    }
+
+    /// Number of files recorded so far
+    pub fn completed_files(&self) -> usize {
+        self.completed_files
+    }
+
+    /// Processing speed ratio (wall-time / audio-seconds), if any duration is known
+    pub fn speed_ratio(&self) -> Option<f64> {
+        if self.timed_audio_seconds > 0.0 {
+            Some(self.timed_processing_time / self.timed_audio_seconds)
+        } else {
+            None
+        }
+    }
+
+    /// Estimate the seconds left for the remaining files.
+    ///
+    /// `remaining_durations` starts with the file currently being processed, which is
+    /// `current_file_fraction` (0.0-1.0) done. Returns `None` until a file has completed.
+    pub fn estimate_remaining(&self, remaining_durations: &[Option<f64>], current_file_fraction: f64) -> Option<f64> {
+        if self.completed_files == 0 {
+            return None;
+        }
+
+        let average_time_per_file = self.total_processing_time / self.completed_files as f64;
+        let ratio = self.speed_ratio();
+
+        let remaining = remaining_durations
+            .iter()
+            .enumerate()
+            .map(|(index, duration)| {
+                let estimate = match (duration.filter(|d| *d > 0.0), ratio) {
+                    (Some(duration), Some(ratio)) => duration * ratio,
+                    _ => average_time_per_file,
+                };
+                if index == 0 {
+                    estimate * (1.0 - current_file_fraction.clamp(0.0, 1.0))
+                } else {
+                    estimate
+                }
+            })
+            .sum();
+
+        Some(remaining)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,6 +1172,29 @@ mod tests {
         assert!(validate_audio_format("test.txt").is_err());
     }
 
+    #[test]
+    fn test_validate_audio_format_from_contents() {
+        let temp_dir = tempdir().unwrap();
+        let wav = temp_dir.path().join("voice memo");
+        write_wav_fixture(&wav, 16000, 1, 1600);
+        let mp3 = temp_dir.path().join("exported");
+        let mut mp3_bytes = b"ID3\x04\x00\x00\x00\x00\x00\x00".to_vec();
+        mp3_bytes.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x64, 0x00, 0x00]);
+        std::fs::write(&mp3, mp3_bytes).unwrap();
+        let text = temp_dir.path().join("notes");
+        std::fs::write(&text, "not audio").unwrap();
+
+        assert_eq!(validate_audio_format(wav.to_str().unwrap()).unwrap(), "wav");
+        assert_eq!(validate_audio_format(mp3.to_str().unwrap()).unwrap(), "mp3");
+        assert!(matches!(validate_audio_format(text.to_str().unwrap()), Err(AppError::UnsupportedFormat(_))));
+        assert_eq!(create_audio_file(wav.to_str().unwrap()).unwrap().format, "wav");
+
+        // Unknown extensions fall back to the contents too
+        let renamed = temp_dir.path().join("meeting.audio");
+        std::fs::copy(&wav, &renamed).unwrap();
+        assert_eq!(validate_audio_format(renamed.to_str().unwrap()).unwrap(), "wav");
+    }
+
     #[test]
     fn test_format_file_size() {
         assert_eq!(format_file_size(512), "512 B");
@@ -275,6 +1210,15 @@ mod tests {
         assert_eq!(format_duration(30.5), "00:30");
     }
 
+    #[test]
+    fn test_realtime_factor() {
+        assert_eq!(realtime_factor(230.0, 100.0), Some(2.3));
+        assert_eq!(realtime_factor(30.0, 60.0), Some(0.5));
+        assert_eq!(realtime_factor(30.0, 0.0), None);
+        assert_eq!(realtime_factor(0.0, 12.0), None);
+        assert_eq!(realtime_factor(f64::NAN, 12.0), None);
+    }
+
     #[test]
     fn test_sanitize_filename() {
         assert_eq!(sanitize_filename("test/file.txt"), "test_file.txt");
@@ -286,7 +1230,7 @@ mod tests {
     fn test_create_audio_file() {
         let temp_dir = tempdir().unwrap();
         let file_path = temp_dir.path().join("test.m4a");
-        File::create(&file_path).unwrap();
+        std::fs::write(&file_path, b"audio").unwrap();
         
         let audio_file = create_audio_file(file_path.to_str().unwrap()).unwrap();
         assert_eq!(audio_file.name, "test.m4a");
@@ -294,13 +1238,345 @@ mod tests {
         assert!(matches!(audio_file.status, FileStatus::Pending));
     }
 
+    #[test]
+    fn test_composed_and_decomposed_paths_resolve_to_same_file() {
+        use unicode_normalization::UnicodeNormalization;
+
+        let temp_dir = tempdir().unwrap();
+        let composed_name: String = "회의 녹음.m4a".nfc().collect();
+        let decomposed_name: String = "회의 녹음.m4a".nfd().collect();
+        assert_ne!(composed_name, decomposed_name);
+
+        // Stored decomposed, as macOS does
+        let stored = temp_dir.path().join(&decomposed_name);
+        std::fs::write(&stored, b"audio").unwrap();
+        let composed_path = temp_dir.path().join(&composed_name).to_string_lossy().to_string();
+        let decomposed_path = stored.to_string_lossy().to_string();
+
+        assert_eq!(resolve_file_path(&composed_path), decomposed_path);
+        assert_eq!(resolve_file_path(&decomposed_path), decomposed_path);
+        assert!(validate_file_path(&composed_path).is_ok());
+
+        let from_composed = create_audio_file(&composed_path).unwrap();
+        let from_decomposed = create_audio_file(&decomposed_path).unwrap();
+        assert_eq!(from_composed.path, from_decomposed.path);
+        assert_eq!(from_composed.name, composed_name);
+    }
+
+    #[test]
+    fn test_max_file_size_limit() {
+        let temp_dir = tempdir().unwrap();
+        let under = temp_dir.path().join("under.wav");
+        let over = temp_dir.path().join("over.wav");
+        std::fs::write(&under, vec![0u8; 1024 * 1024]).unwrap();
+        std::fs::write(&over, vec![0u8; 1024 * 1024 + 1]).unwrap();
+        let under = under.to_str().unwrap();
+        let over = over.to_str().unwrap();
+
+        assert!(create_audio_file_with_limit(under, Some(1)).is_ok());
+        let error = create_audio_file_with_limit(over, Some(1)).unwrap_err();
+        assert!(matches!(error, AppError::FileTooLarge(_)));
+        assert!(error.to_string().contains("1 MB limit"), "{}", error);
+
+        // No limit accepts anything
+        assert!(create_audio_file_with_limit(over, None).is_ok());
+        assert!(create_audio_file(over).is_ok());
+    }
+
+    #[test]
+    fn test_create_audio_file_rejects_empty_file() {
+        let temp_dir = tempdir().unwrap();
+        let empty = temp_dir.path().join("empty.wav");
+        File::create(&empty).unwrap();
+
+        let error = create_audio_file(empty.to_str().unwrap()).unwrap_err();
+        assert!(matches!(error, AppError::AudioDecodeError(_)));
+        assert!(error.to_string().contains("empty (0 bytes)"), "{}", error);
+    }
+
+    #[test]
+    fn test_hash_file_is_stable() {
+        let temp_dir = tempdir().unwrap();
+        let first = temp_dir.path().join("first.m4a");
+        let copy = temp_dir.path().join("copy.m4a");
+        let other = temp_dir.path().join("other.m4a");
+        std::fs::write(&first, b"recording").unwrap();
+        std::fs::write(&copy, b"recording").unwrap();
+        std::fs::write(&other, b"different recording").unwrap();
+
+        let hash = hash_file(first.to_str().unwrap()).unwrap();
+        assert_eq!(hash, "3ebb153fb24e4411400e94a9a92b0ec458c3a8473e51e03cd37d4a34c99dfda6");
+        assert_eq!(hash, hash_file(first.to_str().unwrap()).unwrap());
+        assert_eq!(hash, hash_file(copy.to_str().unwrap()).unwrap());
+        assert_ne!(hash, hash_file(other.to_str().unwrap()).unwrap());
+
+        // Only hashed when asked for
+        assert!(create_audio_file(first.to_str().unwrap()).unwrap().hash.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transcode_to_wav() {
+        if !crate::system::SystemIntegration::is_ffmpeg_available() {
+            println!("Skipping: FFmpeg is not installed");
+            return;
+        }
+
+        let temp_dir = tempdir().unwrap();
+        let input = temp_dir.path().join("stereo.wav");
+        write_wav_fixture(&input, 44100, 2, 44100);
+
+        // Same directory as the input, so the output must not overwrite it
+        let output = transcode_to_wav(input.to_str().unwrap(), temp_dir.path().to_str().unwrap()).await.unwrap();
+        assert!(output.ends_with("stereo_16k.wav"));
+
+        let info = probe_audio_metadata(&output).unwrap();
+        assert_eq!(info.sample_rate, Some(WHISPER_SAMPLE_RATE));
+        assert_eq!(info.channels, Some(1));
+        assert!((info.duration - 1.0).abs() < 0.05);
+
+        let missing = temp_dir.path().join("missing.ogg");
+        assert!(transcode_to_wav(missing.to_str().unwrap(), temp_dir.path().to_str().unwrap()).await.is_err());
+    }
+
+    #[test]
+    fn test_merge_text_files() {
+        let temp_dir = tempdir().unwrap();
+        let paths: Vec<String> = ["part1", "part2", "part3"]
+            .iter()
+            .map(|name| {
+                let path = temp_dir.path().join(format!("{}_transcription.txt", name));
+                std::fs::write(&path, format!("{} text\n", name)).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let merged = temp_dir.path().join("merged.txt");
+        let merged = merged.to_str().unwrap();
+        merge_text_files(&paths, merged, None).unwrap();
+        assert_eq!(std::fs::read_to_string(merged).unwrap(), "part1 text\n\npart2 text\n\npart3 text");
+
+        let reversed: Vec<String> = paths.iter().rev().cloned().collect();
+        merge_text_files(&reversed, merged, Some("\n---\n")).unwrap();
+        assert_eq!(std::fs::read_to_string(merged).unwrap(), "part3 text\n---\npart2 text\n---\npart1 text");
+
+        let mut missing = paths.clone();
+        missing.push(temp_dir.path().join("missing.txt").to_string_lossy().to_string());
+        assert!(matches!(merge_text_files(&missing, merged, None), Err(AppError::FileNotFound(_))));
+        assert!(merge_text_files(&[], merged, None).is_err());
+    }
+
+    #[test]
+    fn test_write_output_text_with_bom() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("회의_transcription.txt");
+        let path = path.to_str().unwrap();
+
+        write_output_text(path, "안녕하세요", &OutputEncoding::Utf8Bom).unwrap();
+        let bytes = std::fs::read(path).unwrap();
+        assert_eq!(&bytes[..3], &[0xEF, 0xBB, 0xBF]);
+        assert_eq!(strip_bom(std::fs::read_to_string(path).unwrap()), "안녕하세요");
+
+        // Re-encoding never doubles the mark
+        apply_output_encoding(path, &OutputEncoding::Utf8Bom).unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), bytes);
+
+        write_output_text(path, "안녕하세요", &OutputEncoding::Utf8).unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), "안녕하세요".as_bytes());
+    }
+
+    #[test]
+    fn test_normalize_transcription() {
+        assert_eq!(normalize_transcription("hello   world \t again"), "hello world again");
+        assert_eq!(normalize_transcription("  first line  \n\tsecond line\t"), "first line\nsecond line");
+        assert_eq!(normalize_transcription("\n\n  \nbody\n \n\n"), "body");
+        assert_eq!(normalize_transcription("one\n\n\n   \ntwo\n\nthree"), "one\n\ntwo\n\nthree");
+        assert_eq!(normalize_transcription("line\r\nnext\r\n"), "line\nnext");
+        assert_eq!(normalize_transcription(" \n\t"), "");
+    }
+
     #[test]
     fn test_get_output_filename() {
-        let result = get_output_filename("/path/to/audio.m4a", "/output").unwrap();
-        assert!(result.ends_with("audio.txt"));
+        let result = get_output_filename("/path/to/audio.m4a", "/output", &AppSettings::default()).unwrap();
+        assert!(result.ends_with("audio_transcription.txt"));
         assert!(result.starts_with("/output"));
     }
 
+    #[test]
+    fn test_reserve_output_filename() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir = temp_dir.path().to_str().unwrap();
+        let settings = AppSettings::default();
+
+        let first = reserve_output_filename("/a/meeting.m4a", output_dir, &settings).unwrap();
+        let second = reserve_output_filename("/b/meeting.m4a", output_dir, &settings).unwrap();
+        assert_eq!(first.path(), temp_dir.path().join("meeting_transcription.txt"));
+        assert_eq!(second.path(), temp_dir.path().join("meeting_transcription (1).txt"));
+        assert_eq!(
+            get_output_filename("/c/meeting.m4a", output_dir, &settings).unwrap(),
+            temp_dir.path().join("meeting_transcription (2).txt").to_string_lossy()
+        );
+
+        // Dropping a reservation releases its path
+        drop(first);
+        let third = reserve_output_filename("/c/meeting.m4a", output_dir, &settings).unwrap();
+        assert_eq!(third.path(), temp_dir.path().join("meeting_transcription.txt"));
+    }
+
+    #[test]
+    fn test_render_output_filename_template() {
+        use chrono::TimeZone;
+        let now = chrono::Local.with_ymd_and_hms(2026, 3, 14, 9, 5, 7).unwrap();
+        let settings = |template: &str| AppSettings {
+            output_filename_template: template.to_string(),
+            language: "ko".to_string(),
+            model_size: ModelSize::Small,
+            ..AppSettings::default()
+        };
+
+        let render = |template: &str, input: &str| render_output_filename(input, &settings(template), now).unwrap();
+        assert_eq!(render("{date}_{name}_{lang}", "/audio/meeting.m4a"), "2026-03-14_meeting_ko");
+        assert_eq!(render("{name}-{time}-{model}", "/audio/meeting.m4a"), "meeting-090507-small");
+
+        // Illegal characters from the template or the input name are replaced
+        assert_eq!(render("{lang}/{name}: <draft>?", "/audio/a|b.m4a"), "ko_a_b_ _draft__");
+
+        // Empty templates, or ones that render to nothing usable, use the default
+        assert_eq!(render("", "/audio/meeting.m4a"), "meeting_transcription");
+        assert_eq!(render("  ..  ", "/audio/meeting.m4a"), "meeting_transcription");
+
+        let temp_dir = tempdir().unwrap();
+        let path = get_output_filename("/audio/meeting.m4a", temp_dir.path().to_str().unwrap(), &settings("{name}_{lang}")).unwrap();
+        assert_eq!(path, temp_dir.path().join("meeting_ko.txt").to_string_lossy());
+    }
+
+    #[test]
+    fn test_rename_outputs() {
+        let temp_dir = tempdir().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            path.to_string_lossy().to_string()
+        };
+        let meeting = write("meeting_transcription.txt", "hello");
+        write("meeting_transcription.json", r#"{"language": "en", "segments": []}"#);
+        let lecture = write("lecture_transcription_202601011200.txt", "안녕하세요");
+        let taken = write("memo_transcription.txt", "memo");
+        write("memo_ko.txt", "already here");
+
+        let settings = AppSettings { language: "ko".to_string(), ..AppSettings::default() };
+        let renamed = rename_outputs(&[meeting.clone(), lecture.clone(), taken.clone()], "{name}_{lang}", &settings);
+
+        let expected_path = |name: &str| temp_dir.path().join(name).to_string_lossy().to_string();
+        assert_eq!(renamed, vec![
+            (meeting, expected_path("meeting_en.txt")),
+            (lecture, expected_path("lecture_ko.txt")),
+        ]);
+        assert!(temp_dir.path().join("meeting_en.json").exists());
+        assert_eq!(std::fs::read_to_string(expected_path("lecture_ko.txt")).unwrap(), "안녕하세요");
+
+        // The collision was left alone
+        assert_eq!(std::fs::read_to_string(&taken).unwrap(), "memo");
+        assert_eq!(std::fs::read_to_string(expected_path("memo_ko.txt")).unwrap(), "already here");
+    }
+
+    #[test]
+    fn test_get_output_path_increments_suffix() {
+        let temp_dir = tempdir().unwrap();
+        let output_dir = temp_dir.path().to_str().unwrap();
+
+        let expected = ["talk_transcription.srt", "talk_transcription (1).srt", "talk_transcription (2).srt"];
+        for name in expected {
+            let path = get_output_path("/recordings/talk.m4a", output_dir, &AppSettings::default(), &OutputFormat::Srt).unwrap();
+            assert_eq!(path, temp_dir.path().join(name).to_string_lossy());
+            File::create(&path).unwrap();
+        }
+
+        // Other formats don't collide with the files above
+        let path = get_output_path("/recordings/talk.m4a", output_dir, &AppSettings::default(), &OutputFormat::Txt).unwrap();
+        assert!(path.ends_with("talk_transcription.txt"));
+
+        // The filename template applies to every format
+        let settings = AppSettings { output_filename_template: "{name}_{lang}".to_string(), ..AppSettings::default() };
+        let path = get_output_path("/recordings/talk.m4a", output_dir, &settings, &OutputFormat::Srt).unwrap();
+        assert_eq!(path, temp_dir.path().join("talk_ko.srt").to_string_lossy());
+    }
+
+    #[test]
+    fn test_get_output_path_per_directory_mode() {
+        use crate::models::{AppSettings, OutputDirectoryMode};
+
+        let temp_dir = tempdir().unwrap();
+        let source_dir = temp_dir.path().join("recordings");
+        std::fs::create_dir(&source_dir).unwrap();
+        let input = source_dir.join("talk.m4a").to_string_lossy().to_string();
+        let output_path = |settings: &AppSettings| {
+            get_output_path(&input, &settings.output_directory_for(&input), settings, &OutputFormat::Txt).unwrap()
+        };
+
+        let absolute = AppSettings { output_directory: temp_dir.path().to_string_lossy().to_string(), ..AppSettings::default() };
+        assert_eq!(output_path(&absolute), temp_dir.path().join("talk_transcription.txt").to_string_lossy());
+
+        let beside_input = AppSettings { output_directory_mode: OutputDirectoryMode::SameAsInput, ..absolute.clone() };
+        assert_eq!(output_path(&beside_input), source_dir.join("talk_transcription.txt").to_string_lossy());
+
+        let relative = AppSettings {
+            output_directory: "transcripts".to_string(),
+            output_directory_mode: OutputDirectoryMode::RelativeTo(temp_dir.path().to_string_lossy().to_string()),
+            ..AppSettings::default()
+        };
+        assert_eq!(output_path(&relative), temp_dir.path().join("transcripts").join("talk_transcription.txt").to_string_lossy());
+    }
+
+    #[test]
+    fn test_existing_output_path() {
+        let temp_dir = tempdir().unwrap();
+        let input = temp_dir.path().join("talk.m4a");
+        std::fs::write(&input, b"audio").unwrap();
+        let input = input.to_string_lossy().to_string();
+        let output_dir = temp_dir.path().to_string_lossy().to_string();
+        let settings = AppSettings::default();
+        assert!(existing_output_path(&input, &output_dir, &settings).is_none());
+
+        let output = temp_dir.path().join("talk_transcription.txt");
+        std::fs::write(&output, "text").unwrap();
+        assert_eq!(existing_output_path(&input, &output_dir, &settings), Some(output.to_string_lossy().to_string()));
+        assert_eq!(existing_output_path(&input, "", &settings), Some(output.to_string_lossy().to_string()));
+
+        // Other formats need their own output
+        let as_srt = AppSettings { output_format: OutputFormat::Srt, ..settings.clone() };
+        assert!(existing_output_path(&input, &output_dir, &as_srt).is_none());
+
+        // An input changed after its output was written needs transcribing again
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options().write(true).open(&input).unwrap().set_modified(later).unwrap();
+        assert!(existing_output_path(&input, &output_dir, &settings).is_none());
+    }
+
+    #[test]
+    fn test_existing_output_path_matches_templated_and_timestamped_names() {
+        let temp_dir = tempdir().unwrap();
+        let input = temp_dir.path().join("talk.m4a");
+        std::fs::write(&input, b"audio").unwrap();
+        let input = input.to_string_lossy().to_string();
+        let output_dir = temp_dir.path().to_string_lossy().to_string();
+        let settings = AppSettings { output_filename_template: "{date}_{name}_{lang}".to_string(), ..AppSettings::default() };
+
+        // Another input's output and unrelated files don't count
+        std::fs::write(temp_dir.path().join("2026-01-05_talkshow_ko.txt"), "other").unwrap();
+        std::fs::write(temp_dir.path().join("talk_notes.txt"), "notes").unwrap();
+        assert!(existing_output_path(&input, &output_dir, &settings).is_none());
+
+        // Each output is newer than the last, so each one becomes the match
+        let names = ["2026-01-05_talk_ko (1).txt", "talk_transcription_202601051200.txt", "2025-12-31_talk_ko.txt"];
+        for (minutes, name) in names.into_iter().enumerate() {
+            let output = temp_dir.path().join(name);
+            std::fs::write(&output, "text").unwrap();
+            let modified = std::time::SystemTime::now() + std::time::Duration::from_secs(60 * (minutes as u64 + 1));
+            std::fs::File::options().write(true).open(&output).unwrap().set_modified(modified).unwrap();
+            assert_eq!(existing_output_path(&input, &output_dir, &settings), Some(output.to_string_lossy().to_string()));
+        }
+    }
+
     #[test]
     fn test_validate_output_directory() {
         let temp_dir = tempdir().unwrap();
@@ -313,6 +1589,33 @@ mod tests {
         assert!(validate_output_directory("/nonexistent/path").is_err());
     }
 
+    #[test]
+    fn test_check_output_directory_writable() {
+        let temp_dir = tempdir().unwrap();
+        assert!(check_output_directory_writable(temp_dir.path().to_str().unwrap()).is_ok());
+        assert!(!temp_dir.path().join(".write_test").exists());
+
+        let missing = check_output_directory_writable("/nonexistent/path").unwrap_err();
+        assert!(missing.to_string().contains("doesn't exist"), "{}", missing);
+        assert!(matches!(check_output_directory_writable("  "), Err(AppError::ConfigError(_))));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let read_only = temp_dir.path().join("read-only");
+            std::fs::create_dir(&read_only).unwrap();
+            std::fs::set_permissions(&read_only, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+            // Permissions don't apply to root, so only check when they are enforced
+            if std::fs::File::create(read_only.join("probe")).is_err() {
+                let error = check_output_directory_writable(read_only.to_str().unwrap()).unwrap_err();
+                assert!(error.to_string().contains("read-only"), "{}", error);
+            }
+            std::fs::set_permissions(&read_only, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+    }
+
     #[test]
     fn test_get_audio_duration() {
         let temp_dir = tempdir().unwrap();
@@ -323,13 +1626,202 @@ mod tests {
         assert!(duration.is_none()); // Placeholder implementation returns None
     }
 
+    /// Write a 16-bit PCM WAV file filled with silence
+    fn write_wav_fixture(path: &Path, sample_rate: u32, channels: u16, frames: u32) {
+        let data_len = frames * channels as u32 * 2;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        bytes.resize(bytes.len() + data_len as usize, 0);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    /// Write a 16-bit stereo FLAC file: STREAMINFO plus one frame of silence
+    fn write_flac_fixture(path: &Path, sample_rate: u32, frames: u64) {
+        let mut bytes = b"fLaC".to_vec();
+        // Last metadata block, type STREAMINFO, 34 bytes long
+        bytes.extend_from_slice(&[0x80, 0x00, 0x00, 34]);
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&4096u16.to_be_bytes());
+        bytes.extend_from_slice(&[0; 6]);
+        // 20-bit sample rate, 3-bit channels - 1, 5-bit bits per sample - 1, 36-bit total samples
+        let packed = ((sample_rate as u64) << 44) | (1u64 << 41) | (15u64 << 36) | frames;
+        bytes.extend_from_slice(&packed.to_be_bytes());
+        bytes.extend_from_slice(&[0; 16]);
+        // 4096-sample frame with two CONSTANT subframes, followed by its CRC-16
+        bytes.extend_from_slice(&[
+            0xFF, 0xF8, 0xC0, 0x10, 0x00, 0x50, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x70, 0x70,
+        ]);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_probe_audio_metadata_wav() {
+        let temp_dir = tempdir().unwrap();
+
+        let mono = temp_dir.path().join("mono.wav");
+        write_wav_fixture(&mono, 16000, 1, 16000);
+        let info = probe_audio_metadata(mono.to_str().unwrap()).unwrap();
+        assert!((info.duration - 1.0).abs() < 1e-6);
+        assert_eq!(info.sample_rate, Some(16000));
+        assert_eq!(info.channels, Some(1));
+
+        let stereo = temp_dir.path().join("stereo.wav");
+        write_wav_fixture(&stereo, 44100, 2, 22050);
+        let info = probe_audio_metadata(stereo.to_str().unwrap()).unwrap();
+        assert!((info.duration - 0.5).abs() < 1e-6);
+        assert_eq!(info.sample_rate, Some(44100));
+        assert_eq!(info.channels, Some(2));
+    }
+
+    #[test]
+    fn test_write_sample_wav() {
+        let temp_dir = tempdir().unwrap();
+        let sample = temp_dir.path().join("sample.wav");
+        write_sample_wav(&sample, 2).unwrap();
+
+        let info = probe_audio_metadata(sample.to_str().unwrap()).unwrap();
+        assert!((info.duration - 2.0).abs() < 1e-6);
+        assert_eq!(info.sample_rate, Some(WHISPER_SAMPLE_RATE));
+        assert_eq!(info.channels, Some(1));
+    }
+
+    #[test]
+    fn test_probe_audio_metadata_flac() {
+        let temp_dir = tempdir().unwrap();
+        let flac = temp_dir.path().join("speech.flac");
+        write_flac_fixture(&flac, 48000, 96000);
+
+        let info = probe_audio_metadata(flac.to_str().unwrap()).unwrap();
+        assert!((info.duration - 2.0).abs() < 1e-6);
+        assert_eq!(info.sample_rate, Some(48000));
+        assert_eq!(info.channels, Some(2));
+    }
+
+    #[test]
+    fn test_probe_audio_metadata_errors() {
+        let temp_dir = tempdir().unwrap();
+
+        let corrupt = temp_dir.path().join("corrupt.mp3");
+        std::fs::write(&corrupt, b"this is not audio").unwrap();
+        assert!(matches!(probe_audio_metadata(corrupt.to_str().unwrap()), Err(AppError::UnsupportedFormat(_))));
+
+        let text = temp_dir.path().join("notes.txt");
+        std::fs::write(&text, b"hello").unwrap();
+        assert!(matches!(probe_audio_metadata(text.to_str().unwrap()), Err(AppError::UnsupportedFormat(_))));
+
+        assert!(matches!(probe_audio_metadata("/nonexistent/file.wav"), Err(AppError::FileNotFound(_))));
+    }
+
+    #[test]
+    fn test_scan_directory_for_audio() {
+        let temp_dir = tempdir().unwrap();
+        let nested = temp_dir.path().join("day1").join("morning");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        std::fs::write(temp_dir.path().join("intro.m4a"), b"audio").unwrap();
+        File::create(temp_dir.path().join("notes.txt")).unwrap();
+        std::fs::write(temp_dir.path().join("day1").join("talk.MP3"), b"audio").unwrap();
+        std::fs::write(nested.join("standup.wav"), b"audio").unwrap();
+        File::create(nested.join("cover.jpg")).unwrap();
+
+        let dir_path = temp_dir.path().to_str().unwrap();
+
+        let top_level = scan_directory_for_audio(dir_path, false).unwrap();
+        let names: Vec<_> = top_level.iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(names, vec!["intro.m4a"]);
+
+        let all = scan_directory_for_audio(dir_path, true).unwrap();
+        let mut names: Vec<_> = all.iter().map(|file| file.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["intro.m4a", "standup.wav", "talk.MP3"]);
+
+        assert!(scan_directory_for_audio("/nonexistent/dir", true).is_err());
+        assert!(scan_directory_for_audio(temp_dir.path().join("intro.m4a").to_str().unwrap(), false).is_err());
+    }
+
+    #[test]
+    fn test_list_and_delete_files_under_root() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().join("SpeechToText");
+        let cache = root.join("transcriptions");
+        std::fs::create_dir_all(root.join("transcoded")).unwrap();
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(root.join("talk_preprocessed.wav"), vec![0u8; 100]).unwrap();
+        std::fs::write(root.join("transcoded").join("talk.wav"), vec![0u8; 50]).unwrap();
+        std::fs::write(cache.join("entry.json"), "{}").unwrap();
+        let outside = temp_dir.path().join("keep.txt");
+        std::fs::write(&outside, "outside").unwrap();
+
+        let mut listed = list_files_under(&root, Some(&cache));
+        listed.sort();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed.iter().map(|(_, size)| size).sum::<u64>(), 150);
+        assert!(listed.iter().all(|(path, _)| path.starts_with(&*root.to_string_lossy())));
+
+        // Paths that escape the root are refused and nothing is deleted
+        let traversal = root.join("..").join("keep.txt").to_string_lossy().to_string();
+        let paths: Vec<String> = listed.iter().map(|(path, _)| path.clone()).collect();
+        let mut with_traversal = paths.clone();
+        with_traversal.push(traversal);
+        assert!(matches!(delete_files_under(&root, &with_traversal), Err(AppError::ConfigError(_))));
+        assert!(outside.exists());
+        assert_eq!(list_files_under(&root, Some(&cache)).len(), 2);
+
+        let report = delete_files_under(&root, &paths).unwrap();
+        assert_eq!(report, CacheClearReport { files_removed: 2, bytes_freed: 150 });
+        assert!(list_files_under(&root, Some(&cache)).is_empty());
+        assert!(cache.join("entry.json").exists());
+
+        // Already deleted files are skipped
+        assert_eq!(delete_files_under(&root, &paths).unwrap().files_removed, 0);
+    }
+
+    #[test]
+    fn test_clear_transcription_outputs() {
+        let temp_dir = tempdir().unwrap();
+        let write = |name: &str, size: usize| std::fs::write(temp_dir.path().join(name), vec![b'x'; size]).unwrap();
+
+        write("a_transcription.txt", 10);
+        write("a_transcription.srt", 20);
+        write("b_transcription_202601011200.json", 30);
+        write("c_transcription (1).vtt", 40);
+        write("notes.txt", 50);
+        write("recording.m4a", 60);
+        std::fs::create_dir(temp_dir.path().join("d_transcription.txt")).unwrap();
+
+        let srt_only = vec![".SRT".to_string()];
+        let report = clear_transcription_outputs(temp_dir.path(), Some(&srt_only)).unwrap();
+        assert_eq!(report, CacheClearReport { files_removed: 1, bytes_freed: 20 });
+
+        let report = clear_transcription_outputs(temp_dir.path(), None).unwrap();
+        assert_eq!(report, CacheClearReport { files_removed: 3, bytes_freed: 80 });
+
+        assert!(temp_dir.path().join("notes.txt").exists());
+        assert!(temp_dir.path().join("recording.m4a").exists());
+        assert!(temp_dir.path().join("d_transcription.txt").is_dir());
+
+        let report = clear_transcription_outputs(&temp_dir.path().join("missing"), None).unwrap();
+        assert_eq!(report, CacheClearReport::default());
+    }
+
     #[test]
     fn test_validate_multiple_file_paths() {
         let temp_dir = tempdir().unwrap();
         let file1 = temp_dir.path().join("test1.m4a");
         let file2 = temp_dir.path().join("test2.wav");
-        File::create(&file1).unwrap();
-        File::create(&file2).unwrap();
+        std::fs::write(&file1, b"audio").unwrap();
+        std::fs::write(&file2, b"audio").unwrap();
         
         let paths = vec![
             file1.to_string_lossy().to_string(),
@@ -344,6 +1836,92 @@ mod tests {
         assert!(results[2].is_err());
     }
 
+    #[test]
+    fn test_eta_estimator_tightens_as_files_finish() {
+        // (audio seconds, wall-clock seconds) per file; the first file is an outlier
+        let files = [(60.0, 45.0), (120.0, 54.0), (60.0, 30.0), (120.0, 60.0)];
+        let mut estimator = EtaEstimator::new();
+        let durations: Vec<Option<f64>> = files.iter().map(|(d, _)| Some(*d)).collect();
+
+        assert!(estimator.estimate_remaining(&durations, 0.0).is_none());
+
+        let mut errors = Vec::new();
+        for (index, (duration, processing_time)) in files.iter().enumerate().take(3) {
+            estimator.record_file(*processing_time, Some(*duration));
+
+            let estimate = estimator.estimate_remaining(&durations[index + 1..], 0.0).unwrap();
+            let actual: f64 = files[index + 1..].iter().map(|(_, t)| t).sum();
+            errors.push((estimate - actual).abs());
+        }
+
+        assert!(errors[1] < errors[0]);
+        assert!(errors[2] < errors[1]);
+    }
+
+    #[test]
+    fn test_eta_estimator_without_durations() {
+        let mut estimator = EtaEstimator::new();
+        estimator.record_file(20.0, None);
+        estimator.record_file(40.0, None);
+
+        assert!(estimator.speed_ratio().is_none());
+        // Average of 30s per file, current file half done
+        assert_eq!(estimator.estimate_remaining(&[None, None], 0.5), Some(45.0));
+    }
+
+    #[test]
+    fn test_estimate_batch_duration() {
+        let durations = [Some(60.0), Some(120.0), Some(600.0), None];
+        let models = [ModelSize::Tiny, ModelSize::Base, ModelSize::Small, ModelSize::Medium, ModelSize::Large];
+
+        // Larger models take longer for the same audio
+        let estimates: Vec<f64> = models.iter().map(|model| estimate_batch_duration(&durations, model, 1)).collect();
+        assert!(estimates.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", estimates);
+
+        // Known durations scale by the realtime factor; unknown ones use the fallback
+        assert_eq!(estimate_batch_duration(&[Some(600.0)], &ModelSize::Large, 1), 900.0);
+        assert_eq!(estimate_batch_duration(&[None, None], &ModelSize::Tiny, 1), 2.0 * FALLBACK_SECONDS_PER_FILE);
+
+        // Concurrency divides the total but can't beat the longest file
+        assert_eq!(estimate_batch_duration(&[Some(100.0), Some(100.0)], &ModelSize::Large, 2), 150.0);
+        assert_eq!(estimate_batch_duration(&[Some(1000.0), Some(10.0)], &ModelSize::Large, 4), 1500.0);
+        assert_eq!(estimate_batch_duration(&[], &ModelSize::Base, 2), 0.0);
+    }
+
+    #[test]
+    fn test_overall_batch_progress() {
+        // Without durations every file weighs the same
+        let unknown = [None; 4];
+        assert_eq!(overall_batch_progress(&unknown, &[], Some((0, 0.0))), 0.0);
+        assert_eq!(overall_batch_progress(&unknown, &[0], Some((1, 0.5))), 37.5);
+        assert_eq!(overall_batch_progress(&unknown, &[0, 1, 2, 3], None), 100.0);
+        assert_eq!(overall_batch_progress(&unknown, &[0, 1, 2], Some((3, 2.0))), 100.0);
+        assert_eq!(overall_batch_progress(&[], &[], Some((0, 0.5))), 0.0);
+
+        // A 300 second file moves the bar three times as far as a 100 second one
+        let durations = [Some(100.0), Some(300.0)];
+        assert_eq!(overall_batch_progress(&durations, &[0], Some((1, 0.0))), 25.0);
+        assert_eq!(overall_batch_progress(&durations, &[1], Some((0, 0.0))), 75.0);
+        assert_eq!(overall_batch_progress(&durations, &[0], Some((1, 0.5))), 62.5);
+
+        // Unknown durations count as the average of the known ones
+        assert_eq!(overall_batch_progress(&[Some(10.0), Some(30.0), None, None], &[2], None), 25.0);
+    }
+
+    #[test]
+    fn test_estimate_output_size() {
+        let durations = [Some(600.0), Some(90.0), None];
+        let estimates: Vec<u64> = OutputFormat::ALL.iter().map(|format| estimate_output_size(&durations, format)).collect();
+        let (txt, srt, vtt, json) = (estimates[0], estimates[1], estimates[2], estimates[3]);
+        assert!(json > srt && json > vtt, "{:?}", estimates);
+        assert!(srt > txt && vtt > txt, "{:?}", estimates);
+
+        // Ten minutes of text, plus the fallback length for unknown durations
+        assert_eq!(estimate_output_size(&[Some(600.0)], &OutputFormat::Txt), 1500);
+        assert_eq!(estimate_output_size(&[None], &OutputFormat::Txt), 750);
+        assert_eq!(estimate_output_size(&[], &OutputFormat::Json), 0);
+    }
+
     #[test]
     fn test_check_available_space() {
         let temp_dir = tempdir().unwrap();
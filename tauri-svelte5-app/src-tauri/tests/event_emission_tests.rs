@@ -0,0 +1,162 @@
+use std::sync::mpsc;
+use std::time::Duration;
+
+use tauri::Listener;
+use tauri_gui_app_lib::error::AppError;
+use tauri_gui_app_lib::events;
+use tauri_gui_app_lib::models::TranscriptionResult;
+use tauri_gui_app_lib::{emit_file_completed, emit_file_error, emit_file_skipped, emit_low_confidence, emit_no_speech};
+
+mod common;
+
+fn sample_result(path: &str) -> TranscriptionResult {
+    TranscriptionResult {
+        transcribed_text: "안녕하세요".to_string(),
+        ..common::sample_result(path)
+    }
+}
+
+#[test]
+fn test_file_completed_event_fires_once() {
+    let app = tauri::test::mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen(events::FILE_COMPLETED, move |event| {
+        let _ = tx.send(event.payload().to_string());
+    });
+
+    emit_file_completed(app.handle(), &sample_result("/audio/meeting.m4a"));
+
+    let payload = rx.recv_timeout(Duration::from_secs(1)).expect("file-completed should be emitted");
+    let result: TranscriptionResult = serde_json::from_str(&payload).unwrap();
+    assert_eq!(result.original_file.path, "/audio/meeting.m4a");
+    assert_eq!(result.transcribed_text, "안녕하세요");
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+}
+
+#[test]
+fn test_file_error_event_payload() {
+    let app = tauri::test::mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen(events::FILE_ERROR, move |event| {
+        let _ = tx.send(event.payload().to_string());
+    });
+
+    let error = AppError::FileNotFound("/audio/missing.m4a".to_string());
+    emit_file_error(app.handle(), "/audio/missing.m4a", &error, Some("/logs/job-1.log"));
+
+    let payload = rx.recv_timeout(Duration::from_secs(1)).expect("file-error should be emitted");
+    let event: serde_json::Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(event["file_path"], "/audio/missing.m4a");
+    assert_eq!(event["error"], error.to_string());
+    assert_eq!(event["log_path"], "/logs/job-1.log");
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+}
+
+#[test]
+fn test_file_skipped_event_payload() {
+    let app = tauri::test::mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen(events::FILE_SKIPPED, move |event| {
+        let _ = tx.send(event.payload().to_string());
+    });
+
+    emit_file_skipped(app.handle(), "/audio/meeting.m4a", Some("/out/meeting_transcription.txt"));
+
+    let payload = rx.recv_timeout(Duration::from_secs(1)).expect("file-skipped should be emitted");
+    let event: serde_json::Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(event["file_path"], "/audio/meeting.m4a");
+    assert_eq!(event["output_path"], "/out/meeting_transcription.txt");
+}
+
+#[test]
+fn test_low_confidence_event_only_below_threshold() {
+    let app = tauri::test::mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen(events::LOW_CONFIDENCE, move |event| {
+        let _ = tx.send(event.payload().to_string());
+    });
+
+    let mut result = sample_result("/audio/meeting.m4a");
+    result.confidence = Some(0.9);
+    emit_low_confidence(app.handle(), &result, 0.6);
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+    result.confidence = Some(0.3);
+    emit_low_confidence(app.handle(), &result, 0.6);
+    let payload = rx.recv_timeout(Duration::from_secs(1)).expect("low-confidence should be emitted");
+    let event: serde_json::Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(event["file_path"], "/audio/meeting.m4a");
+    assert_eq!(event["confidence"], 0.3);
+    assert_eq!(event["threshold"], 0.6);
+}
+
+#[test]
+fn test_no_speech_event_only_for_empty_results() {
+    let app = tauri::test::mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen(events::NO_SPEECH, move |event| {
+        let _ = tx.send(event.payload().to_string());
+    });
+
+    let mut result = sample_result("/audio/silence.wav");
+    emit_no_speech(app.handle(), &result);
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+    result.no_speech_detected = true;
+    emit_no_speech(app.handle(), &result);
+    let payload = rx.recv_timeout(Duration::from_secs(1)).expect("no-speech should be emitted");
+    let event: serde_json::Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(event["file_path"], "/audio/silence.wav");
+}
+
+// Multi-threaded so the output readers keep running while the test blocks on the channel
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_log_stream_forwards_cli_output() {
+    use std::sync::Arc;
+    use tauri_gui_app_lib::cli::{CliManager, ProgressCallback};
+    use tauri_gui_app_lib::models::{AppSettings, CliLogLine, CliLogStream};
+    use tauri_gui_app_lib::{disable_log_stream, enable_log_stream, log_stream_callback};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let binary = common::write_fake_cli(temp_dir.path(), "echo 'loading model'
+echo 'decoder warning' >&2
+write_output done");
+    let audio = temp_dir.path().join("memo.m4a");
+    std::fs::write(&audio, b"audio").unwrap();
+
+    let app = tauri::test::mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen(events::CLI_LOG, move |event| {
+        let _ = tx.send(event.payload().to_string());
+    });
+
+    let settings = AppSettings {
+        output_directory: temp_dir.path().to_string_lossy().to_string(),
+        retry_count: 0,
+        ..AppSettings::default()
+    };
+    let manager = CliManager::new_dev()
+        .with_cli_binary_path(binary.to_string_lossy())
+        .with_log_line_callback(log_stream_callback(app.handle().clone(), "log-job".to_string()));
+    let progress: ProgressCallback = Arc::new(|_| {});
+    let audio_path = audio.to_string_lossy().to_string();
+
+    // Nothing is forwarded until the stream is enabled
+    manager.process_file(&audio_path, &settings, Some(progress.clone())).await.unwrap();
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+    enable_log_stream("log-job");
+    manager.process_file(&audio_path, &settings, Some(progress)).await.unwrap();
+    assert!(disable_log_stream("log-job"));
+
+    // Output readers may still be draining after the process exits
+    let mut lines: Vec<CliLogLine> = (0..2)
+        .map(|_| rx.recv_timeout(Duration::from_secs(1)).expect("cli-log should be emitted"))
+        .map(|payload| serde_json::from_str(&payload).unwrap())
+        .collect();
+    lines.sort_by_key(|line| line.stream == CliLogStream::Stderr);
+    let received: Vec<(CliLogStream, &str)> = lines.iter().map(|line| (line.stream, line.line.as_str())).collect();
+    assert_eq!(received, vec![(CliLogStream::Stdout, "loading model"), (CliLogStream::Stderr, "decoder warning")]);
+    assert!(lines.iter().all(|line| line.job_id.as_deref() == Some("log-job")));
+}
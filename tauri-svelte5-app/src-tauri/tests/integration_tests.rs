@@ -33,7 +33,7 @@ async fn test_cli_integration_with_mock_command() {
 async fn test_cli_integration_file_processing_structure() {
     let temp_dir = tempdir().unwrap();
     let file_path = temp_dir.path().join("test.m4a");
-    File::create(&file_path).unwrap();
+    std::fs::write(&file_path, b"audio").unwrap();
     
     let manager = CliManager::new("echo".to_string()); // Mock with echo
     let settings = AppSettings::default();
@@ -64,8 +64,8 @@ async fn test_batch_processing_structure() {
     let temp_dir = tempdir().unwrap();
     let file1 = temp_dir.path().join("test1.m4a");
     let file2 = temp_dir.path().join("test2.m4a");
-    File::create(&file1).unwrap();
-    File::create(&file2).unwrap();
+    std::fs::write(&file1, b"audio").unwrap();
+    std::fs::write(&file2, b"audio").unwrap();
     
     let manager = CliManager::new("echo".to_string());
     let settings = AppSettings::default();
@@ -112,8 +112,8 @@ async fn test_batch_processing_with_progress_callback() {
     let temp_dir = tempdir().unwrap();
     let file1 = temp_dir.path().join("test1.m4a");
     let file2 = temp_dir.path().join("test2.m4a");
-    File::create(&file1).unwrap();
-    File::create(&file2).unwrap();
+    std::fs::write(&file1, b"audio").unwrap();
+    std::fs::write(&file2, b"audio").unwrap();
     
     let manager = CliManager::new("echo".to_string());
     let settings = AppSettings::default();
@@ -165,8 +165,8 @@ async fn test_file_validation_batch() {
     let valid_file2 = temp_dir.path().join("test2.wav");
     let invalid_file = temp_dir.path().join("test.txt");
     
-    File::create(&valid_file1).unwrap();
-    File::create(&valid_file2).unwrap();
+    std::fs::write(&valid_file1, b"audio").unwrap();
+    std::fs::write(&valid_file2, b"audio").unwrap();
     File::create(&invalid_file).unwrap();
     
     let file_paths = vec![
@@ -197,7 +197,7 @@ async fn test_concurrent_processing_simulation() {
     for i in 0..3 {
         let temp_dir = tempdir().unwrap();
         let file_path = temp_dir.path().join(format!("test{}.m4a", i));
-        File::create(&file_path).unwrap();
+        std::fs::write(&file_path, b"audio").unwrap();
         
         let audio_file = utils::create_audio_file(file_path.to_str().unwrap()).unwrap();
         
@@ -211,6 +211,7 @@ async fn test_concurrent_processing_simulation() {
             estimated_completion: None,
             is_cancelled: false,
             can_cancel: true,
+            concurrency: 1,
         };
         
         jobs.push(job);
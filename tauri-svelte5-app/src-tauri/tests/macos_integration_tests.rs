@@ -1,6 +1,15 @@
 #[cfg(test)]
 mod macos_integration_tests {
-    use tauri_gui_app_lib::macos_integration::{MacOSIntegration, NotificationOptions, DockBadgeInfo};
+    use tauri_gui_app_lib::error::AppResult;
+    use tauri_gui_app_lib::macos_integration::{MacOSIntegration, NotificationOptions, NotificationSender, DockBadgeInfo};
+
+    struct NoopSender;
+
+    impl NotificationSender for NoopSender {
+        fn send_notification(&self, _options: &NotificationOptions) -> AppResult<()> {
+            Ok(())
+        }
+    }
 
     #[test]
     fn test_macos_integration_creation() {
@@ -92,7 +101,7 @@ mod macos_integration_tests {
             identifier: Some("test-notification".to_string()),
         };
         
-        let result = integration.show_notification(options);
+        let result = integration.show_notification(&NoopSender, options, true);
         assert!(result.is_ok());
     }
 
@@ -223,7 +232,7 @@ mod macos_integration_tests {
             identifier: None,
         };
         
-        let result = integration.show_notification(options);
+        let result = integration.show_notification(&NoopSender, options, true);
         assert!(result.is_ok());
     }
 
@@ -239,7 +248,7 @@ mod macos_integration_tests {
             identifier: None,
         };
         
-        let result = integration.show_notification(options);
+        let result = integration.show_notification(&NoopSender, options, true);
         assert!(result.is_ok());
     }
 }
\ No newline at end of file
@@ -4,6 +4,8 @@ import type {
   AudioFile, 
   TranscriptionResult, 
   AppSettings, 
+  CancellationResult,
+  FormatInfo,
   ProcessingProgress 
 } from '../types';
 
@@ -24,9 +26,12 @@ export interface TauriService {
   // Settings operations
   loadSettings(): Promise<AppSettings>;
   saveSettings(settings: AppSettings): Promise<void>;
+  getSettingsWarnings(settings: AppSettings): Promise<string[]>;
   
   // System operations
   getSupportedFormats(): Promise<string[]>;
+  getSupportedFormatsDetailed(): Promise<FormatInfo[]>;
+  checkFfmpegAvailability(): Promise<boolean>;
   checkCliAvailability(): Promise<boolean>;
   
   // Event listeners
@@ -150,7 +155,8 @@ class TauriServiceImpl implements TauriService {
 
   async cancelProcessing(jobId: string): Promise<boolean> {
     try {
-      return await invoke<boolean>('cancel_processing_job', { jobId });
+      const result = await invoke<CancellationResult>('cancel_processing_job', { jobId });
+      return result.cancelled;
     } catch (error) {
       throw new Error(`Failed to cancel processing: ${error}`);
     }
@@ -206,40 +212,67 @@ class TauriServiceImpl implements TauriService {
     }
   }
 
+  private toBackendSettings(settings: AppSettings) {
+    return {
+      language: settings.language,
+      model_size: settings.modelSize,
+      output_directory: settings.outputDirectory,
+      include_metadata: settings.includeMetadata,
+      auto_save: settings.autoSave,
+      theme: settings.theme,
+      max_concurrent_jobs: settings.maxConcurrentJobs,
+      enable_gpu_acceleration: settings.enableGpuAcceleration,
+      memory_optimization: settings.memoryOptimization,
+      enable_voice_activity_detection: settings.enableVoiceActivityDetection,
+      noise_reduction: settings.noiseReduction,
+      output_format: settings.outputFormat,
+      compact_mode: settings.compactMode,
+      show_advanced_options: settings.showAdvancedOptions,
+      enable_notifications: settings.enableNotifications,
+      auto_check_updates: settings.autoCheckUpdates,
+    };
+  }
+
   async saveSettings(settings: AppSettings): Promise<void> {
     try {
-      // Convert frontend settings to backend format
-      const backendSettings = {
-        language: settings.language,
-        model_size: settings.modelSize,
-        output_directory: settings.outputDirectory,
-        include_metadata: settings.includeMetadata,
-        auto_save: settings.autoSave,
-        theme: settings.theme,
-        max_concurrent_jobs: settings.maxConcurrentJobs,
-        enable_gpu_acceleration: settings.enableGpuAcceleration,
-        memory_optimization: settings.memoryOptimization,
-        enable_voice_activity_detection: settings.enableVoiceActivityDetection,
-        noise_reduction: settings.noiseReduction,
-        output_format: settings.outputFormat,
-        compact_mode: settings.compactMode,
-        show_advanced_options: settings.showAdvancedOptions,
-        enable_notifications: settings.enableNotifications,
-        auto_check_updates: settings.autoCheckUpdates,
-      };
-
-      await invoke('save_settings', { settings: backendSettings });
+      await invoke('save_settings', { settings: this.toBackendSettings(settings) });
     } catch (error) {
       throw new Error(`Failed to save settings: ${error}`);
     }
   }
 
+  async getSettingsWarnings(settings: AppSettings): Promise<string[]> {
+    try {
+      // Checks free memory and GPUs, so call it once when settings are saved
+      return await invoke<string[]>('get_settings_warnings', { settings: this.toBackendSettings(settings) });
+    } catch (error) {
+      return [];
+    }
+  }
+
   async getSupportedFormats(): Promise<string[]> {
     try {
       return await invoke<string[]>('get_supported_formats');
     } catch (error) {
       // Return default formats if command fails
-      return ['.m4a', '.wav', '.mp3', '.aac', '.flac', '.ogg'];
+      return ['.m4a', '.wav', '.mp3', '.aac', '.flac', '.ogg', '.opus', '.wma'];
+    }
+  }
+
+  async getSupportedFormatsDetailed(): Promise<FormatInfo[]> {
+    try {
+      return await invoke<FormatInfo[]>('get_supported_formats_detailed');
+    } catch (error) {
+      return [];
+    }
+  }
+
+  async checkFfmpegAvailability(): Promise<boolean> {
+    try {
+      const check = await invoke<{ ffmpeg_available: boolean }>('check_system_dependencies');
+      return check.ffmpeg_available;
+    } catch (error) {
+      return false;
     }
   }
 
@@ -8,6 +8,7 @@ export interface AudioFile {
   format: string;
   duration?: number;
   status: 'pending' | 'processing' | 'completed' | 'error';
+  hash?: string;
 }
 
 export interface TranscriptionResult {
@@ -41,14 +42,17 @@ export interface ProcessingJob {
   estimatedCompletion?: number;
   isCancelled?: boolean;
   canCancel?: boolean;
+  concurrency?: number;
 }
 
 export type ProcessingStage = 
   | 'initializing'
+  | 'downloading'
   | 'loading_model'
   | 'preprocessing'
   | 'transcribing'
   | 'postprocessing'
+  | 'finalizing'
   | 'saving';
 
 export interface AppSettings {
@@ -120,6 +124,20 @@ export interface ErrorSuggestion {
   priority: 'primary' | 'secondary';
 }
 
+export interface CancellationResult {
+  cancelled: boolean;
+  completed: number;
+  remaining: number;
+}
+
+export interface FormatInfo {
+  extension: string;
+  description: string;
+  mime_type: string;
+  requires_ffmpeg: boolean;
+  is_lossless: boolean;
+}
+
 export interface SystemInfo {
   platform: string;
   version: string;
@@ -241,6 +241,49 @@ class TestCLIOptions:
             
             mock_transcriber.assert_called_with(model_size='large')
     
+    @patch('speech_to_text.cli.AudioProcessor')
+    @patch('speech_to_text.cli.SpeechTranscriber')
+    @patch('speech_to_text.cli.TextExporter')
+    def test_decoding_options(self, mock_exporter, mock_transcriber, mock_processor):
+        """Test temperature and beam size reach the transcriber's decoding options."""
+        mock_transcriber_instance = Mock(decode_options={})
+        mock_transcriber.return_value = mock_transcriber_instance
+        mock_transcriber_instance.transcribe_file.return_value = Mock(error_message=None, transcribed_text="Hello")
+        mock_exporter.return_value.save_transcription_result.return_value = "/path/to/output.txt"
+        
+        result = self.runner.invoke(transcribe, [
+            self.test_audio_file,
+            '--temperature', '0.2',
+            '--beam-size', '8'
+        ])
+        
+        assert result.exit_code == 0
+        assert mock_transcriber_instance.decode_options == {'temperature': 0.2, 'beam_size': 8}
+        
+        # Out-of-range values are rejected before anything runs
+        result = self.runner.invoke(transcribe, [self.test_audio_file, '--beam-size', '11'])
+        assert result.exit_code != 0
+    
+    @patch('speech_to_text.cli.AudioProcessor')
+    @patch('speech_to_text.cli.SpeechTranscriber')
+    @patch('speech_to_text.cli.TextExporter')
+    def test_initial_prompt_option(self, mock_exporter, mock_transcriber, mock_processor):
+        """Test the initial prompt is passed to Whisper, including one that starts with a dash."""
+        mock_transcriber_instance = Mock(decode_options={})
+        mock_transcriber.return_value = mock_transcriber_instance
+        mock_transcriber_instance.transcribe_file.return_value = Mock(error_message=None, transcribed_text="Hello")
+        mock_exporter.return_value.save_transcription_result.return_value = "/path/to/output.txt"
+        
+        result = self.runner.invoke(transcribe, [
+            self.test_audio_file,
+            '--initial-prompt=-Quarterly review: Kubernetes, "gRPC"'
+        ])
+        
+        assert result.exit_code == 0
+        assert mock_transcriber_instance.decode_options == {
+            'initial_prompt': '-Quarterly review: Kubernetes, "gRPC"'
+        }
+    
     @patch('speech_to_text.cli.AudioProcessor')
     @patch('speech_to_text.cli.SpeechTranscriber')
     @patch('speech_to_text.cli.TextExporter')
@@ -99,6 +99,28 @@ class TestSpeechTranscriber:
                 if os.path.exists(temp_path):
                     os.unlink(temp_path)
     
+    def test_transcribe_file_auto_language(self):
+        """Test that "auto" lets Whisper detect the language and reports the detected one."""
+        with patch('src.speech_to_text.transcriber.whisper.load_model') as mock_load:
+            mock_model = Mock()
+            mock_model.transcribe.return_value = {"text": "Hello there", "language": "en"}
+            mock_load.return_value = mock_model
+            
+            with tempfile.NamedTemporaryFile(suffix=".wav", delete=False) as temp_file:
+                temp_path = temp_file.name
+            
+            try:
+                transcriber = SpeechTranscriber("base")
+                result = transcriber.transcribe_file(temp_path, "auto")
+                
+                assert result.error_message is None
+                assert result.language == "en"
+                assert mock_model.transcribe.call_args.kwargs["language"] is None
+            
+            finally:
+                if os.path.exists(temp_path):
+                    os.unlink(temp_path)
+    
     def test_transcribe_file_empty_result(self):
         """Test transcription with empty result."""
         with patch('src.speech_to_text.transcriber.whisper.load_model') as mock_load: